@@ -0,0 +1,135 @@
+// Benchmarks do curso: cargo bench --bench data_structures_bench
+//
+// Os exemplos são arquivos soltos, compilados com rustc; os módulos medidos
+// aqui entram pelo caminho do arquivo.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+#[path = "../modulo-02-ownership-borrowing/exemplos/busca.rs"]
+#[allow(dead_code)]
+mod busca;
+
+#[path = "../modulo-01-fundamentos-rust/exemplos/memoizacao.rs"]
+#[allow(dead_code, unused_imports)]
+mod memoizacao;
+
+use busca::{AhoCorasick, Busca, Horspool, Kmp, Modo};
+use memoizacao::{CacheIlimitado, CacheLru, Memo, Recursao};
+
+// ~100 KB de texto em português, com o padrão só no final
+fn texto_de_teste() -> String {
+    let paragrafo = "A programação em Rust garante segurança de memória sem coletor de lixo. \
+                     Ownership, borrowing e lifetimes são verificados em tempo de compilação. ";
+    let mut texto = paragrafo.repeat(100_000 / paragrafo.len());
+    texto.push_str("concorrência sem medo");
+    texto
+}
+
+// Busca da primeira ocorrência: o algoritmo ingênuo contra KMP e Horspool
+fn bench_primeira_ocorrencia(c: &mut Criterion) {
+    let texto = texto_de_teste();
+    let mut grupo = c.benchmark_group("primeira_ocorrencia");
+    grupo.throughput(Throughput::Bytes(texto.len() as u64));
+
+    for padrao in ["sem medo", "concorrência sem medo"] {
+        grupo.bench_with_input(BenchmarkId::new("ingenua", padrao), padrao, |b, padrao| {
+            b.iter(|| busca::ingenua(black_box(&texto), black_box(padrao)))
+        });
+        let kmp = Kmp::new(padrao);
+        grupo.bench_with_input(BenchmarkId::new("kmp", padrao), padrao, |b, _| {
+            b.iter(|| kmp.primeira(black_box(&texto)))
+        });
+        let horspool = Horspool::new(padrao);
+        grupo.bench_with_input(BenchmarkId::new("horspool", padrao), padrao, |b, _| {
+            b.iter(|| horspool.primeira(black_box(&texto)))
+        });
+    }
+    grupo.finish();
+}
+
+// Vários padrões: uma passada de Aho–Corasick contra uma busca por padrão
+fn bench_varios_padroes(c: &mut Criterion) {
+    let texto = texto_de_teste();
+    let padroes = ["Rust", "memória", "lifetimes", "compilação", "medo"];
+    let mut grupo = c.benchmark_group("varios_padroes");
+    grupo.throughput(Throughput::Bytes(texto.len() as u64));
+
+    grupo.bench_function("horspool_por_padrao", |b| {
+        let buscas: Vec<Horspool> = padroes.iter().map(|padrao| Horspool::new(padrao)).collect();
+        b.iter(|| {
+            buscas
+                .iter()
+                .map(|busca| busca.ocorrencias(black_box(&texto), Modo::Sobrepostas).count())
+                .sum::<usize>()
+        })
+    });
+    grupo.bench_function("aho_corasick", |b| {
+        let automato = AhoCorasick::new(&padroes);
+        b.iter(|| automato.ocorrencias(black_box(&texto), Modo::Sobrepostas).count())
+    });
+    grupo.finish();
+}
+
+// As recursões de funcoes.rs, direta e em recursão aberta para o memo
+fn fibonacci(n: u32) -> u64 {
+    match n {
+        0 => 0,
+        1 => 1,
+        _ => fibonacci(n - 1) + fibonacci(n - 2),
+    }
+}
+
+fn fibonacci_memo(fib: Recursao<u32, u64>, n: u32) -> u64 {
+    match n {
+        0 => 0,
+        1 => 1,
+        _ => fib(n - 1) + fib(n - 2),
+    }
+}
+
+fn fatorial(n: u32) -> u64 {
+    if n <= 1 {
+        1
+    } else {
+        n as u64 * fatorial(n - 1)
+    }
+}
+
+fn fatorial_memo(fat: Recursao<u32, u64>, n: u32) -> u64 {
+    if n <= 1 {
+        1
+    } else {
+        n as u64 * fat(n - 1)
+    }
+}
+
+// Memoizado contra recursão direta. O memo é criado dentro da iteração:
+// mede o custo de preencher o cache, não só o de consultá-lo.
+fn bench_memoizacao(c: &mut Criterion) {
+    let mut grupo = c.benchmark_group("fibonacci");
+    for n in [20, 25] {
+        grupo.bench_with_input(BenchmarkId::new("direta", n), &n, |b, &n| b.iter(|| fibonacci(black_box(n))));
+        grupo.bench_with_input(BenchmarkId::new("memo", n), &n, |b, &n| {
+            b.iter(|| Memo::nova(fibonacci_memo, CacheIlimitado::new()).chamar(black_box(n)))
+        });
+        grupo.bench_with_input(BenchmarkId::new("memo_lru_3", n), &n, |b, &n| {
+            b.iter(|| Memo::nova(fibonacci_memo, CacheLru::new(3)).chamar(black_box(n)))
+        });
+    }
+    grupo.finish();
+
+    // O fatorial não repete subproblemas e cada multiplicação é barata: aqui
+    // o memo só acrescenta o custo do cache
+    let mut grupo = c.benchmark_group("fatoriais_0_a_20");
+    grupo.bench_function("direta", |b| b.iter(|| (0..=black_box(20)).map(fatorial).sum::<u64>()));
+    grupo.bench_function("memo", |b| {
+        b.iter(|| {
+            let mut memo = Memo::nova(fatorial_memo, CacheIlimitado::new());
+            (0..=black_box(20)).map(|n| memo.chamar(n)).sum::<u64>()
+        })
+    });
+    grupo.finish();
+}
+
+criterion_group!(benches, bench_primeira_ocorrencia, bench_varios_padroes, bench_memoizacao);
+criterion_main!(benches);
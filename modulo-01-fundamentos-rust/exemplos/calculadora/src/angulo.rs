@@ -0,0 +1,92 @@
+use std::f64::consts::PI;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+// Unidade em que o usuário digita (e lê) ângulos. As funções de `operacoes`
+// trabalham sempre em radianos; a conversão acontece em `Operacao::aplicar`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnidadeAngulo {
+    #[default]
+    Graus,
+    Radianos,
+    Grados,
+}
+
+impl UnidadeAngulo {
+    pub const TODAS: [UnidadeAngulo; 3] = [UnidadeAngulo::Graus, UnidadeAngulo::Radianos, UnidadeAngulo::Grados];
+
+    pub fn descricao(&self) -> &'static str {
+        match self {
+            UnidadeAngulo::Graus => "graus, volta completa = 360 (padrão)",
+            UnidadeAngulo::Radianos => "radianos, volta completa = 2π",
+            UnidadeAngulo::Grados => "grados, volta completa = 400",
+        }
+    }
+
+    pub fn para_radianos(&self, angulo: f64) -> f64 {
+        match self {
+            UnidadeAngulo::Graus => angulo.to_radians(),
+            UnidadeAngulo::Radianos => angulo,
+            UnidadeAngulo::Grados => angulo / 200.0 * PI,
+        }
+    }
+
+    pub fn de_radianos(&self, radianos: f64) -> f64 {
+        match self {
+            UnidadeAngulo::Graus => radianos.to_degrees(),
+            UnidadeAngulo::Radianos => radianos,
+            UnidadeAngulo::Grados => radianos / PI * 200.0,
+        }
+    }
+}
+
+impl fmt::Display for UnidadeAngulo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nome = match self {
+            UnidadeAngulo::Graus => "deg",
+            UnidadeAngulo::Radianos => "rad",
+            UnidadeAngulo::Grados => "grad",
+        };
+        write!(f, "{}", nome)
+    }
+}
+
+impl FromStr for UnidadeAngulo {
+    type Err = String;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        match texto.trim().to_lowercase().as_str() {
+            "deg" | "graus" => Ok(UnidadeAngulo::Graus),
+            "rad" | "radianos" => Ok(UnidadeAngulo::Radianos),
+            "grad" | "grados" => Ok(UnidadeAngulo::Grados),
+            _ => Err(format!("Unidade de ângulo desconhecida: {}", texto)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unidade_texto() {
+        for unidade in UnidadeAngulo::TODAS {
+            assert_eq!(unidade.to_string().parse::<UnidadeAngulo>(), Ok(unidade));
+        }
+        assert_eq!("Radianos".parse::<UnidadeAngulo>(), Ok(UnidadeAngulo::Radianos));
+        assert!("voltas".parse::<UnidadeAngulo>().is_err());
+    }
+
+    #[test]
+    fn test_conversoes() {
+        assert_eq!(UnidadeAngulo::Graus.para_radianos(180.0), PI);
+        assert_eq!(UnidadeAngulo::Grados.para_radianos(200.0), PI);
+        assert_eq!(UnidadeAngulo::Radianos.para_radianos(1.5), 1.5);
+        for unidade in UnidadeAngulo::TODAS {
+            let ida_e_volta = unidade.de_radianos(unidade.para_radianos(30.0));
+            assert!((ida_e_volta - 30.0).abs() < 1e-12);
+        }
+    }
+}
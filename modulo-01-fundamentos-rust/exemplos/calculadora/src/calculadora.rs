@@ -1,52 +1,848 @@
-use std::io;
+use std::fs;
 
-pub fn ler_numero() -> f64 {
+use num_bigint::BigInt;
+
+use crate::angulo::UnidadeAngulo;
+use crate::calculo::{self, ErroCalculo};
+use crate::equacoes::{self, Criterios, Equacao, ErroEquacao, Raiz};
+use crate::erro::CalcError;
+use crate::estatistica::{self, ErroEstatistica};
+use crate::expressao::{self, ErroExpressao, Expr};
+use crate::grafico::{self, Estilo, Grafico};
+use crate::matriz::{self, ErroMatriz, Matriz};
+use crate::numero::{no_modo, FormaComplexa, Modo, Numero, Valor};
+use crate::programador::{Aritmetica, Base, FormatoPalavra, TamanhoPalavra};
+use crate::registro::{self, Angulo, Operacao, OperacaoBits};
+use crate::sessao::Sessao;
+use crate::terminal;
+use crate::unidades::{self, Dimensao};
+
+// Lê um número no formato do modo atual (ex: "0.1" vira 1/10 no modo racional)
+pub fn ler_numero<N: Numero>() -> N {
     loop {
-        let mut entrada = String::new();
-        io::stdin()
-            .read_line(&mut entrada)
-            .expect("Falha ao ler entrada");
-        
-        match entrada.trim().parse() {
+        match N::de_texto(terminal::ler_linha("").trim()) {
             Ok(num) => return num,
+            Err(erro @ CalcError::Overflow) => {
+                println!("{}. Digite um número menor:", erro);
+                continue;
+            }
             Err(_) => {
-                println!("Por favor, digite um número válido:");
+                println!("Por favor, digite um número válido no modo {}:", N::MODO);
+                continue;
+            }
+        }
+    }
+}
+
+pub fn executar(operacao: &Operacao, sessao: &mut Sessao) {
+    no_modo!(sessao.modo, N => executar_operacao::<N>(operacao, sessao))
+}
+
+pub fn executar_operacao<N: Numero>(operacao: &Operacao, sessao: &mut Sessao) {
+    println!("\n=== {} ===", operacao.nome.to_uppercase());
+    if operacao.angulo != Angulo::Nenhum {
+        println!("Ângulos em {} ({})", sessao.angulo, sessao.angulo.descricao());
+    }
+    if N::MODO == Modo::Complexo {
+        println!("Digite complexos na forma a + bi (ex: 3 - 4i, 2i)");
+    }
+
+    let (argumentos, texto) = if operacao.aridade() == 1 {
+        println!("Digite o número:");
+        let numero: N = ler_numero();
+        let texto = format!("{}({})", operacao.simbolo, numero);
+        (vec![numero], texto)
+    } else {
+        println!("Digite o primeiro número:");
+        let a: N = ler_numero();
+
+        println!("Digite o segundo número:");
+        let b: N = ler_numero();
+
+        let texto = format!("{} {} {}", a, operacao.simbolo, b);
+        (vec![a, b], texto)
+    };
+
+    match operacao.aplicar(argumentos, sessao.angulo) {
+        Ok(resultado) => {
+            let valor = resultado.clone().para_valor();
+            let exibido = match (sessao.forma, &valor) {
+                (FormaComplexa::Polar, Valor::Complexo(_)) => sessao.formatar(&valor),
+                _ => resultado.exibir(),
+            };
+            println!("Resultado: {} = {}", texto, exibido);
+            sessao.registrar(&texto, valor);
+        }
+        Err(erro) => println!("Erro: {}", erro),
+    }
+}
+
+// Lê um inteiro para o modo programador (ex: 255, -1, 0xFF, 0b1010)
+fn ler_palavra(formato: &FormatoPalavra) -> u64 {
+    loop {
+        match formato.ler(&terminal::ler_linha("")) {
+            Some(bits) => return bits,
+            None => println!("Por favor, digite um número que caiba em {}:", formato),
+        }
+    }
+}
+
+// Número da opção escolhida em um submenu com `total` opções
+fn ler_opcao(total: usize) -> Option<usize> {
+    let entrada = terminal::ler_linha(&format!("Digite sua escolha (1-{}): ", total));
+    match entrada.trim().parse::<usize>() {
+        Ok(n) if n >= 1 && n <= total => Some(n),
+        _ => None,
+    }
+}
+
+pub fn executar_conversao(sessao: &mut Sessao) {
+    println!("\n=== CONVERSÃO DE UNIDADES ===");
+    let mut dimensoes: Vec<Dimensao> = Vec::new();
+    for unidade in unidades::UNIDADES {
+        if !dimensoes.contains(&unidade.dimensao) {
+            dimensoes.push(unidade.dimensao);
+        }
+    }
+    for dimensao in dimensoes {
+        let simbolos: Vec<&str> = unidades::UNIDADES
+            .iter()
+            .filter(|unidade| unidade.dimensao == dimensao)
+            .map(|unidade| unidade.simbolo)
+            .collect();
+        println!("{}: {}", dimensao, simbolos.join(", "));
+    }
+
+    println!("Digite o valor:");
+    let valor: f64 = ler_numero();
+    println!("Digite a unidade de origem (ex: km, °F, km/h):");
+    let origem = ler_texto();
+    println!("Digite a unidade de destino:");
+    let destino = ler_texto();
+
+    // Reaproveita o analisador de expressões: vira a linha "valor origem to destino"
+    let linha = format!("{} {} to {}", valor, origem, destino);
+    avaliar_na_sessao(&linha, sessao);
+}
+
+// Quantidade de subintervalos do Simpson composto: inteiro positivo, sem fração
+fn ler_subintervalos() -> usize {
+    loop {
+        match ler_texto().parse::<usize>() {
+            Ok(n) if n > 0 => return n,
+            _ => println!("Por favor, digite um número inteiro positivo:"),
+        }
+    }
+}
+
+fn ler_texto() -> String {
+    terminal::ler_linha("").trim().to_string()
+}
+
+pub fn executar_estatistica(sessao: &mut Sessao) {
+    // Dados da última lista lida, usados pela opção de percentil
+    let mut dados: Vec<f64> = Vec::new();
+
+    loop {
+        println!("\n=== ESTATÍSTICA ===");
+        if !dados.is_empty() {
+            println!("Dados atuais: {} valores", dados.len());
+        }
+        println!("1. Digitar uma lista de números");
+        println!("2. Ler uma coluna de arquivo CSV");
+        println!("3. Percentil dos dados atuais");
+        println!("4. Regressão linear (digitar x e y)");
+        println!("5. Regressão linear de arquivo CSV");
+        println!("6. Voltar");
+
+        let resultado = match ler_opcao(6) {
+            Some(1) => {
+                println!("Digite os números separados por vírgula ou espaço:");
+                estatistica::ler_lista(&ler_texto()).and_then(|lista| carregar_dados(lista, &mut dados, sessao))
+            }
+            Some(2) => ler_colunas_csv(1).and_then(|mut colunas| {
+                carregar_dados(colunas.remove(0), &mut dados, sessao)
+            }),
+            Some(3) => {
+                println!("Digite o percentil (0 a 100):");
+                let p: f64 = ler_numero();
+                estatistica::percentil(&dados, p).map(|valor| {
+                    let texto = format!("p{}({} valores)", p, dados.len());
+                    println!("Resultado: {} = {}", texto, valor);
+                    sessao.registrar(&texto, Valor::Real(valor));
+                })
+            }
+            Some(4) => {
+                println!("Digite os valores de x:");
+                let x = estatistica::ler_lista(&ler_texto());
+                println!("Digite os valores de y:");
+                let y = estatistica::ler_lista(&ler_texto());
+                x.and_then(|x| Ok((x, y?))).and_then(|(x, y)| mostrar_regressao(&x, &y))
+            }
+            Some(5) => ler_colunas_csv(2).and_then(|colunas| mostrar_regressao(&colunas[0], &colunas[1])),
+            Some(_) => break,
+            None => {
+                println!("Opção inválida! Tente novamente.");
+                continue;
+            }
+        };
+        if let Err(erro) = resultado {
+            println!("Erro: {}", erro);
+        }
+    }
+}
+
+// Mostra o resumo da lista e a guarda como dados atuais; a média vai para o
+// histórico para poder ser usada em expressões
+fn carregar_dados(lista: Vec<f64>, dados: &mut Vec<f64>, sessao: &mut Sessao) -> Result<(), ErroEstatistica> {
+    let resumo = estatistica::resumir(&lista)?;
+    let acumulador = &resumo.acumulador;
+    let opcional = |valor: Option<f64>| valor.map_or("-".to_string(), |valor| valor.to_string());
+    let modas: Vec<String> = resumo.modas.iter().map(f64::to_string).collect();
+
+    println!("Quantidade: {}", acumulador.contagem());
+    println!("Média: {}", opcional(acumulador.media()));
+    println!("Mediana: {}", resumo.mediana);
+    println!("Moda: {}", if modas.is_empty() { "nenhum valor se repete".to_string() } else { modas.join(", ") });
+    println!("Variância: {} (amostral), {} (populacional)",
+        opcional(acumulador.variancia_amostral()), opcional(acumulador.variancia_populacional()));
+    println!("Desvio padrão: {} (amostral), {} (populacional)",
+        opcional(acumulador.desvio_padrao_amostral()), opcional(acumulador.desvio_padrao_populacional()));
+    println!("Mínimo: {}  Máximo: {}", opcional(acumulador.minimo()), opcional(acumulador.maximo()));
+    println!("Quartis: {}, {}, {}", resumo.quartis[0], resumo.quartis[1], resumo.quartis[2]);
+
+    if let Some(media) = acumulador.media() {
+        sessao.registrar(&format!("média({} valores)", lista.len()), Valor::Real(media));
+    }
+    *dados = lista;
+    Ok(())
+}
+
+fn mostrar_regressao(x: &[f64], y: &[f64]) -> Result<(), ErroEstatistica> {
+    let regressao = estatistica::regressao_linear(x, y)?;
+    println!("Reta: {} ({} pontos)", regressao, regressao.pontos);
+    println!("Correlação: r = {}, r² = {}", regressao.correlacao, regressao.correlacao.powi(2));
+
+    println!("Digite um x para prever y (linha vazia para pular):");
+    let texto = ler_texto();
+    if let Ok(x) = texto.parse::<f64>() {
+        println!("y({}) = {}", x, regressao.prever(x));
+    }
+    Ok(())
+}
+
+// Pede o caminho de um CSV e `quantidade` colunas (nome no cabeçalho ou posição)
+fn ler_colunas_csv(quantidade: usize) -> Result<Vec<Vec<f64>>, ErroEstatistica> {
+    println!("Digite o caminho do arquivo CSV:");
+    let caminho = ler_texto();
+    let conteudo = match fs::read_to_string(&caminho) {
+        Ok(conteudo) => conteudo,
+        Err(erro) => return Err(ErroEstatistica::Arquivo(format!("{}: {}", caminho, erro))),
+    };
+
+    let mut colunas = Vec::new();
+    for i in 0..quantidade {
+        match (quantidade, i) {
+            (1, _) => println!("Digite a coluna (nome ou número):"),
+            (_, 0) => println!("Digite a coluna de x (nome ou número):"),
+            _ => println!("Digite a coluna de y (nome ou número):"),
+        }
+        colunas.push(ler_texto());
+    }
+    let colunas: Vec<&str> = colunas.iter().map(String::as_str).collect();
+    estatistica::colunas_csv(&conteudo, &colunas)
+}
+
+pub fn executar_matrizes(sessao: &mut Sessao) {
+    loop {
+        println!("\n=== MATRIZES ===");
+        println!("1. Soma (A + B)");
+        println!("2. Multiplicação (A · B)");
+        println!("3. Transposta");
+        println!("4. Determinante");
+        println!("5. Inversa");
+        println!("6. Posto");
+        println!("7. Resolver sistema A · x = b");
+        println!("8. Voltar");
+
+        let resultado = match ler_opcao(8) {
+            Some(1) => ler_matriz("A").and_then(|a| a.somar(&ler_matriz("B")?)).map(mostrar_matriz),
+            Some(2) => ler_matriz("A").and_then(|a| a.multiplicar(&ler_matriz("B")?)).map(mostrar_matriz),
+            Some(3) => ler_matriz("A").map(|a| mostrar_matriz(a.transposta())),
+            Some(4) => ler_matriz("A").and_then(|a| {
+                let det = a.determinante()?;
+                let (linhas, colunas) = a.dimensoes();
+                let texto = format!("det({}x{})", linhas, colunas);
+                println!("Resultado: {} = {}", texto, det);
+                sessao.registrar(&texto, Valor::Real(det));
+                Ok(())
+            }),
+            Some(5) => ler_matriz("A").and_then(|a| a.inversa()).map(mostrar_matriz),
+            Some(6) => ler_matriz("A").map(|a| {
+                let (linhas, colunas) = a.dimensoes();
+                let texto = format!("posto({}x{})", linhas, colunas);
+                let posto = a.posto();
+                println!("Resultado: {} = {}", texto, posto);
+                sessao.registrar(&texto, Valor::Real(posto as f64));
+            }),
+            Some(7) => ler_matriz("A").and_then(|a| {
+                println!("Digite o vetor b (números separados por vírgula ou espaço):");
+                let b = matriz::ler_vetor(&ler_texto())?;
+                a.resolver(&Matriz::coluna(&b))
+            }).map(|x| {
+                println!("x =");
+                mostrar_matriz(x);
+            }),
+            Some(_) => break,
+            None => {
+                println!("Opção inválida! Tente novamente.");
+                continue;
+            }
+        };
+        if let Err(erro) = resultado {
+            println!("Erro: {}", erro);
+        }
+    }
+}
+
+// Uma matriz em uma linha só: linhas separadas por ';', ex: 1 2; 3 4
+fn ler_matriz(nome: &str) -> Result<Matriz, ErroMatriz> {
+    println!("Digite a matriz {} (linhas separadas por ';', ex: 1 2; 3 4):", nome);
+    matriz::ler_matriz(&ler_texto())
+}
+
+fn mostrar_matriz(matriz: Matriz) {
+    println!("{}", matriz);
+}
+
+pub fn executar_equacoes(sessao: &mut Sessao) {
+    loop {
+        println!("\n=== EQUAÇÕES ===");
+        println!("1. Resolver equação (ex: x^2 - 2 = 0, cos(x) = x)");
+        println!("2. Bisseção em um intervalo");
+        println!("3. Newton a partir de um chute inicial");
+        println!("4. Brent em um intervalo");
+        println!("5. Voltar");
+
+        let opcao = match ler_opcao(5) {
+            Some(5) => break,
+            Some(opcao) => opcao,
+            None => {
+                println!("Opção inválida! Tente novamente.");
+                continue;
+            }
+        };
+
+        println!("Digite a equação:");
+        let texto = ler_texto();
+        let resultado = Equacao::analisar(&texto, sessao).and_then(|equacao| {
+            let f = |x| equacao.avaliar(x, sessao);
+            let criterios = Criterios::default();
+            let raiz = match opcao {
+                1 => {
+                    mostrar_solucao(&texto, equacao.resolver(sessao)?, sessao);
+                    return Ok(());
+                }
+                2 | 4 => {
+                    println!("Digite o início do intervalo:");
+                    let a: f64 = ler_numero();
+                    println!("Digite o fim do intervalo:");
+                    let b: f64 = ler_numero();
+                    if opcao == 2 {
+                        equacoes::bissecao(f, a, b, criterios)?
+                    } else {
+                        equacoes::brent(f, a, b, criterios)?
+                    }
+                }
+                _ => {
+                    println!("Digite o chute inicial:");
+                    let x0: f64 = ler_numero();
+                    equacoes::newton(f, |x| equacoes::derivada_numerica(&f, x), x0, criterios)?
+                }
+            };
+            mostrar_raiz(&texto, &equacao.incognita, raiz, sessao);
+            Ok(())
+        });
+        if let Err(erro) = resultado {
+            println!("Erro: {}", erro);
+        }
+    }
+}
+
+// Cada raiz vai para o histórico; complexas só aparecem para polinômios
+fn mostrar_solucao(texto: &str, solucao: equacoes::Solucao, sessao: &mut Sessao) {
+    println!("Método: {}", solucao.metodo);
+    for raiz in solucao.raizes {
+        let valor = if raiz.eh_real() { Valor::Real(raiz.re()) } else { Valor::Complexo(raiz) };
+        sessao.registrar(&format!("solve {}", texto), valor.clone());
+        println!("${} = {} = {}", sessao.historico.len(), solucao.incognita, sessao.formatar(&valor));
+    }
+}
+
+fn mostrar_raiz(texto: &str, incognita: &str, raiz: Raiz, sessao: &mut Sessao) {
+    sessao.registrar(&format!("solve {}", texto), Valor::Real(raiz.x));
+    println!("${} = {} = {} ({} iterações)", sessao.historico.len(), incognita, raiz.x, raiz.iteracoes);
+}
+
+pub fn executar_calculo(sessao: &mut Sessao) {
+    loop {
+        println!("\n=== CÁLCULO ===");
+        println!("1. Derivada simbólica");
+        println!("2. Derivada em um ponto");
+        println!("3. Integral definida (Simpson composto)");
+        println!("4. Integral definida (Simpson adaptativo)");
+        println!("5. Voltar");
+
+        let opcao = match ler_opcao(5) {
+            Some(5) => break,
+            Some(opcao) => opcao,
+            None => {
+                println!("Opção inválida! Tente novamente.");
                 continue;
             }
+        };
+
+        println!("Digite a expressão (ex: x^2 * sin(x)):");
+        let texto = ler_texto();
+        let resultado = ler_funcao(&texto, sessao).and_then(|(expr, variavel)| match opcao {
+            1 => mostrar_derivada(&expr, &variavel, None, sessao),
+            2 => {
+                println!("Digite o ponto:");
+                let ponto: f64 = ler_numero();
+                mostrar_derivada(&expr, &variavel, Some(ponto), sessao)
+            }
+            _ => {
+                println!("Digite o limite inferior:");
+                let a: f64 = ler_numero();
+                println!("Digite o limite superior:");
+                let b: f64 = ler_numero();
+                let subintervalos = if opcao == 3 {
+                    println!("Digite o número de subintervalos:");
+                    Some(ler_subintervalos())
+                } else {
+                    None
+                };
+                mostrar_integral(&texto, &expr, &variavel, (a, b), subintervalos, sessao)
+            }
+        });
+        if let Err(erro) = resultado {
+            println!("Erro: {}", erro);
+        }
+    }
+}
+
+// Expressão de uma variável: a única sem valor na sessão (x se não houver)
+fn ler_funcao(texto: &str, sessao: &Sessao) -> Result<(Expr, String), ErroCalculo> {
+    let expr = expressao::analisar(texto).map_err(ErroExpressao::from)?;
+    let variavel = calculo::variavel_livre(&expr, sessao)?;
+    Ok((expr.multiplicacao_implicita(&variavel), variavel))
+}
+
+// Sem ponto, mostra a derivada simbólica; com ponto, também o valor, que vai
+// para o histórico
+fn mostrar_derivada(expr: &Expr, variavel: &str, ponto: Option<f64>, sessao: &mut Sessao) -> Result<(), ErroCalculo> {
+    let derivada = calculo::derivar(expr, variavel, sessao.angulo)?;
+    println!("d/d{} {} = {}", variavel, expr.legivel(), derivada.legivel());
+    if let Some(ponto) = ponto {
+        let valor = calculo::avaliar_em(&derivada, variavel, ponto, sessao)?;
+        sessao.registrar(&format!("d/d{} {} at {}", variavel, expr.legivel(), ponto), Valor::Real(valor));
+        println!("${} = {}", sessao.historico.len(), valor);
+    }
+    Ok(())
+}
+
+// Erro absoluto aceito pelo Simpson adaptativo
+const TOLERANCIA_INTEGRAL: f64 = 1e-10;
+
+// Com `subintervalos` usa Simpson composto; sem, o adaptativo
+fn mostrar_integral(
+    texto: &str,
+    expr: &Expr,
+    variavel: &str,
+    (a, b): (f64, f64),
+    subintervalos: Option<usize>,
+    sessao: &mut Sessao,
+) -> Result<(), ErroCalculo> {
+    let f = |x| calculo::avaliar_em(expr, variavel, x, sessao);
+    let valor = match subintervalos {
+        Some(n) => calculo::simpson(f, a, b, n)?,
+        None => calculo::simpson_adaptativo(f, a, b, TOLERANCIA_INTEGRAL)?,
+    };
+    sessao.registrar(&format!("integral {} from {} to {}", texto, a, b), Valor::Real(valor));
+    println!("${} = {}", sessao.historico.len(), valor);
+    Ok(())
+}
+
+// `d/dx <expressão> [at <ponto>]`; o ponto pode ser uma expressão (ex: pi / 4)
+fn derivar_na_sessao(variavel: &str, argumento: &str, sessao: &mut Sessao) -> Result<(), ErroCalculo> {
+    let (texto, ponto) = match argumento.rsplit_once(" at ") {
+        Some((texto, ponto)) => (texto, Some(avaliar_limite(ponto, sessao)?)),
+        None => (argumento, None),
+    };
+    let expr = expressao::analisar(texto).map_err(ErroExpressao::from)?;
+    mostrar_derivada(&expr.multiplicacao_implicita(variavel), variavel, ponto, sessao)
+}
+
+// `integral <expressão> from <a> to <b>`
+fn integrar_na_sessao(argumento: &str, sessao: &mut Sessao) -> Result<(), String> {
+    let uso = "Uso: integral <expressão> from <a> to <b>";
+    let (texto, limites) = argumento.rsplit_once(" from ").ok_or(uso)?;
+    let (a, b) = limites.split_once(" to ").ok_or(uso)?;
+    let resultado = avaliar_limite(a, sessao).and_then(|a| {
+        let b = avaliar_limite(b, sessao)?;
+        let (expr, variavel) = ler_funcao(texto, sessao)?;
+        mostrar_integral(texto, &expr, &variavel, (a, b), None, sessao)
+    });
+    resultado.map_err(|erro| erro.to_string())
+}
+
+fn avaliar_limite(texto: &str, sessao: &Sessao) -> Result<f64, ErroCalculo> {
+    let expr = expressao::analisar(texto).map_err(ErroExpressao::from)?;
+    Ok(expressao::avaliar::<f64>(&expr, sessao)?)
+}
+
+pub fn executar_grafico(sessao: &mut Sessao) {
+    println!("\n=== GRÁFICO ===");
+    println!("Digite as funções separadas por ; (ex: sin(x); cos(x)):");
+    let funcoes = ler_texto();
+    println!("Digite o início do intervalo:");
+    let a: f64 = ler_numero();
+    println!("Digite o fim do intervalo:");
+    let b: f64 = ler_numero();
+    println!("Estilo: 1. Braille  2. ASCII");
+    let estilo = match ler_opcao(2) {
+        Some(2) => Estilo::Ascii,
+        _ => Estilo::Braille,
+    };
+    println!("Arquivo CSV para exportar os pontos (vazio para não exportar):");
+    let arquivo = ler_texto();
+    let arquivo = Some(arquivo.as_str()).filter(|arquivo| !arquivo.is_empty());
+    if let Err(erro) = mostrar_grafico(&funcoes, (a, b), estilo, arquivo, sessao) {
+        println!("Erro: {}", erro);
+    }
+}
+
+// Cada função tem sua própria variável livre (x se não houver)
+fn mostrar_grafico(
+    funcoes: &str,
+    (a, b): (f64, f64),
+    estilo: Estilo,
+    arquivo: Option<&str>,
+    sessao: &Sessao,
+) -> Result<(), String> {
+    let mut grafico = Grafico::novo(a, b, grafico::LARGURA_PADRAO, grafico::ALTURA_PADRAO, estilo)
+        .map_err(|erro| erro.to_string())?;
+    for texto in funcoes.split(';').map(str::trim).filter(|texto| !texto.is_empty()) {
+        let (expr, variavel) = ler_funcao(texto, sessao).map_err(|erro| erro.to_string())?;
+        grafico
+            .adicionar(texto, |x| calculo::avaliar_em(&expr, &variavel, x, sessao))
+            .map_err(|erro| erro.to_string())?;
+    }
+    if grafico.series().is_empty() {
+        return Err("Nenhuma função informada".to_string());
+    }
+    print!("{}", grafico.desenhar());
+    if let Some(arquivo) = arquivo {
+        fs::write(arquivo, grafico.csv()).map_err(|erro| format!("ao exportar {}: {}", arquivo, erro))?;
+        println!("Pontos exportados para {}", arquivo);
+    }
+    Ok(())
+}
+
+// `plot <f>[; <g> ...] from <a> to <b> [ascii] [csv <arquivo>]`
+fn plotar_na_sessao(argumento: &str, sessao: &Sessao) -> Result<(), String> {
+    let uso = "Uso: plot <f>[; <g> ...] from <a> to <b> [ascii] [csv <arquivo>]";
+    let (argumento, arquivo) = match argumento.rsplit_once(" csv ") {
+        Some((resto, arquivo)) => (resto, Some(arquivo.trim())),
+        None => (argumento, None),
+    };
+    let (argumento, estilo) = match argumento.strip_suffix(" ascii") {
+        Some(resto) => (resto, Estilo::Ascii),
+        None => (argumento, Estilo::Braille),
+    };
+    let (funcoes, limites) = argumento.rsplit_once(" from ").ok_or(uso)?;
+    let (a, b) = limites.split_once(" to ").ok_or(uso)?;
+    let a = avaliar_limite(a, sessao).map_err(|erro| erro.to_string())?;
+    let b = avaliar_limite(b, sessao).map_err(|erro| erro.to_string())?;
+    mostrar_grafico(funcoes, (a, b), estilo, arquivo, sessao)
+}
+
+pub fn executar_programador(sessao: &mut Sessao) {
+    let operacoes = registro::OPERACOES_PROGRAMADOR;
+    let total = operacoes.len();
+
+    loop {
+        println!("\n=== MODO PROGRAMADOR ===");
+        println!("Palavra: {}", sessao.programador);
+        for (i, operacao) in operacoes.iter().enumerate() {
+            println!("{}. {} ({})", i + 1, operacao.nome, operacao.simbolo);
+        }
+        println!("{}. Tamanho da Palavra", total + 1);
+        println!("{}. Com/Sem Sinal", total + 2);
+        println!("{}. Base", total + 3);
+        println!("{}. Aritmética Circular/Verificada", total + 4);
+        println!("{}. Voltar", total + 5);
+
+        let formato = &mut sessao.programador;
+        match ler_opcao(total + 5) {
+            Some(n) if n <= total => executar_operacao_bits(&operacoes[n - 1], sessao),
+            Some(n) if n == total + 1 => {
+                println!("Tamanho da palavra:");
+                for (i, tamanho) in TamanhoPalavra::TODOS.iter().enumerate() {
+                    println!("{}. {} bits", i + 1, tamanho.bits());
+                }
+                match ler_opcao(TamanhoPalavra::TODOS.len()) {
+                    Some(n) => formato.tamanho = TamanhoPalavra::TODOS[n - 1],
+                    None => println!("Opção inválida!"),
+                }
+            }
+            Some(n) if n == total + 2 => formato.com_sinal = !formato.com_sinal,
+            Some(n) if n == total + 3 => {
+                println!("Base para entrada e exibição:");
+                for (i, base) in Base::TODAS.iter().enumerate() {
+                    println!("{}. {}", i + 1, base);
+                }
+                match ler_opcao(Base::TODAS.len()) {
+                    Some(n) => formato.base = Base::TODAS[n - 1],
+                    None => println!("Opção inválida!"),
+                }
+            }
+            Some(n) if n == total + 4 => {
+                formato.aritmetica = match formato.aritmetica {
+                    Aritmetica::Circular => Aritmetica::Verificada,
+                    Aritmetica::Verificada => Aritmetica::Circular,
+                }
+            }
+            Some(_) => break,
+            None => println!("Opção inválida! Tente novamente."),
+        }
+    }
+}
+
+fn executar_operacao_bits(operacao: &OperacaoBits, sessao: &mut Sessao) {
+    let formato = sessao.programador;
+    println!("\n=== {} ===", operacao.nome.to_uppercase());
+
+    let mut argumentos = Vec::new();
+    for i in 0..operacao.aridade() {
+        match (operacao.aridade(), i) {
+            (1, _) => println!("Digite o número:"),
+            (_, 0) => println!("Digite o primeiro número:"),
+            _ => println!("Digite o segundo número:"),
+        }
+        argumentos.push(ler_palavra(&formato));
+    }
+
+    let exibir = |bits| formato.exibir(bits, formato.base);
+    let texto = match argumentos[..] {
+        [a] => format!("{}({})", operacao.simbolo, exibir(a)),
+        [a, b] => format!("{} {} {}", exibir(a), operacao.simbolo, exibir(b)),
+        _ => unreachable!("operações do modo programador têm um ou dois argumentos"),
+    };
+
+    match operacao.aplicar(&formato, &argumentos) {
+        Ok(resultado) => {
+            println!("Resultado: {} = {}", texto, formato.exibir_todas(resultado));
+            // No histórico entra o valor numérico, para uso em expressões
+            let valor = BigInt::from(formato.interpretar(resultado));
+            sessao.registrar(&texto, Valor::Inteiro(valor));
+        }
+        Err(erro) => println!("Erro: {}", erro),
+    }
+}
+
+pub fn escolher_modo(sessao: &mut Sessao) {
+    println!("\n=== MODO NUMÉRICO ===");
+    for (i, modo) in Modo::TODOS.iter().enumerate() {
+        let atual = if *modo == sessao.modo { " (atual)" } else { "" };
+        println!("{}. {} - {}{}", i + 1, modo, modo.descricao(), atual);
+    }
+
+    match ler_opcao(Modo::TODOS.len()) {
+        Some(n) => {
+            sessao.modo = Modo::TODOS[n - 1];
+            println!("Modo numérico: {}", sessao.modo);
+            if sessao.modo == Modo::Complexo {
+                escolher_forma_complexa(sessao);
+            }
+        }
+        _ => println!("Opção inválida! O modo continua {}.", sessao.modo),
+    }
+}
+
+fn escolher_forma_complexa(sessao: &mut Sessao) {
+    println!("Exibir complexos na forma:");
+    for (i, forma) in FormaComplexa::TODAS.iter().enumerate() {
+        let atual = if *forma == sessao.forma { " (atual)" } else { "" };
+        println!("{}. {} - {}{}", i + 1, forma, forma.descricao(), atual);
+    }
+    match ler_opcao(FormaComplexa::TODAS.len()) {
+        Some(n) => sessao.forma = FormaComplexa::TODAS[n - 1],
+        None => println!("Opção inválida! A forma continua {}.", sessao.forma),
+    }
+}
+
+pub fn escolher_unidade_angulo(sessao: &mut Sessao) {
+    println!("\n=== UNIDADE DE ÂNGULO ===");
+    for (i, unidade) in UnidadeAngulo::TODAS.iter().enumerate() {
+        let atual = if *unidade == sessao.angulo { " (atual)" } else { "" };
+        println!("{}. {} - {}{}", i + 1, unidade, unidade.descricao(), atual);
+    }
+
+    match ler_opcao(UnidadeAngulo::TODAS.len()) {
+        Some(n) => {
+            sessao.angulo = UnidadeAngulo::TODAS[n - 1];
+            println!("Unidade de ângulo: {}", sessao.angulo);
+        }
+        _ => println!("Opção inválida! A unidade continua {}.", sessao.angulo),
+    }
+}
+
+pub fn executar_expressao(sessao: &mut Sessao) {
+    println!("\n=== EXPRESSÃO ===");
+    println!("Digite expressões (ex: 2 * (3 + sin(30)) ^ 2 / 4), atribuições (ex: x = 3.5)");
+    println!("ou conversões de unidades (ex: 5 km to mi, 100 °F to °C).");
+    println!("No modo complexo: sqrt(-4), 3 + 4i, 2∠45 (forma polar), abs, arg, conj, re, im.");
+    println!("Use ans, $n (histórico) e os comandos m+, m-, mr, mc, hist, vars,");
+    println!("modo <real|inteiro|racional|decimal|complexo>, forma <retangular|polar>,");
+    println!("angulo <deg|rad|grad>, solve <equação>, d/dx <expressão> [at <ponto>],");
+    println!("integral <expressão> from <a> to <b>, plot <f>[; <g>] from <a> to <b> [ascii] [csv <arquivo>],");
+    println!("salvar <arquivo> e carregar <arquivo>.");
+    println!("Linha vazia volta ao menu. Modo atual: {}, ângulos em {}", sessao.modo, sessao.angulo);
+
+    loop {
+        let texto = terminal::ler_expressao("> ", sessao.variaveis.keys().cloned().collect());
+        if texto.is_empty() {
+            break;
+        }
+
+        if !executar_comando_sessao(&texto, sessao) {
+            avaliar_na_sessao(&texto, sessao);
         }
     }
 }
 
-pub fn executar_operacao(nome_operacao: &str, operacao: fn(f64, f64) -> f64) {
-    println!("\n=== {} ===", nome_operacao.to_uppercase());
-    
-    println!("Digite o primeiro número:");
-    let a = ler_numero();
-    
-    println!("Digite o segundo número:");
-    let b = ler_numero();
-    
-    let resultado = operacao(a, b);
-    println!("Resultado: {} {} {} = {:.2}", a, obter_simbolo_operacao(nome_operacao), b, resultado);
+fn avaliar_na_sessao(texto: &str, sessao: &mut Sessao) {
+    match sessao.executar(texto) {
+        Ok(_) => {
+            let entrada = sessao.historico.last().expect("resultado acabou de ser registrado");
+            let resultado = match entrada.unidade {
+                Some(_) => entrada.resultado_com_unidade(),
+                None => sessao.formatar(&entrada.resultado),
+            };
+            println!("${} = {}", sessao.historico.len(), resultado);
+        }
+        Err(ErroExpressao::Sintaxe(erro)) => {
+            // Aponta a coluna do erro logo abaixo da expressão digitada
+            println!("{}", texto);
+            println!("{}^", " ".repeat(erro.coluna - 1));
+            println!("{}", erro);
+        }
+        Err(erro) => println!("{}", erro),
+    }
 }
 
-pub fn executar_operacao_unaria(nome_operacao: &str, operacao: fn(f64) -> f64) {
-    println!("\n=== {} ===", nome_operacao.to_uppercase());
-    
-    println!("Digite o número:");
-    let numero = ler_numero();
-    
-    let resultado = operacao(numero);
-    println!("√{} = {:.2}", numero, resultado);
+// Retorna false quando a linha não é um comando e deve ser avaliada
+fn executar_comando_sessao(texto: &str, sessao: &mut Sessao) -> bool {
+    let (comando, argumento) = match texto.split_once(char::is_whitespace) {
+        Some((comando, argumento)) => (comando, argumento.trim()),
+        None => (texto, ""),
+    };
+
+    match comando.to_lowercase().as_str() {
+        "m+" => match sessao.memoria_somar() {
+            Ok(()) => mostrar_memoria(sessao),
+            Err(erro) => println!("Erro: {}", erro),
+        },
+        "m-" | "m−" => match sessao.memoria_subtrair() {
+            Ok(()) => mostrar_memoria(sessao),
+            Err(erro) => println!("Erro: {}", erro),
+        },
+        "mr" => match sessao.memoria_recuperar() {
+            Ok(valor) => println!("M = {}", valor),
+            Err(erro) => println!("Erro: {}", erro),
+        },
+        "mc" => {
+            sessao.memoria_limpar();
+            println!("Memória limpa");
+        }
+        "hist" | "historico" | "histórico" => {
+            for (i, entrada) in sessao.historico.iter().enumerate() {
+                println!("${}: {} = {}", i + 1, entrada.expressao, sessao.formatar(&entrada.resultado));
+            }
+        }
+        "vars" => {
+            if let Some(ans) = &sessao.ans {
+                println!("ans = {}", ans);
+            }
+            for (nome, valor) in &sessao.variaveis {
+                println!("{} = {}", nome, valor);
+            }
+        }
+        "modo" if !argumento.is_empty() => match argumento.parse::<Modo>() {
+            Ok(modo) => {
+                sessao.modo = modo;
+                println!("Modo numérico: {}", modo);
+            }
+            Err(erro) => println!("{}", erro),
+        },
+        "modo" => println!("Modo numérico: {} ({})", sessao.modo, sessao.modo.descricao()),
+        "forma" if !argumento.is_empty() => match argumento.parse::<FormaComplexa>() {
+            Ok(forma) => {
+                sessao.forma = forma;
+                println!("Forma dos complexos: {}", forma);
+            }
+            Err(erro) => println!("{}", erro),
+        },
+        "forma" => println!("Forma dos complexos: {} ({})", sessao.forma, sessao.forma.descricao()),
+        "angulo" | "ângulo" if !argumento.is_empty() => match argumento.parse::<UnidadeAngulo>() {
+            Ok(unidade) => {
+                sessao.angulo = unidade;
+                println!("Unidade de ângulo: {}", unidade);
+            }
+            Err(erro) => println!("{}", erro),
+        },
+        "angulo" | "ângulo" => println!("Unidade de ângulo: {} ({})", sessao.angulo, sessao.angulo.descricao()),
+        "solve" | "resolver" if !argumento.is_empty() => match equacoes::resolver(argumento, sessao) {
+            Ok(solucao) => mostrar_solucao(argumento, solucao, sessao),
+            Err(ErroEquacao::Expressao(erro)) => println!("{}", erro),
+            Err(erro) => println!("Erro: {}", erro),
+        },
+        derivada if derivada.starts_with("d/d") && derivada.len() > 3 && !argumento.is_empty() => {
+            // O nome da variável mantém a caixa original: d/dX
+            if let Err(erro) = derivar_na_sessao(&comando[3..], argumento, sessao) {
+                println!("Erro: {}", erro);
+            }
+        }
+        "integral" | "integrar" if !argumento.is_empty() => {
+            if let Err(erro) = integrar_na_sessao(argumento, sessao) {
+                println!("Erro: {}", erro);
+            }
+        }
+        "plot" | "grafico" | "gráfico" if !argumento.is_empty() => {
+            if let Err(erro) = plotar_na_sessao(argumento, sessao) {
+                println!("Erro: {}", erro);
+            }
+        }
+        "salvar" if !argumento.is_empty() => match sessao.salvar(argumento) {
+            Ok(()) => println!("Sessão salva em {}", argumento),
+            Err(erro) => println!("Erro ao salvar sessão: {}", erro),
+        },
+        "carregar" if !argumento.is_empty() => match Sessao::carregar(argumento) {
+            Ok(carregada) => {
+                *sessao = carregada;
+                println!("Sessão carregada de {}", argumento);
+            }
+            Err(erro) => println!("Erro ao carregar sessão: {}", erro),
+        },
+        _ => return false,
+    }
+    true
 }
 
-fn obter_simbolo_operacao(operacao: &str) -> &str {
-    match operacao {
-        "soma" => "+",
-        "subtração" => "-",
-        "multiplicação" => "×",
-        "divisão" => "÷",
-        "potenciação" => "^",
-        _ => "?",
+fn mostrar_memoria(sessao: &Sessao) {
+    match &sessao.memoria {
+        Some(valor) => println!("M = {}", valor),
+        None => println!("M = 0"),
     }
 }
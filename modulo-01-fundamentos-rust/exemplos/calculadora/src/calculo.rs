@@ -0,0 +1,587 @@
+use std::fmt;
+
+use crate::angulo::UnidadeAngulo;
+use crate::expressao::{self, ComVariavel, Contexto, ErroAvaliacao, ErroExpressao, Expr, OpBinario};
+use crate::registro::{self, Angulo};
+
+// Passadas de `simplificar` até a expressão parar de mudar
+const MAX_PASSADAS_SIMPLIFICACAO: usize = 32;
+
+// Limites do Simpson adaptativo: cada nível divide o intervalo ao meio
+const PROFUNDIDADE_MAXIMA: usize = 50;
+const MAX_AVALIACOES: usize = 200_000;
+
+fn numero(x: f64) -> Expr {
+    if x < 0.0 {
+        Expr::Negacao(Box::new(numero(-x)))
+    } else {
+        Expr::Numero((x + 0.0).to_string())
+    }
+}
+
+fn binario(op: OpBinario, esquerda: Expr, direita: Expr) -> Expr {
+    Expr::Binario { op, esquerda: Box::new(esquerda), direita: Box::new(direita) }
+}
+
+fn somar(a: Expr, b: Expr) -> Expr {
+    binario(OpBinario::Soma, a, b)
+}
+
+fn subtrair(a: Expr, b: Expr) -> Expr {
+    binario(OpBinario::Subtracao, a, b)
+}
+
+fn multiplicar(a: Expr, b: Expr) -> Expr {
+    binario(OpBinario::Multiplicacao, a, b)
+}
+
+fn dividir(a: Expr, b: Expr) -> Expr {
+    binario(OpBinario::Divisao, a, b)
+}
+
+fn potencia(a: Expr, b: Expr) -> Expr {
+    binario(OpBinario::Potencia, a, b)
+}
+
+fn negar(a: Expr) -> Expr {
+    Expr::Negacao(Box::new(a))
+}
+
+fn chamar(funcao: &str, argumento: &Expr) -> Expr {
+    Expr::Chamada { funcao: funcao.to_string(), argumentos: vec![argumento.clone()] }
+}
+
+// Valor de um literal, com ou sem sinal de menos na frente
+fn constante(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expr::Numero(literal) => literal.parse().ok(),
+        Expr::Negacao(operando) => constante(operando).map(|x| -x),
+        _ => None,
+    }
+}
+
+fn eh(expr: &Expr, valor: f64) -> bool {
+    constante(expr) == Some(valor)
+}
+
+// Radianos por unidade de ângulo, como expressão (pi / 180 em graus)
+fn fator_angulo(unidade: UnidadeAngulo) -> Option<Expr> {
+    let meia_volta = match unidade {
+        UnidadeAngulo::Radianos => return None,
+        UnidadeAngulo::Graus => 180.0,
+        UnidadeAngulo::Grados => 200.0,
+    };
+    Some(dividir(Expr::Variavel("pi".to_string()), numero(meia_volta)))
+}
+
+// Derivada de `expr` em relação a `variavel`, já simplificada. As funções
+// trigonométricas seguem a unidade de ângulo: em graus, d/dx sin(x) é
+// pi / 180 * cos(x).
+pub fn derivar(expr: &Expr, variavel: &str, unidade: UnidadeAngulo) -> Result<Expr, ErroCalculo> {
+    let expr = expr.clone().multiplicacao_implicita(variavel);
+    Ok(simplificar(derivada(&expr, variavel, unidade)?))
+}
+
+fn derivada(expr: &Expr, x: &str, unidade: UnidadeAngulo) -> Result<Expr, ErroCalculo> {
+    if !expr.contem(x) {
+        return Ok(numero(0.0));
+    }
+    let d = |expr: &Expr| derivada(expr, x, unidade);
+    let resultado = match expr {
+        Expr::Variavel(_) => numero(1.0),
+        Expr::Negacao(u) => negar(d(u)?),
+        Expr::Binario { op, esquerda: u, direita: v } => {
+            let (u, v) = (u.as_ref(), v.as_ref());
+            match op {
+                OpBinario::Soma => somar(d(u)?, d(v)?),
+                OpBinario::Subtracao => subtrair(d(u)?, d(v)?),
+                OpBinario::Multiplicacao => somar(multiplicar(d(u)?, v.clone()), multiplicar(u.clone(), d(v)?)),
+                OpBinario::Divisao => dividir(
+                    subtrair(multiplicar(d(u)?, v.clone()), multiplicar(u.clone(), d(v)?)),
+                    potencia(v.clone(), numero(2.0)),
+                ),
+                OpBinario::Potencia => derivada_potencia(u, v, x, unidade)?,
+                // u % c tem a mesma inclinação de u, exceto nos saltos
+                OpBinario::Resto if !v.contem(x) => d(u)?,
+                OpBinario::Resto | OpBinario::Polar => return Err(ErroCalculo::NaoDerivavel(op.simbolo().to_string())),
+            }
+        }
+        Expr::Chamada { funcao, argumentos } => derivada_funcao(funcao, argumentos, x, unidade)?,
+        Expr::ComUnidade { .. } => return Err(ErroCalculo::NaoDerivavel("unidades".to_string())),
+        Expr::Numero(_) | Expr::Historico(_) => numero(0.0),
+    };
+    Ok(resultado)
+}
+
+fn derivada_potencia(u: &Expr, v: &Expr, x: &str, unidade: UnidadeAngulo) -> Result<Expr, ErroCalculo> {
+    let resultado = if !v.contem(x) {
+        // v·u^(v-1)·u'
+        multiplicar(
+            derivada(u, x, unidade)?,
+            multiplicar(v.clone(), potencia(u.clone(), subtrair(v.clone(), numero(1.0)))),
+        )
+    } else if !u.contem(x) {
+        // u^v·ln(u)·v'
+        multiplicar(derivada(v, x, unidade)?, multiplicar(potencia(u.clone(), v.clone()), chamar("ln", u)))
+    } else {
+        // u^v·(v'·ln(u) + v·u'/u)
+        let expoente = somar(
+            multiplicar(derivada(v, x, unidade)?, chamar("ln", u)),
+            dividir(multiplicar(v.clone(), derivada(u, x, unidade)?), u.clone()),
+        );
+        multiplicar(potencia(u.clone(), v.clone()), expoente)
+    };
+    Ok(resultado)
+}
+
+// Regra da cadeia: f'(u)·u', com f' escrita com os identificadores do registro
+fn derivada_funcao(funcao: &str, argumentos: &[Expr], x: &str, unidade: UnidadeAngulo) -> Result<Expr, ErroCalculo> {
+    let operacao = registro::buscar_por_identificador(funcao)
+        .ok_or_else(|| ErroAvaliacao::FuncaoDesconhecida(funcao.to_string()))?;
+    if argumentos.len() != operacao.aridade() {
+        return Err(ErroAvaliacao::NumeroArgumentos {
+            funcao: funcao.to_string(),
+            esperado: operacao.aridade(),
+            recebido: argumentos.len(),
+        }
+        .into());
+    }
+
+    let u = &argumentos[0];
+    let um = || numero(1.0);
+    let externa = match operacao.identificadores[0] {
+        "pow" => return derivada_potencia(u, &argumentos[1], x, unidade),
+        "atan2" => {
+            // atan2(y, w)' = (w·y' - y·w') / (w² + y²)
+            let w = &argumentos[1];
+            let derivada = dividir(
+                subtrair(multiplicar(w.clone(), derivada(u, x, unidade)?), multiplicar(u.clone(), derivada(w, x, unidade)?)),
+                somar(potencia(w.clone(), numero(2.0)), potencia(u.clone(), numero(2.0))),
+            );
+            return Ok(match fator_angulo(unidade) {
+                Some(fator) => dividir(derivada, fator),
+                None => derivada,
+            });
+        }
+        "sqrt" => dividir(um(), multiplicar(numero(2.0), chamar("sqrt", u))),
+        "ln" => dividir(um(), u.clone()),
+        "sin" => chamar("cos", u),
+        "cos" => negar(chamar("sin", u)),
+        "tan" => dividir(um(), potencia(chamar("cos", u), numero(2.0))),
+        "asin" => dividir(um(), chamar("sqrt", &subtrair(um(), potencia(u.clone(), numero(2.0))))),
+        "acos" => negar(dividir(um(), chamar("sqrt", &subtrair(um(), potencia(u.clone(), numero(2.0)))))),
+        "atan" => dividir(um(), somar(um(), potencia(u.clone(), numero(2.0)))),
+        "sinh" => chamar("cosh", u),
+        "cosh" => chamar("sinh", u),
+        "tanh" => dividir(um(), potencia(chamar("cosh", u), numero(2.0))),
+        "asinh" => dividir(um(), chamar("sqrt", &somar(potencia(u.clone(), numero(2.0)), um()))),
+        "acosh" => dividir(um(), chamar("sqrt", &subtrair(potencia(u.clone(), numero(2.0)), um()))),
+        "atanh" => dividir(um(), subtrair(um(), potencia(u.clone(), numero(2.0)))),
+        "abs" => dividir(u.clone(), chamar("abs", u)),
+        _ => return Err(ErroCalculo::NaoDerivavel(funcao.to_string())),
+    };
+
+    // Em graus ou grados, sin recebe fator·u radianos e asin devolve radianos / fator
+    let externa = match (operacao.angulo, fator_angulo(unidade)) {
+        (Angulo::Entrada, Some(fator)) => multiplicar(fator, externa),
+        (Angulo::Saida, Some(fator)) => dividir(externa, fator),
+        _ => externa,
+    };
+    Ok(multiplicar(derivada(u, x, unidade)?, externa))
+}
+
+// Regras algébricas simples (elementos neutros, constantes, sinais) aplicadas
+// de baixo para cima até a expressão parar de mudar
+pub fn simplificar(expr: Expr) -> Expr {
+    let mut atual = expr;
+    for _ in 0..MAX_PASSADAS_SIMPLIFICACAO {
+        let proxima = passada(atual.clone());
+        if proxima == atual {
+            break;
+        }
+        atual = proxima;
+    }
+    atual
+}
+
+fn passada(expr: Expr) -> Expr {
+    match expr {
+        Expr::Negacao(operando) => simplificar_negacao(passada(*operando)),
+        Expr::Binario { op, esquerda, direita } => simplificar_binario(op, passada(*esquerda), passada(*direita)),
+        Expr::Chamada { funcao, argumentos } => {
+            let argumentos: Vec<Expr> = argumentos.into_iter().map(passada).collect();
+            // ln(e) aparece ao derivar e^x
+            let eh_ln = registro::buscar_por_identificador(&funcao).is_some_and(|op| op.identificadores[0] == "ln");
+            if eh_ln && argumentos == [Expr::Variavel("e".to_string())] {
+                return numero(1.0);
+            }
+            Expr::Chamada { funcao, argumentos }
+        }
+        outra => outra,
+    }
+}
+
+fn simplificar_negacao(operando: Expr) -> Expr {
+    match operando {
+        Expr::Negacao(interno) => *interno,
+        operando if eh(&operando, 0.0) => numero(0.0),
+        operando => negar(operando),
+    }
+}
+
+// Junta dois literais só quando o resultado é exato e curto: 2 * 3 vira 6,
+// mas 1 / 3 fica como está
+fn dobrar(op: OpBinario, a: f64, b: f64) -> Option<f64> {
+    let resultado = match op {
+        OpBinario::Soma => a + b,
+        OpBinario::Subtracao => a - b,
+        OpBinario::Multiplicacao => a * b,
+        OpBinario::Divisao if b != 0.0 => a / b,
+        OpBinario::Potencia if b.fract() == 0.0 && b >= 0.0 => a.powf(b),
+        _ => return None,
+    };
+    let exato = match op {
+        OpBinario::Divisao | OpBinario::Potencia => resultado.fract() == 0.0,
+        _ => true,
+    };
+    (resultado.is_finite() && exato).then_some(resultado)
+}
+
+fn expoentes_compoem(m: Option<f64>, n: Option<f64>) -> bool {
+    let inteiro = |x: f64| x.fract() == 0.0;
+    match (m, n) {
+        (Some(m), Some(n)) => inteiro(n) || (inteiro(m) && m.rem_euclid(2.0) == 1.0 && inteiro(m * n)),
+        _ => false,
+    }
+}
+
+fn simplificar_binario(op: OpBinario, a: Expr, b: Expr) -> Expr {
+    if let (Some(x), Some(y)) = (constante(&a), constante(&b)) {
+        if let Some(resultado) = dobrar(op, x, y) {
+            return numero(resultado);
+        }
+    }
+
+    match op {
+        OpBinario::Soma if eh(&a, 0.0) => b,
+        OpBinario::Soma if eh(&b, 0.0) => a,
+        OpBinario::Soma if a == b => multiplicar(numero(2.0), a),
+        OpBinario::Soma => match (a, b) {
+            (a, Expr::Negacao(b)) => subtrair(a, *b),
+            (Expr::Negacao(a), b) => subtrair(b, *a),
+            (a, b) => somar(a, b),
+        },
+
+        OpBinario::Subtracao if eh(&b, 0.0) => a,
+        OpBinario::Subtracao if eh(&a, 0.0) => negar(b),
+        OpBinario::Subtracao if a == b => numero(0.0),
+        OpBinario::Subtracao => match (a, b) {
+            (a, Expr::Negacao(b)) => somar(a, *b),
+            // (u + v) - v vira u
+            (Expr::Binario { op: OpBinario::Soma, esquerda, direita }, b) if *direita == b => *esquerda,
+            (Expr::Binario { op: OpBinario::Soma, esquerda, direita }, b) if *esquerda == b => *direita,
+            (a, b) => subtrair(a, b),
+        },
+
+        OpBinario::Multiplicacao if eh(&a, 0.0) || eh(&b, 0.0) => numero(0.0),
+        OpBinario::Multiplicacao if eh(&a, 1.0) => b,
+        OpBinario::Multiplicacao if eh(&b, 1.0) => a,
+        OpBinario::Multiplicacao if eh(&a, -1.0) => negar(b),
+        OpBinario::Multiplicacao if a == b => potencia(a, numero(2.0)),
+        // Constantes à esquerda: x * 2 vira 2 * x
+        OpBinario::Multiplicacao if constante(&b).is_some() && constante(&a).is_none() => multiplicar(b, a),
+        OpBinario::Multiplicacao => match (a, b) {
+            (Expr::Negacao(a), b) => negar(multiplicar(*a, b)),
+            (a, Expr::Negacao(b)) => negar(multiplicar(a, *b)),
+            // a * (1 / b) vira a / b
+            (a, Expr::Binario { op: OpBinario::Divisao, esquerda, direita }) if eh(&esquerda, 1.0) => dividir(a, *direita),
+            // 2 * (3 * x) vira 6 * x
+            (a, Expr::Binario { op: OpBinario::Multiplicacao, esquerda, direita }) => {
+                match (constante(&a), constante(&esquerda)) {
+                    (Some(x), Some(y)) => multiplicar(numero(x * y), *direita),
+                    _ => multiplicar(a, multiplicar(*esquerda, *direita)),
+                }
+            }
+            (a, b) => multiplicar(a, b),
+        },
+
+        // 0 / 0 fica como está: não é 0 nem 1
+        OpBinario::Divisao if eh(&b, 0.0) => dividir(a, b),
+        OpBinario::Divisao if eh(&a, 0.0) => numero(0.0),
+        OpBinario::Divisao if eh(&b, 1.0) => a,
+        OpBinario::Divisao if a == b => numero(1.0),
+        OpBinario::Divisao => match (a, b) {
+            (Expr::Negacao(a), b) => negar(dividir(*a, b)),
+            (a, Expr::Negacao(b)) => negar(dividir(a, *b)),
+            (a, b) => dividir(a, b),
+        },
+
+        OpBinario::Potencia if eh(&b, 0.0) || eh(&a, 1.0) => numero(1.0),
+        OpBinario::Potencia if eh(&b, 1.0) => a,
+        OpBinario::Potencia => match (a, b) {
+            // (u^m)^n = u^(m·n) só quando vale para u negativo também: n
+            // inteiro, ou m ímpar com m·n inteiro. (x^2)^0.5 é |x|, não x.
+            (Expr::Binario { op: OpBinario::Potencia, esquerda, direita }, b)
+                if expoentes_compoem(constante(&direita), constante(&b)) =>
+            {
+                let expoente = constante(&direita).unwrap_or(1.0) * constante(&b).unwrap_or(1.0);
+                potencia(*esquerda, numero(expoente))
+            }
+            (a, b) => potencia(a, b),
+        },
+
+        op => binario(op, a, b),
+    }
+}
+
+// Valor de `expr` com `variavel` = x, no contexto da sessão
+pub fn avaliar_em(expr: &Expr, variavel: &str, x: f64, contexto: &impl Contexto) -> Result<f64, ErroCalculo> {
+    let contexto = ComVariavel { contexto, nome: variavel, valor: x };
+    Ok(expressao::avaliar::<f64>(expr, &contexto)?)
+}
+
+// O único nome sem valor na sessão; sem nenhum, a expressão é constante e
+// qualquer nome serve
+pub fn variavel_livre(expr: &Expr, contexto: &impl Contexto) -> Result<String, ErroCalculo> {
+    let livres: Vec<String> = expressao::variaveis(expr)
+        .into_iter()
+        .filter(|nome| contexto.variavel(nome).is_none())
+        .collect();
+    match livres.as_slice() {
+        [] => Ok("x".to_string()),
+        [unica] => Ok(unica.clone()),
+        _ => Err(ErroCalculo::VariasVariaveis(livres)),
+    }
+}
+
+// Simpson composto com `subintervalos` (arredondado para cima até ser par),
+// limitado ao mesmo número de avaliações do adaptativo
+pub fn simpson<F>(f: F, a: f64, b: f64, subintervalos: usize) -> Result<f64, ErroCalculo>
+where
+    F: Fn(f64) -> Result<f64, ErroCalculo>,
+{
+    if subintervalos > MAX_AVALIACOES {
+        return Err(ErroCalculo::SubintervalosDemais {
+            pedidos: subintervalos,
+            maximo: MAX_AVALIACOES,
+        });
+    }
+    let n = subintervalos.max(2).next_multiple_of(2);
+    let h = (b - a) / n as f64;
+    let mut soma = f(a)? + f(b)?;
+    for i in 1..n {
+        let peso = if i % 2 == 1 { 4.0 } else { 2.0 };
+        soma += peso * f(a + i as f64 * h)?;
+    }
+    Ok(soma * h / 3.0)
+}
+
+// Simpson adaptativo: subdivide só onde as duas metades discordam do todo
+// além da tolerância. Singularidades e oscilações rápidas esgotam o limite de
+// avaliações em vez de devolver um número sem sentido.
+pub fn simpson_adaptativo<F>(f: F, a: f64, b: f64, tolerancia: f64) -> Result<f64, ErroCalculo>
+where
+    F: Fn(f64) -> Result<f64, ErroCalculo>,
+{
+    let (fa, fb) = (f(a)?, f(b)?);
+    let meio = (a + b) / 2.0;
+    let fm = f(meio)?;
+    let todo = (b - a) / 6.0 * (fa + 4.0 * fm + fb);
+    let mut intervalo = Intervalo { f: &f, avaliacoes: 3 };
+    intervalo.integrar([a, meio, b], [fa, fm, fb], todo, tolerancia, PROFUNDIDADE_MAXIMA)
+}
+
+struct Intervalo<'a, F> {
+    f: &'a F,
+    avaliacoes: usize,
+}
+
+impl<F> Intervalo<'_, F>
+where
+    F: Fn(f64) -> Result<f64, ErroCalculo>,
+{
+    fn integrar(&mut self, [a, m, b]: [f64; 3], [fa, fm, fb]: [f64; 3], todo: f64, tolerancia: f64, profundidade: usize) -> Result<f64, ErroCalculo> {
+        let (m1, m2) = ((a + m) / 2.0, (m + b) / 2.0);
+        let (f1, f2) = ((self.f)(m1)?, (self.f)(m2)?);
+        self.avaliacoes += 2;
+        let esquerda = (m - a) / 6.0 * (fa + 4.0 * f1 + fm);
+        let direita = (b - m) / 6.0 * (fm + 4.0 * f2 + fb);
+        let diferenca = esquerda + direita - todo;
+
+        // O fator 15 vem da extrapolação de Richardson do erro de Simpson
+        if diferenca.abs() <= 15.0 * tolerancia {
+            return Ok(esquerda + direita + diferenca / 15.0);
+        }
+        if profundidade == 0 || self.avaliacoes >= MAX_AVALIACOES {
+            return Err(ErroCalculo::NaoConvergiu { a, b });
+        }
+        let esquerda = self.integrar([a, m1, m], [fa, f1, fm], esquerda, tolerancia / 2.0, profundidade - 1)?;
+        let direita = self.integrar([m, m2, b], [fm, f2, fb], direita, tolerancia / 2.0, profundidade - 1)?;
+        Ok(esquerda + direita)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErroCalculo {
+    Expressao(ErroExpressao),
+    // Função ou operador sem regra de derivação (fact, mod, ∠, unidades)
+    NaoDerivavel(String),
+    VariasVariaveis(Vec<String>),
+    // A integral adaptativa não atingiu a tolerância perto de [a, b]
+    NaoConvergiu {
+        a: f64,
+        b: f64,
+    },
+    // Simpson composto pedido com mais subintervalos que o limite de avaliações
+    SubintervalosDemais {
+        pedidos: usize,
+        maximo: usize,
+    },
+}
+
+impl fmt::Display for ErroCalculo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroCalculo::Expressao(erro) => write!(f, "{}", erro),
+            ErroCalculo::NaoDerivavel(nome) => write!(f, "Não sei derivar {}", nome),
+            ErroCalculo::VariasVariaveis(nomes) => {
+                write!(f, "A expressão tem mais de uma variável: {}", nomes.join(", "))
+            }
+            ErroCalculo::NaoConvergiu { a, b } => write!(
+                f,
+                "A integral não convergiu perto de [{}, {}]; a função pode ter uma singularidade ali",
+                a, b
+            ),
+            ErroCalculo::SubintervalosDemais { pedidos, maximo } => write!(
+                f,
+                "{} subintervalos é demais; use no máximo {}",
+                pedidos, maximo
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ErroCalculo {}
+
+impl From<ErroExpressao> for ErroCalculo {
+    fn from(erro: ErroExpressao) -> Self {
+        ErroCalculo::Expressao(erro)
+    }
+}
+
+impl From<ErroAvaliacao> for ErroCalculo {
+    fn from(erro: ErroAvaliacao) -> Self {
+        ErroCalculo::Expressao(ErroExpressao::Avaliacao(erro))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressao::{analisar, SemContexto};
+
+    fn derivada_de(texto: &str, unidade: UnidadeAngulo) -> String {
+        derivar(&analisar(texto).unwrap(), "x", unidade).unwrap().legivel()
+    }
+
+    fn d(texto: &str) -> String {
+        derivada_de(texto, UnidadeAngulo::Radianos)
+    }
+
+    fn integrando(texto: &str) -> impl Fn(f64) -> Result<f64, ErroCalculo> {
+        let expr = analisar(texto).unwrap();
+        move |x| avaliar_em(&expr, "x", x, &SemContexto)
+    }
+
+    #[test]
+    fn test_derivadas_basicas() {
+        assert_eq!(d("5"), "0");
+        assert_eq!(d("x"), "1");
+        assert_eq!(d("3x + 2"), "3");
+        assert_eq!(d("x^3"), "3 * x^2");
+        assert_eq!(d("x^2 - 4x + 1"), "2 * x - 4");
+        assert_eq!(d("1 / x"), "-(1 / x^2)");
+        assert_eq!(d("x * y"), "y");
+    }
+
+    #[test]
+    fn test_regra_da_cadeia() {
+        assert_eq!(d("sin(x^2)"), "2 * x * cos(x^2)");
+        assert_eq!(d("cos(x)"), "-sin(x)");
+        assert_eq!(d("ln(x)"), "1 / x");
+        assert_eq!(d("sqrt(x)"), "1 / (2 * sqrt(x))");
+        assert_eq!(d("e^x"), "e^x");
+        assert_eq!(d("2^x"), "2^x * ln(2)");
+        assert_eq!(d("x^x"), "x^x * (ln(x) + 1)");
+        assert_eq!(d("atan(x)"), "1 / (1 + x^2)");
+    }
+
+    #[test]
+    fn test_derivada_em_graus() {
+        assert_eq!(derivada_de("sin(x)", UnidadeAngulo::Graus), "pi / 180 * cos(x)");
+        assert_eq!(derivada_de("asin(x)", UnidadeAngulo::Graus), "1 / sqrt(1 - x^2) / (pi / 180)");
+        // A derivada simbólica bate com a numérica na unidade da sessão
+        let expr = analisar("sin(x)").unwrap();
+        let derivada = derivar(&expr, "x", UnidadeAngulo::Graus).unwrap();
+        let valor = avaliar_em(&derivada, "x", 60.0, &SemContexto).unwrap();
+        assert!((valor - 0.5 * std::f64::consts::PI / 180.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_derivada_nao_suportada() {
+        let expr = analisar("fact(x)").unwrap();
+        assert_eq!(
+            derivar(&expr, "x", UnidadeAngulo::Radianos),
+            Err(ErroCalculo::NaoDerivavel("fact".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_simplificar() {
+        let simplificada = |texto: &str| simplificar(analisar(texto).unwrap()).legivel();
+        assert_eq!(simplificada("0 + x * 1"), "x");
+        assert_eq!(simplificada("x * 2 * 3"), "6 * x");
+        assert_eq!(simplificada("x - -y"), "x + y");
+        assert_eq!(simplificada("(x^2)^3"), "x^6");
+        assert_eq!(simplificada("1 / 3 + x - x"), "1 / 3");
+        assert_eq!(simplificada("0 / 0"), "0 / 0");
+        assert_eq!(simplificada("(x^5)^0.2"), "x");
+    }
+
+    #[test]
+    fn test_potencia_de_potencia() {
+        let simplificada = |texto: &str| simplificar(analisar(texto).unwrap());
+        // (x^2)^0.5 = |x|: juntar os expoentes daria x, errado para x < 0
+        let raiz = simplificada("(x^2)^0.5");
+        assert_eq!(raiz.legivel(), "(x^2)^0.5");
+        assert_eq!(avaliar_em(&raiz, "x", -3.0, &SemContexto), Ok(3.0));
+        assert_eq!(simplificada("(x^0.5)^4").legivel(), "x^2");
+    }
+
+    #[test]
+    fn test_simpson() {
+        let resultado = simpson(integrando("x^3"), 0.0, 2.0, 4).unwrap();
+        assert!((resultado - 4.0).abs() < 1e-12);
+        let resultado = simpson(integrando("sin(x)"), 0.0, 180.0, 100).unwrap();
+        assert!((resultado - 360.0 / std::f64::consts::PI).abs() < 1e-5);
+        for pedidos in [1_000_000_000, usize::MAX] {
+            assert_eq!(
+                simpson(integrando("x"), 0.0, 1.0, pedidos),
+                Err(ErroCalculo::SubintervalosDemais { pedidos, maximo: MAX_AVALIACOES })
+            );
+        }
+    }
+
+    #[test]
+    fn test_simpson_adaptativo() {
+        let resultado = simpson_adaptativo(integrando("e^(-x^2)"), -5.0, 5.0, 1e-12).unwrap();
+        assert!((resultado - std::f64::consts::PI.sqrt()).abs() < 1e-10);
+        let resultado = simpson_adaptativo(integrando("sqrt(x)"), 0.0, 1.0, 1e-10).unwrap();
+        assert!((resultado - 2.0 / 3.0).abs() < 1e-9);
+        // 1/(x - 0.3) tem uma singularidade não integrável dentro do intervalo
+        assert!(matches!(
+            simpson_adaptativo(integrando("1 / (x - 0.3)"), 0.0, 1.0, 1e-10),
+            Err(ErroCalculo::NaoConvergiu { .. })
+        ));
+    }
+}
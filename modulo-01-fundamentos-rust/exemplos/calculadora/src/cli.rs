@@ -0,0 +1,277 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::ExitCode;
+
+use crate::angulo::UnidadeAngulo;
+use crate::expressao::{self, ErroExpressao};
+use crate::registro;
+use crate::sessao::Sessao;
+
+// Códigos de saída do modo não interativo, pensados para scripts e CI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Saida {
+    Sucesso,
+    ErroUso,
+    ErroSintaxe,
+    ErroCalculo,
+}
+
+impl Saida {
+    pub fn codigo(self) -> u8 {
+        match self {
+            Saida::Sucesso => 0,
+            Saida::ErroUso => 1,
+            Saida::ErroSintaxe => 2,
+            Saida::ErroCalculo => 3,
+        }
+    }
+
+    // Só o que o tokenizador e o parser rejeitam é erro de sintaxe; nomes
+    // desconhecidos e aridade errada só aparecem ao avaliar
+    fn de_erro(erro: &ErroExpressao) -> Saida {
+        match erro {
+            ErroExpressao::Sintaxe(_) => Saida::ErroSintaxe,
+            ErroExpressao::Avaliacao(_) => Saida::ErroCalculo,
+        }
+    }
+}
+
+impl From<Saida> for ExitCode {
+    fn from(saida: Saida) -> Self {
+        ExitCode::from(saida.codigo())
+    }
+}
+
+const USO: &str = "Uso:
+  calculadora                      inicia o menu interativo
+  calculadora eval <expressão>     avalia uma expressão, ex: eval \"3^4 - 1\"
+  calculadora <função> <números>   aplica uma função, ex: sqrt 2, pow 2 10, seno 30
+  calculadora --batch [arquivo]    avalia uma expressão por linha (stdin se omitido)
+  calculadora --help               mostra esta ajuda
+
+Ângulos em graus (sin 30, asin 0.5).
+Conversões de unidades com to ou em, ex: eval \"5 km to mi\".
+Códigos de saída: 0 sucesso, 1 uso incorreto, 2 erro de sintaxe, 3 erro de cálculo";
+
+pub fn executar(argumentos: &[String]) -> Saida {
+    let stdout = io::stdout();
+    let stderr = io::stderr();
+    let mut saida = stdout.lock();
+    let mut erros = stderr.lock();
+
+    match argumentos[0].as_str() {
+        "--help" | "-h" => {
+            let _ = writeln!(saida, "{}", USO);
+            Saida::Sucesso
+        }
+        "eval" => {
+            if argumentos.len() < 2 {
+                let _ = writeln!(erros, "{}", USO);
+                return Saida::ErroUso;
+            }
+            avaliar_linha(&argumentos[1..].join(" "), &mut saida, &mut erros)
+        }
+        "--batch" => match argumentos.get(1).map(String::as_str) {
+            None | Some("-") => processar_lote(io::stdin().lock(), &mut saida, &mut erros),
+            Some(caminho) => match File::open(caminho) {
+                Ok(arquivo) => processar_lote(BufReader::new(arquivo), &mut saida, &mut erros),
+                Err(erro) => {
+                    let _ = writeln!(erros, "Não foi possível abrir {}: {}", caminho, erro);
+                    Saida::ErroUso
+                }
+            },
+        },
+        funcao => aplicar_funcao(funcao, &argumentos[1..], &mut saida, &mut erros),
+    }
+}
+
+fn avaliar_linha(texto: &str, saida: &mut impl Write, erros: &mut impl Write) -> Saida {
+    match expressao::calcular(texto) {
+        Ok(resultado) => {
+            let _ = writeln!(saida, "{}", resultado);
+            Saida::Sucesso
+        }
+        Err(erro) => {
+            let _ = writeln!(erros, "{}", erro);
+            Saida::de_erro(&erro)
+        }
+    }
+}
+
+fn aplicar_funcao(nome: &str, valores: &[String], saida: &mut impl Write, erros: &mut impl Write) -> Saida {
+    let operacao = match registro::buscar_por_identificador(nome).or_else(|| registro::buscar_por_nome(nome)) {
+        Some(operacao) => operacao,
+        None => {
+            let _ = writeln!(erros, "Comando ou função desconhecida: {}\n\n{}", nome, USO);
+            return Saida::ErroUso;
+        }
+    };
+
+    if valores.len() != operacao.aridade() {
+        let _ = writeln!(
+            erros,
+            "{} espera {} argumento(s), mas recebeu {}",
+            nome,
+            operacao.aridade(),
+            valores.len()
+        );
+        return Saida::ErroUso;
+    }
+
+    let mut numeros = Vec::new();
+    for valor in valores {
+        match valor.parse::<f64>() {
+            Ok(numero) => numeros.push(numero),
+            Err(_) => {
+                let _ = writeln!(erros, "Número inválido: {}", valor);
+                return Saida::ErroSintaxe;
+            }
+        }
+    }
+
+    match operacao.aplicar(numeros, UnidadeAngulo::default()) {
+        Ok(resultado) => {
+            let _ = writeln!(saida, "{}", resultado);
+            Saida::Sucesso
+        }
+        Err(erro) => {
+            let _ = writeln!(erros, "{}", erro);
+            Saida::ErroCalculo
+        }
+    }
+}
+
+// Uma linha de resultado por linha de entrada. Linhas com erro geram uma
+// linha vazia na saída padrão (para manter o alinhamento) e a mensagem vai
+// para a saída de erro. O código final é o do primeiro erro encontrado.
+// As linhas compartilham uma sessão, então `x = 2` vale para as seguintes.
+pub fn processar_lote(entrada: impl BufRead, saida: &mut impl Write, erros: &mut impl Write) -> Saida {
+    let mut codigo = Saida::Sucesso;
+    let mut sessao = Sessao::nova();
+
+    for (numero, linha) in entrada.lines().enumerate() {
+        let linha = match linha {
+            Ok(linha) => linha,
+            Err(erro) => {
+                let _ = writeln!(erros, "Falha ao ler a entrada: {}", erro);
+                return Saida::ErroUso;
+            }
+        };
+
+        if linha.trim().is_empty() {
+            let _ = writeln!(saida);
+            continue;
+        }
+
+        match sessao.executar(&linha) {
+            Ok(resultado) => {
+                let _ = writeln!(saida, "{}", resultado);
+            }
+            Err(erro) => {
+                let _ = writeln!(saida);
+                let _ = writeln!(erros, "linha {}: {}", numero + 1, erro);
+                if codigo == Saida::Sucesso {
+                    codigo = Saida::de_erro(&erro);
+                }
+            }
+        }
+    }
+
+    codigo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(lista: &[&str]) -> Vec<String> {
+        lista.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn lote(texto: &str) -> (Saida, String, String) {
+        let mut saida = Vec::new();
+        let mut erros = Vec::new();
+        let codigo = processar_lote(texto.as_bytes(), &mut saida, &mut erros);
+        (codigo, String::from_utf8(saida).unwrap(), String::from_utf8(erros).unwrap())
+    }
+
+    #[test]
+    fn test_codigos_de_saida() {
+        assert_eq!(Saida::Sucesso.codigo(), 0);
+        assert_ne!(Saida::ErroSintaxe.codigo(), Saida::ErroCalculo.codigo());
+    }
+
+    #[test]
+    fn test_lote_um_resultado_por_linha() {
+        let (codigo, saida, erros) = lote("3^4 - 1\n\nsqrt(16)\n");
+        assert_eq!(codigo, Saida::Sucesso);
+        assert_eq!(saida, "80\n\n4\n");
+        assert!(erros.is_empty());
+    }
+
+    #[test]
+    fn test_lote_erros_mantem_alinhamento() {
+        let (codigo, saida, erros) = lote("1 +\n1 / 0\n2\n");
+        assert_eq!(codigo, Saida::ErroSintaxe);
+        assert_eq!(saida, "\n\n2\n");
+        assert!(erros.contains("linha 1"));
+        assert!(erros.contains("linha 2"));
+    }
+
+    #[test]
+    fn test_lote_compartilha_sessao() {
+        let (codigo, saida, _) = lote("x = 4\nx * ans\n$1 + $2\n");
+        assert_eq!(codigo, Saida::Sucesso);
+        assert_eq!(saida, "4\n16\n20\n");
+    }
+
+    #[test]
+    fn test_lote_erro_de_calculo() {
+        let (codigo, _, _) = lote("sqrt(-1)\n");
+        assert_eq!(codigo, Saida::ErroCalculo);
+        // Expressões bem formadas que falham ao avaliar
+        for linha in ["y + 1", "foo(2)", "sqrt(1, 2)", "$9", "pi = 3"] {
+            assert_eq!(lote(linha).0, Saida::ErroCalculo, "{}", linha);
+        }
+        assert_eq!(lote("2 +* 3").0, Saida::ErroSintaxe);
+    }
+
+    #[test]
+    fn test_avaliar_linha_com_conversao() {
+        let mut saida = Vec::new();
+        let mut erros = Vec::new();
+        assert_eq!(avaliar_linha("1 ft to in", &mut saida, &mut erros), Saida::Sucesso);
+        assert_eq!(String::from_utf8(saida).unwrap(), "12\n");
+        assert_eq!(avaliar_linha("1 m + 1 s to m", &mut Vec::new(), &mut erros), Saida::ErroCalculo);
+        assert!(String::from_utf8(erros).unwrap().contains("Dimensões incompatíveis"));
+    }
+
+    #[test]
+    fn test_aplicar_funcao() {
+        let mut saida = Vec::new();
+        let mut erros = Vec::new();
+        let codigo = aplicar_funcao("pow", &args(&["2", "10"]), &mut saida, &mut erros);
+        assert_eq!(codigo, Saida::Sucesso);
+        assert_eq!(String::from_utf8(saida).unwrap(), "1024\n");
+    }
+
+    #[test]
+    fn test_aplicar_funcao_pelo_nome_e_em_graus() {
+        let mut saida = Vec::new();
+        let mut erros = Vec::new();
+        assert_eq!(aplicar_funcao("seno", &args(&["90"]), &mut saida, &mut erros), Saida::Sucesso);
+        assert_eq!(aplicar_funcao("atan", &args(&["1"]), &mut saida, &mut erros), Saida::Sucesso);
+        assert_eq!(String::from_utf8(saida).unwrap(), "1\n45\n");
+        assert_eq!(aplicar_funcao("tan", &args(&["90"]), &mut Vec::new(), &mut erros), Saida::ErroCalculo);
+    }
+
+    #[test]
+    fn test_aplicar_funcao_erros() {
+        let mut saida = Vec::new();
+        let mut erros = Vec::new();
+        assert_eq!(aplicar_funcao("sqrt", &args(&["-2"]), &mut saida, &mut erros), Saida::ErroCalculo);
+        assert_eq!(aplicar_funcao("sqrt", &args(&["x"]), &mut saida, &mut erros), Saida::ErroSintaxe);
+        assert_eq!(aplicar_funcao("sqrt", &args(&["1", "2"]), &mut saida, &mut erros), Saida::ErroUso);
+        assert_eq!(aplicar_funcao("foo", &args(&["1"]), &mut saida, &mut erros), Saida::ErroUso);
+    }
+}
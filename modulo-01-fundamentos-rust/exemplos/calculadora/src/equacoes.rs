@@ -0,0 +1,718 @@
+use std::f64::consts::PI;
+use std::fmt;
+
+use num_complex::Complex64;
+
+use crate::expressao::{self, ComVariavel, Contexto, ErroAvaliacao, ErroExpressao, Expr, OpBinario};
+use crate::numero::Complexo;
+
+// Limites dos métodos iterativos: param quando o passo (ou o intervalo) fica
+// menor que `tolerancia` relativa a |x|, ou falham depois de `max_iteracoes`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Criterios {
+    pub tolerancia: f64,
+    pub max_iteracoes: usize,
+}
+
+impl Default for Criterios {
+    fn default() -> Self {
+        Criterios { tolerancia: 1e-14, max_iteracoes: 100 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Raiz {
+    pub x: f64,
+    pub iteracoes: usize,
+}
+
+// Durand–Kerner converge devagar em raízes múltiplas; o limite é bem maior
+// que o dos métodos de uma variável
+const MAX_ITERACOES_DURAND_KERNER: usize = 1000;
+
+// Grau máximo de um polinômio reconhecido, em potências e em produtos
+const GRAU_MAXIMO: f64 = 64.0;
+
+// Sem intervalo, a resolução automática procura trocas de sinal aqui
+const INTERVALO_BUSCA: (f64, f64) = (-100.0, 100.0);
+const PASSOS_BUSCA: usize = 2000;
+
+// Parte de uma raiz tão pequena em relação ao módulo que é só erro numérico
+const PARTE_DESPREZIVEL: f64 = 1e-10;
+
+// Raízes de a_n·x^n + ... + a_1·x + a_0, com os coeficientes do maior grau
+// para o menor: [1, 0, -2] é x² - 2. Raízes repetidas aparecem repetidas.
+pub fn raizes_polinomio(coeficientes: &[f64]) -> Result<Vec<Complexo>, ErroEquacao> {
+    // Zeros no início não mudam nada; zeros no fim são raízes x = 0
+    let inicio = coeficientes.iter().position(|&c| c != 0.0).ok_or(ErroEquacao::GrauZero)?;
+    let fim = coeficientes.iter().rposition(|&c| c != 0.0).expect("há um coeficiente não nulo");
+    if inicio == coeficientes.len() - 1 {
+        return Err(ErroEquacao::GrauZero);
+    }
+    let p = &coeficientes[inicio..=fim];
+
+    let mut raizes = vec![Complex64::new(0.0, 0.0); coeficientes.len() - 1 - fim];
+    match p.len() - 1 {
+        0 => {}
+        1 => raizes.push(Complex64::new(-p[1] / p[0], 0.0)),
+        2 => raizes.extend(quadratica(p[0], p[1], p[2])),
+        3 => raizes.extend(cubica(p[0], p[1], p[2], p[3])),
+        _ => raizes.extend(durand_kerner(p)?),
+    }
+
+    // Coeficientes reais: as raízes não reais vêm em pares conjugados exatos
+    let raizes: Vec<Complexo> = raizes.into_iter().map(sem_residuo).collect();
+    let superiores = raizes.iter().filter(|z| z.im() > 0.0);
+    let mut raizes: Vec<Complexo> = raizes
+        .iter()
+        .filter(|z| z.eh_real())
+        .chain(superiores.clone())
+        .copied()
+        .chain(superiores.map(|z| Complexo::new(z.re(), -z.im())))
+        .collect();
+    raizes.sort_by(|a, b| a.re().total_cmp(&b.re()).then(a.im().total_cmp(&b.im())));
+    Ok(raizes)
+}
+
+fn sem_residuo(z: Complex64) -> Complexo {
+    let limite = PARTE_DESPREZIVEL * z.norm();
+    let parte = |x: f64| if x.abs() <= limite { 0.0 } else { x };
+    Complexo::new(parte(z.re), parte(z.im))
+}
+
+fn quadratica(a: f64, b: f64, c: f64) -> Vec<Complex64> {
+    let discriminante = b * b - 4.0 * a * c;
+    if discriminante < 0.0 {
+        let re = -b / (2.0 * a);
+        let im = (-discriminante).sqrt() / (2.0 * a).abs();
+        return vec![Complex64::new(re, -im), Complex64::new(re, im)];
+    }
+    // -b ± √Δ com o sinal que soma em vez de cancelar; a outra raiz vem de
+    // x1·x2 = c/a. Aqui c ≠ 0, então q ≠ 0.
+    let q = -0.5 * (b + discriminante.sqrt().copysign(b));
+    vec![Complex64::new(q / a, 0.0), Complex64::new(c / q, 0.0)]
+}
+
+fn cubica(a: f64, b: f64, c: f64, d: f64) -> Vec<Complex64> {
+    let (b, c, d) = (b / a, c / a, d / a);
+    // x = t - b/3 elimina o termo quadrático: t³ + pt + q = 0
+    let deslocamento = b / 3.0;
+    let p = c - b * b / 3.0;
+    let q = 2.0 * b.powi(3) / 27.0 - b * c / 3.0 + d;
+    let discriminante = (q / 2.0).powi(2) + (p / 3.0).powi(3);
+
+    let raizes = if discriminante > 0.0 {
+        // Uma raiz real e um par conjugado (Cardano)
+        let raiz = discriminante.sqrt();
+        let u = (-q / 2.0 + raiz).cbrt();
+        let v = (-q / 2.0 - raiz).cbrt();
+        let re = -(u + v) / 2.0;
+        let im = ((u - v) * 3.0_f64.sqrt() / 2.0).abs();
+        [Complex64::new(u + v, 0.0), Complex64::new(re, -im), Complex64::new(re, im)]
+    } else if p == 0.0 {
+        // Δ ≤ 0 com p = 0 só acontece com q = 0: raiz tripla
+        [Complex64::new(0.0, 0.0); 3]
+    } else {
+        // Três raízes reais pela forma trigonométrica, sem raízes cúbicas de complexos
+        let r = 2.0 * (-p / 3.0).sqrt();
+        let angulo = (3.0 * q / (p * r)).clamp(-1.0, 1.0).acos() / 3.0;
+        [0.0, 1.0, 2.0].map(|k| Complex64::new(r * (angulo - 2.0 * PI * k / 3.0).cos(), 0.0))
+    };
+    raizes.into_iter().map(|t| t - deslocamento).collect()
+}
+
+// Horner com coeficientes do maior grau para o menor
+fn horner(p: &[f64], z: Complex64) -> Complex64 {
+    p.iter().fold(Complex64::new(0.0, 0.0), |soma, &c| soma * z + c)
+}
+
+// Todas as raízes ao mesmo tempo: cada aproximação anda p(z)/∏(z - outras).
+// Para quando p(z) está dentro do erro de arredondamento do próprio Horner,
+// o que funciona também para raízes múltiplas.
+fn durand_kerner(p: &[f64]) -> Result<Vec<Complex64>, ErroEquacao> {
+    let grau = p.len() - 1;
+    let monico: Vec<f64> = p.iter().map(|c| c / p[0]).collect();
+    let modulos: Vec<f64> = monico.iter().map(|c| c.abs()).collect();
+
+    // Cota de Cauchy: todas as raízes têm módulo menor que `raio`. O 0.4
+    // evita pontos iniciais simétricos em relação ao eixo real.
+    let raio = 1.0 + modulos[1..].iter().fold(0.0_f64, |maior, &c| maior.max(c));
+    let mut raizes: Vec<Complex64> = (0..grau)
+        .map(|k| Complex64::from_polar(raio, 2.0 * PI * k as f64 / grau as f64 + 0.4))
+        .collect();
+
+    for _ in 0..MAX_ITERACOES_DURAND_KERNER {
+        let convergiu = raizes.iter().all(|&z| {
+            let erro_horner = 8.0 * grau as f64 * f64::EPSILON * horner(&modulos, Complex64::new(z.norm(), 0.0)).re;
+            horner(&monico, z).norm() <= erro_horner
+        });
+        if convergiu {
+            return Ok(raizes);
+        }
+        for i in 0..grau {
+            let z = raizes[i];
+            let denominador: Complex64 = (0..grau).filter(|&j| j != i).map(|j| z - raizes[j]).product();
+            if denominador != Complex64::new(0.0, 0.0) {
+                raizes[i] = z - horner(&monico, z) / denominador;
+            }
+        }
+    }
+    Err(ErroEquacao::NaoConvergiu { metodo: "Durand–Kerner", iteracoes: MAX_ITERACOES_DURAND_KERNER })
+}
+
+// Nos métodos com intervalo, uma "raiz" onde |f| cresceu em vez de cair é
+// um polo com troca de sinal, como 1/x em 0 ou tan em 90°
+fn verificar_raiz(x: f64, fx: f64, fa: f64, fb: f64, iteracoes: usize) -> Result<Raiz, ErroEquacao> {
+    if fx.abs() <= fa.abs().max(fb.abs()) {
+        Ok(Raiz { x, iteracoes })
+    } else {
+        Err(ErroEquacao::Descontinuidade { x })
+    }
+}
+
+pub fn bissecao<F>(f: F, a: f64, b: f64, criterios: Criterios) -> Result<Raiz, ErroEquacao>
+where
+    F: Fn(f64) -> Result<f64, ErroEquacao>,
+{
+    let (mut a, mut b) = (a.min(b), a.max(b));
+    let (fa_inicial, fb_inicial) = (f(a)?, f(b)?);
+    if fa_inicial == 0.0 {
+        return Ok(Raiz { x: a, iteracoes: 0 });
+    }
+    if fb_inicial == 0.0 {
+        return Ok(Raiz { x: b, iteracoes: 0 });
+    }
+    if fa_inicial.signum() == fb_inicial.signum() {
+        return Err(ErroEquacao::SemTrocaDeSinal { a, b });
+    }
+
+    let mut fa = fa_inicial;
+    for iteracao in 1..=criterios.max_iteracoes {
+        let meio = a + (b - a) / 2.0;
+        let fm = f(meio)?;
+        if fm == 0.0 || (b - a) / 2.0 <= criterios.tolerancia * meio.abs().max(1.0) {
+            return verificar_raiz(meio, fm, fa_inicial, fb_inicial, iteracao);
+        }
+        if fm.signum() == fa.signum() {
+            a = meio;
+            fa = fm;
+        } else {
+            b = meio;
+        }
+    }
+    Err(ErroEquacao::NaoConvergiu { metodo: "bisseção", iteracoes: criterios.max_iteracoes })
+}
+
+// Diferença central, com passo proporcional à escala de x
+pub fn derivada_numerica<F>(f: &F, x: f64) -> Result<f64, ErroEquacao>
+where
+    F: Fn(f64) -> Result<f64, ErroEquacao>,
+{
+    let h = f64::EPSILON.cbrt() * x.abs().max(1.0);
+    Ok((f(x + h)? - f(x - h)?) / (2.0 * h))
+}
+
+pub fn newton<F, D>(f: F, derivada: D, x0: f64, criterios: Criterios) -> Result<Raiz, ErroEquacao>
+where
+    F: Fn(f64) -> Result<f64, ErroEquacao>,
+    D: Fn(f64) -> Result<f64, ErroEquacao>,
+{
+    let mut x = x0;
+    for iteracao in 1..=criterios.max_iteracoes {
+        let fx = f(x)?;
+        if fx == 0.0 {
+            return Ok(Raiz { x, iteracoes: iteracao - 1 });
+        }
+        let dfx = derivada(x)?;
+        if dfx == 0.0 || !dfx.is_finite() {
+            return Err(ErroEquacao::DerivadaNula { x });
+        }
+        let passo = fx / dfx;
+        x -= passo;
+        if !x.is_finite() {
+            break;
+        }
+        if passo.abs() <= criterios.tolerancia * x.abs().max(1.0) {
+            return Ok(Raiz { x, iteracoes: iteracao });
+        }
+    }
+    Err(ErroEquacao::NaoConvergiu { metodo: "Newton", iteracoes: criterios.max_iteracoes })
+}
+
+// Método de Brent: interpolação quadrática inversa ou secante quando o passo
+// é confiável, bisseção quando não é. Converge sempre que há troca de sinal.
+pub fn brent<F>(f: F, a: f64, b: f64, criterios: Criterios) -> Result<Raiz, ErroEquacao>
+where
+    F: Fn(f64) -> Result<f64, ErroEquacao>,
+{
+    let (mut a, mut b, mut c) = (a, b, b);
+    let (mut fa, mut fb) = (f(a)?, f(b)?);
+    let (fa_inicial, fb_inicial) = (fa, fb);
+    if fa == 0.0 {
+        return Ok(Raiz { x: a, iteracoes: 0 });
+    }
+    if fb == 0.0 {
+        return Ok(Raiz { x: b, iteracoes: 0 });
+    }
+    if fa.signum() == fb.signum() {
+        return Err(ErroEquacao::SemTrocaDeSinal { a: a.min(b), b: a.max(b) });
+    }
+
+    // b é a melhor estimativa, c o outro extremo do intervalo com troca de
+    // sinal, a a estimativa anterior; d é o último passo e e o penúltimo
+    let mut fc = fb;
+    let (mut d, mut e) = (0.0_f64, 0.0_f64);
+    for iteracao in 1..=criterios.max_iteracoes {
+        if fb.signum() == fc.signum() {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let tolerancia = 2.0 * f64::EPSILON * b.abs() + 0.5 * criterios.tolerancia * b.abs().max(1.0);
+        let metade = 0.5 * (c - b);
+        if metade.abs() <= tolerancia || fb == 0.0 {
+            return verificar_raiz(b, fb, fa_inicial, fb_inicial, iteracao);
+        }
+
+        if e.abs() >= tolerancia && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * metade * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * metade * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+            let limite = (3.0 * metade * q - (tolerancia * q).abs()).min((e * q).abs());
+            if 2.0 * p < limite {
+                e = d;
+                d = p / q;
+            } else {
+                d = metade;
+                e = d;
+            }
+        } else {
+            d = metade;
+            e = d;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tolerancia { d } else { tolerancia.copysign(metade) };
+        fb = f(b)?;
+    }
+    Err(ErroEquacao::NaoConvergiu { metodo: "Brent", iteracoes: criterios.max_iteracoes })
+}
+
+// Divide [a, b] em `passos` partes e refina com Brent cada troca de sinal.
+// Polos e pontos fora do domínio são ignorados.
+pub fn raizes_no_intervalo<F>(f: F, a: f64, b: f64, passos: usize, criterios: Criterios) -> Result<Vec<f64>, ErroEquacao>
+where
+    F: Fn(f64) -> Result<f64, ErroEquacao>,
+{
+    let pontos: Vec<(f64, Option<f64>)> = (0..=passos)
+        .map(|k| {
+            let x = a + (b - a) * k as f64 / passos as f64;
+            (x, f(x).ok())
+        })
+        .collect();
+
+    let mut raizes = Vec::new();
+    for par in pontos.windows(2) {
+        let ((x1, f1), (x2, f2)) = (par[0], par[1]);
+        match (f1, f2) {
+            (Some(0.0), _) => raizes.push(x1),
+            (Some(f1), Some(f2)) if f2 != 0.0 && f1.signum() != f2.signum() => match brent(&f, x1, x2, criterios) {
+                Ok(raiz) => raizes.push(raiz.x),
+                Err(erro @ ErroEquacao::NaoConvergiu { .. }) => return Err(erro),
+                Err(_) => {}
+            },
+            _ => {}
+        }
+    }
+    if let Some(&(x, Some(fx))) = pontos.last() {
+        if fx == 0.0 {
+            raizes.push(x);
+        }
+    }
+    Ok(raizes)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solucao {
+    pub incognita: String,
+    pub raizes: Vec<Complexo>,
+    pub metodo: &'static str,
+}
+
+// Equação em uma incógnita; `esquerda = direita` é guardada como
+// esquerda - direita, e sem `=` a expressão é igualada a zero
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equacao {
+    pub incognita: String,
+    expr: Expr,
+}
+
+impl Equacao {
+    // A incógnita é o único nome livre. Com mais de um, os que já têm valor
+    // na sessão contam como constantes: `solve a*x = 1` com a = 2.
+    pub fn analisar(texto: &str, contexto: &impl Contexto) -> Result<Equacao, ErroEquacao> {
+        let (esquerda, direita) = texto.split_once('=').unwrap_or((texto, "0"));
+        let esquerda = expressao::analisar(esquerda).map_err(ErroExpressao::from)?;
+        let direita = expressao::analisar(direita).map_err(ErroExpressao::from)?;
+
+        let mut nomes = expressao::variaveis(&esquerda);
+        for nome in expressao::variaveis(&direita) {
+            if !nomes.contains(&nome) {
+                nomes.push(nome);
+            }
+        }
+        let livres: Vec<String> = nomes.iter().filter(|nome| contexto.variavel(nome).is_none()).cloned().collect();
+        let incognita = match (nomes.as_slice(), livres.as_slice()) {
+            ([], _) => return Err(ErroEquacao::SemIncognita),
+            ([unico], _) | (_, [unico]) => unico.clone(),
+            (_, []) => return Err(ErroEquacao::VariasIncognitas(nomes)),
+            _ => return Err(ErroEquacao::VariasIncognitas(livres)),
+        };
+
+        let expr = Expr::Binario {
+            op: OpBinario::Subtracao,
+            esquerda: Box::new(esquerda.multiplicacao_implicita(&incognita)),
+            direita: Box::new(direita.multiplicacao_implicita(&incognita)),
+        };
+        Ok(Equacao { incognita, expr })
+    }
+
+    // f(x) = esquerda - direita, sempre em f64
+    pub fn avaliar(&self, x: f64, contexto: &impl Contexto) -> Result<f64, ErroEquacao> {
+        let contexto = ComVariavel { contexto, nome: &self.incognita, valor: x };
+        Ok(expressao::avaliar::<f64>(&self.expr, &contexto)?)
+    }
+
+    // Coeficientes do maior grau para o menor, se f for um polinômio na incógnita
+    pub fn coeficientes(&self, contexto: &impl Contexto) -> Result<Option<Vec<f64>>, ErroEquacao> {
+        let coeficientes = polinomio(&self.expr, &self.incognita, contexto)?;
+        Ok(coeficientes.map(|mut coeficientes| {
+            coeficientes.reverse();
+            coeficientes
+        }))
+    }
+
+    // Polinômios dão todas as raízes, inclusive complexas; outras funções
+    // dão as raízes reais encontradas em INTERVALO_BUSCA
+    pub fn resolver(&self, contexto: &impl Contexto) -> Result<Solucao, ErroEquacao> {
+        let solucao = |raizes, metodo| Solucao { incognita: self.incognita.clone(), raizes, metodo };
+
+        if let Some(coeficientes) = self.coeficientes(contexto)? {
+            let metodo = match coeficientes.iter().skip_while(|&&c| c == 0.0).count() {
+                0 | 1 => return Err(ErroEquacao::GrauZero),
+                2 => "equação linear",
+                3 => "fórmula quadrática",
+                4 => "fórmula cúbica",
+                _ => "Durand–Kerner",
+            };
+            return Ok(solucao(raizes_polinomio(&coeficientes)?, metodo));
+        }
+
+        let (a, b) = INTERVALO_BUSCA;
+        let raizes = raizes_no_intervalo(|x| self.avaliar(x, contexto), a, b, PASSOS_BUSCA, Criterios::default())?;
+        if raizes.is_empty() {
+            return Err(ErroEquacao::RaizNaoEncontrada { a, b });
+        }
+        let raizes = raizes.into_iter().map(|x| Complexo::new(x, 0.0)).collect();
+        Ok(solucao(raizes, "Brent"))
+    }
+}
+
+pub fn resolver(texto: &str, contexto: &impl Contexto) -> Result<Solucao, ErroEquacao> {
+    Equacao::analisar(texto, contexto)?.resolver(contexto)
+}
+
+// Coeficientes do grau 0 para cima. Partes sem a incógnita são avaliadas como
+// constantes; funções, divisões pela incógnita e expoentes não inteiros fazem
+// a expressão deixar de ser polinômio (None).
+fn polinomio(expr: &Expr, incognita: &str, contexto: &impl Contexto) -> Result<Option<Vec<f64>>, ErroEquacao> {
+    if !expr.contem(incognita) {
+        return Ok(Some(vec![expressao::avaliar::<f64>(expr, contexto)?]));
+    }
+    let (op, esquerda, direita) = match expr {
+        Expr::Variavel(_) => return Ok(Some(vec![0.0, 1.0])),
+        Expr::Negacao(operando) => {
+            let p = polinomio(operando, incognita, contexto)?;
+            return Ok(p.map(|p| p.iter().map(|c| -c).collect()));
+        }
+        Expr::Binario { op, esquerda, direita } => (op, esquerda, direita),
+        _ => return Ok(None),
+    };
+    let (Some(a), Some(b)) = (polinomio(esquerda, incognita, contexto)?, polinomio(direita, incognita, contexto)?) else {
+        return Ok(None);
+    };
+
+    let resultado = match op {
+        OpBinario::Soma => somar_polinomios(&a, &b, 1.0),
+        OpBinario::Subtracao => somar_polinomios(&a, &b, -1.0),
+        OpBinario::Multiplicacao => {
+            verificar_grau((a.len() - 1 + b.len() - 1) as f64)?;
+            multiplicar_polinomios(&a, &b)
+        }
+        OpBinario::Divisao if b.len() == 1 && b[0] != 0.0 => a.iter().map(|c| c / b[0]).collect(),
+        // O grau é conferido antes de expandir: (x + 1)^1000000 seria um
+        // milhão de multiplicações
+        OpBinario::Potencia if b.len() == 1 && b[0].fract() == 0.0 && b[0] >= 0.0 => {
+            verificar_grau((a.len() - 1) as f64 * b[0])?;
+            (0..b[0] as usize).fold(vec![1.0], |produto, _| multiplicar_polinomios(&produto, &a))
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(resultado))
+}
+
+fn verificar_grau(grau: f64) -> Result<(), ErroEquacao> {
+    if grau > GRAU_MAXIMO {
+        return Err(ErroEquacao::GrauAlto { grau, maximo: GRAU_MAXIMO });
+    }
+    Ok(())
+}
+
+fn somar_polinomios(a: &[f64], b: &[f64], sinal: f64) -> Vec<f64> {
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).copied().unwrap_or(0.0) + sinal * b.get(i).copied().unwrap_or(0.0))
+        .collect()
+}
+
+fn multiplicar_polinomios(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut produto = vec![0.0; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            produto[i + j] += x * y;
+        }
+    }
+    produto
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErroEquacao {
+    Expressao(ErroExpressao),
+    SemIncognita,
+    VariasIncognitas(Vec<String>),
+    // O polinômio não depende da incógnita (ex: x - x + 1 = 0)
+    GrauZero,
+    // Polinômio com grau acima de GRAU_MAXIMO
+    GrauAlto {
+        grau: f64,
+        maximo: f64,
+    },
+    SemTrocaDeSinal {
+        a: f64,
+        b: f64,
+    },
+    DerivadaNula {
+        x: f64,
+    },
+    NaoConvergiu {
+        metodo: &'static str,
+        iteracoes: usize,
+    },
+    // O método com intervalo parou em um polo, não em uma raiz
+    Descontinuidade {
+        x: f64,
+    },
+    RaizNaoEncontrada {
+        a: f64,
+        b: f64,
+    },
+}
+
+impl fmt::Display for ErroEquacao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroEquacao::Expressao(erro) => write!(f, "{}", erro),
+            ErroEquacao::SemIncognita => write!(f, "A equação não tem incógnita"),
+            ErroEquacao::VariasIncognitas(nomes) => {
+                write!(f, "A equação tem mais de uma incógnita: {}", nomes.join(", "))
+            }
+            ErroEquacao::GrauZero => write!(f, "A equação não depende da incógnita"),
+            ErroEquacao::GrauAlto { grau, maximo } => {
+                write!(f, "Polinômio de grau {} passa do máximo de {}", grau, maximo)
+            }
+            ErroEquacao::SemTrocaDeSinal { a, b } => {
+                write!(f, "f não troca de sinal em [{}, {}]; escolha outro intervalo", a, b)
+            }
+            ErroEquacao::DerivadaNula { x } => write!(f, "Derivada nula em x = {}; tente outro chute inicial", x),
+            ErroEquacao::NaoConvergiu { metodo, iteracoes } => {
+                write!(f, "{} não convergiu em {} iterações", metodo, iteracoes)
+            }
+            ErroEquacao::Descontinuidade { x } => {
+                write!(f, "f troca de sinal em x = {} por uma descontinuidade, não por uma raiz", x)
+            }
+            ErroEquacao::RaizNaoEncontrada { a, b } => write!(
+                f,
+                "Nenhuma troca de sinal em [{}, {}]; tente Newton com um chute inicial",
+                a, b
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ErroEquacao {}
+
+impl From<ErroExpressao> for ErroEquacao {
+    fn from(erro: ErroExpressao) -> Self {
+        ErroEquacao::Expressao(erro)
+    }
+}
+
+impl From<ErroAvaliacao> for ErroEquacao {
+    fn from(erro: ErroAvaliacao) -> Self {
+        ErroEquacao::Expressao(ErroExpressao::Avaliacao(erro))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressao::SemContexto;
+
+    #[track_caller]
+    fn assert_raizes(coeficientes: &[f64], esperadas: &[(f64, f64)]) {
+        let raizes = raizes_polinomio(coeficientes).unwrap();
+        assert_eq!(raizes.len(), esperadas.len(), "{:?}", raizes);
+        for (raiz, &(re, im)) in raizes.iter().zip(esperadas) {
+            assert!((raiz.re() - re).abs() < 1e-9 && (raiz.im() - im).abs() < 1e-9, "{:?}", raizes);
+        }
+    }
+
+    fn cos_menos_x(x: f64) -> Result<f64, ErroEquacao> {
+        Ok(x.cos() - x)
+    }
+
+    const DOTTIE: f64 = 0.7390851332151607;
+
+    #[test]
+    fn test_polinomios_de_grau_baixo() {
+        assert_raizes(&[2.0, -3.0], &[(1.5, 0.0)]);
+        assert_raizes(&[1.0, 0.0, -2.0], &[(-2f64.sqrt(), 0.0), (2f64.sqrt(), 0.0)]);
+        assert_raizes(&[1.0, 2.0, 5.0], &[(-1.0, -2.0), (-1.0, 2.0)]);
+        // Raiz dupla e raízes em zero
+        assert_raizes(&[1.0, -2.0, 1.0], &[(1.0, 0.0), (1.0, 0.0)]);
+        assert_raizes(&[0.0, 3.0, 0.0, 0.0], &[(0.0, 0.0), (0.0, 0.0)]);
+        assert_eq!(raizes_polinomio(&[0.0, 5.0]), Err(ErroEquacao::GrauZero));
+    }
+
+    #[test]
+    fn test_cubica() {
+        // (x - 1)(x - 2)(x - 3)
+        assert_raizes(&[1.0, -6.0, 11.0, -6.0], &[(1.0, 0.0), (2.0, 0.0), (3.0, 0.0)]);
+        // (x - 1)(x² + 1)
+        assert_raizes(&[1.0, -1.0, 1.0, -1.0], &[(0.0, -1.0), (0.0, 1.0), (1.0, 0.0)]);
+        // (x - 1)²(x + 2)
+        assert_raizes(&[1.0, 0.0, -3.0, 2.0], &[(-2.0, 0.0), (1.0, 0.0), (1.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_durand_kerner() {
+        // x⁴ - 1
+        assert_raizes(&[1.0, 0.0, 0.0, 0.0, -1.0], &[(-1.0, 0.0), (0.0, -1.0), (0.0, 1.0), (1.0, 0.0)]);
+        // (x - 1)(x - 2)(x - 3)(x - 4)(x - 5)
+        let esperadas: Vec<(f64, f64)> = (1..=5).map(|k| (k as f64, 0.0)).collect();
+        assert_raizes(&[1.0, -15.0, 85.0, -225.0, 274.0, -120.0], &esperadas);
+    }
+
+    #[test]
+    fn test_metodos_com_intervalo() {
+        let criterios = Criterios::default();
+        assert!((bissecao(cos_menos_x, 0.0, 1.0, criterios).unwrap().x - DOTTIE).abs() < 1e-13);
+        let raiz = brent(cos_menos_x, 0.0, 1.0, criterios).unwrap();
+        assert!((raiz.x - DOTTIE).abs() < 1e-13);
+        assert!(raiz.iteracoes < 10);
+
+        assert_eq!(
+            brent(|x: f64| Ok(x * x + 1.0), -1.0, 1.0, criterios),
+            Err(ErroEquacao::SemTrocaDeSinal { a: -1.0, b: 1.0 })
+        );
+        // 1/x troca de sinal em 0 sem ter raiz
+        assert!(matches!(
+            brent(|x: f64| Ok(1.0 / x), -1.0, 2.0, criterios),
+            Err(ErroEquacao::Descontinuidade { .. })
+        ));
+        let poucas = Criterios { max_iteracoes: 5, ..criterios };
+        assert_eq!(
+            bissecao(cos_menos_x, 0.0, 1.0, poucas),
+            Err(ErroEquacao::NaoConvergiu { metodo: "bisseção", iteracoes: 5 })
+        );
+    }
+
+    #[test]
+    fn test_newton() {
+        let criterios = Criterios::default();
+        let raiz = newton(cos_menos_x, |x| derivada_numerica(&cos_menos_x, x), 1.0, criterios).unwrap();
+        assert!((raiz.x - DOTTIE).abs() < 1e-13);
+
+        let parabola = |x: f64| Ok(x * x + 1.0);
+        assert_eq!(
+            newton(parabola, |x| Ok(2.0 * x), 0.0, criterios),
+            Err(ErroEquacao::DerivadaNula { x: 0.0 })
+        );
+        // x² + 1 não tem raiz real: Newton fica oscilando
+        assert_eq!(
+            newton(parabola, |x| Ok(2.0 * x), 0.5, criterios),
+            Err(ErroEquacao::NaoConvergiu { metodo: "Newton", iteracoes: 100 })
+        );
+    }
+
+    #[test]
+    fn test_resolver_equacoes() {
+        let solucao = resolver("x^2 - 2 = 0", &SemContexto).unwrap();
+        assert_eq!(solucao.incognita, "x");
+        assert_eq!(solucao.metodo, "fórmula quadrática");
+        assert_eq!(solucao.raizes.len(), 2);
+        assert!((solucao.raizes[1].re() - 2f64.sqrt()).abs() < 1e-15);
+
+        // Multiplicação implícita e incógnita dos dois lados
+        let solucao = resolver("3t + 1 = t - 3", &SemContexto).unwrap();
+        assert_eq!(solucao.raizes, vec![Complexo::new(-2.0, 0.0)]);
+
+        let solucao = resolver("e^y = 2", &SemContexto).unwrap();
+        assert_eq!(solucao.metodo, "Brent");
+        assert!((solucao.raizes[0].re() - 2f64.ln()).abs() < 1e-13);
+    }
+
+    #[test]
+    fn test_resolver_erros() {
+        assert_eq!(resolver("1 + 1 = 2", &SemContexto), Err(ErroEquacao::SemIncognita));
+        assert_eq!(
+            resolver("x + y = 1", &SemContexto),
+            Err(ErroEquacao::VariasIncognitas(vec!["x".to_string(), "y".to_string()]))
+        );
+        assert_eq!(resolver("x - x + 1 = 0", &SemContexto), Err(ErroEquacao::GrauZero));
+        // O grau é conferido antes de expandir, em potências e em produtos
+        assert!(resolver("x^64 = 1", &SemContexto).is_ok());
+        assert_eq!(
+            resolver("(x^2 + 1)^1000000000 = 0", &SemContexto),
+            Err(ErroEquacao::GrauAlto { grau: 2e9, maximo: 64.0 })
+        );
+        assert_eq!(
+            resolver("x^40 * x^30 = 1", &SemContexto),
+            Err(ErroEquacao::GrauAlto { grau: 70.0, maximo: 64.0 })
+        );
+        assert_eq!(resolver("x^2 + 1 = ln(x) - 5", &SemContexto), Err(ErroEquacao::RaizNaoEncontrada { a: -100.0, b: 100.0 }));
+        assert!(matches!(resolver("x^2 = ", &SemContexto), Err(ErroEquacao::Expressao(ErroExpressao::Sintaxe(_)))));
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalcError {
+    DivisaoPorZero,
+    // Entrada fora do domínio da função (ex: raiz de negativo, log de zero)
+    Dominio {
+        operacao: &'static str,
+        valor: f64,
+    },
+    // Ponto em que a função tende ao infinito (ex: tangente de 90°)
+    Assintota {
+        operacao: &'static str,
+    },
+    // Entradas finitas produziram um resultado grande demais para f64
+    Overflow,
+    // O cálculo produziu NaN ou infinito a partir de entradas não finitas
+    NaoFinito,
+    // O resultado não pode ser representado sem perda no modo numérico atual
+    NaoExato {
+        operacao: &'static str,
+    },
+    // Resultado com parte imaginária fora do modo complexo
+    Imaginario,
+    // Operação que só existe para números reais (ex: fatorial de 1 + i)
+    SoReal {
+        operacao: &'static str,
+    },
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalcError::DivisaoPorZero => write!(f, "Divisão por zero"),
+            CalcError::Dominio { operacao, valor } => {
+                write!(f, "{} não está definida para {}", operacao, valor)
+            }
+            CalcError::Assintota { operacao } => {
+                write!(f, "{} não está definida neste ponto (assíntota)", operacao)
+            }
+            CalcError::Overflow => write!(f, "Resultado grande demais (overflow)"),
+            CalcError::NaoFinito => write!(f, "Resultado não é um número finito"),
+            CalcError::NaoExato { operacao } => {
+                write!(f, "O resultado de {} não é exato neste modo numérico", operacao)
+            }
+            CalcError::Imaginario => {
+                write!(f, "O resultado tem parte imaginária; use o modo complexo")
+            }
+            CalcError::SoReal { operacao } => {
+                write!(f, "{} não está definida para números complexos", operacao)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CalcError {}
+
+pub type Resultado<N = f64> = Result<N, CalcError>;
+
+// Converte resultados NaN/infinitos em erros em vez de deixá-los vazar
+pub fn verificar_finito(resultado: f64, entradas: &[f64]) -> Resultado {
+    if resultado.is_finite() {
+        Ok(resultado)
+    } else if resultado.is_infinite() && entradas.iter().all(|x| x.is_finite()) {
+        Err(CalcError::Overflow)
+    } else {
+        Err(CalcError::NaoFinito)
+    }
+}
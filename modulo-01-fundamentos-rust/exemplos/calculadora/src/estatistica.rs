@@ -0,0 +1,438 @@
+use std::fmt;
+
+// Estatística descritiva sobre listas de números. Tudo em f64: médias e
+// desvios de dados medidos não têm forma exata, e as listas vêm de texto ou
+// de arquivos CSV.
+
+// Média, variância, mínimo e máximo em uma única passada pelo algoritmo de
+// Welford. A fórmula ingênua (soma dos quadrados menos o quadrado da soma)
+// perde todos os dígitos quando os valores são grandes e próximos entre si.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Acumulador {
+    contagem: usize,
+    media: f64,
+    // Soma dos quadrados dos desvios em relação à média corrente
+    m2: f64,
+    minimo: f64,
+    maximo: f64,
+}
+
+impl Acumulador {
+    pub fn adicionar(&mut self, valor: f64) {
+        self.contagem += 1;
+        if self.contagem == 1 {
+            self.minimo = valor;
+            self.maximo = valor;
+        } else {
+            self.minimo = self.minimo.min(valor);
+            self.maximo = self.maximo.max(valor);
+        }
+        let delta = valor - self.media;
+        self.media += delta / self.contagem as f64;
+        self.m2 += delta * (valor - self.media);
+    }
+
+    pub fn contagem(&self) -> usize {
+        self.contagem
+    }
+
+    pub fn media(&self) -> Option<f64> {
+        (self.contagem > 0).then_some(self.media)
+    }
+
+    pub fn minimo(&self) -> Option<f64> {
+        (self.contagem > 0).then_some(self.minimo)
+    }
+
+    pub fn maximo(&self) -> Option<f64> {
+        (self.contagem > 0).then_some(self.maximo)
+    }
+
+    // Divide por n: os dados são a população inteira
+    pub fn variancia_populacional(&self) -> Option<f64> {
+        (self.contagem > 0).then(|| self.m2 / self.contagem as f64)
+    }
+
+    // Divide por n - 1 (correção de Bessel): os dados são uma amostra
+    pub fn variancia_amostral(&self) -> Option<f64> {
+        (self.contagem > 1).then(|| self.m2 / (self.contagem - 1) as f64)
+    }
+
+    pub fn desvio_padrao_populacional(&self) -> Option<f64> {
+        self.variancia_populacional().map(f64::sqrt)
+    }
+
+    pub fn desvio_padrao_amostral(&self) -> Option<f64> {
+        self.variancia_amostral().map(f64::sqrt)
+    }
+}
+
+impl FromIterator<f64> for Acumulador {
+    fn from_iter<I: IntoIterator<Item = f64>>(valores: I) -> Self {
+        let mut acumulador = Acumulador::default();
+        for valor in valores {
+            acumulador.adicionar(valor);
+        }
+        acumulador
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resumo {
+    pub acumulador: Acumulador,
+    pub mediana: f64,
+    // Vazia quando nenhum valor se repete
+    pub modas: Vec<f64>,
+    pub quartis: [f64; 3],
+}
+
+pub fn resumir(valores: &[f64]) -> Result<Resumo, ErroEstatistica> {
+    if valores.is_empty() {
+        return Err(ErroEstatistica::ListaVazia);
+    }
+    let ordenados = ordenar(valores);
+    Ok(Resumo {
+        acumulador: valores.iter().copied().collect(),
+        mediana: percentil_ordenado(&ordenados, 50.0),
+        modas: modas_ordenado(&ordenados),
+        quartis: [25.0, 50.0, 75.0].map(|p| percentil_ordenado(&ordenados, p)),
+    })
+}
+
+fn ordenar(valores: &[f64]) -> Vec<f64> {
+    let mut ordenados = valores.to_vec();
+    ordenados.sort_by(f64::total_cmp);
+    ordenados
+}
+
+// Percentil com interpolação linear entre as posições vizinhas (o mesmo
+// critério do PERCENTIL.INC das planilhas): p = 0 é o mínimo, p = 100 o máximo
+pub fn percentil(valores: &[f64], p: f64) -> Result<f64, ErroEstatistica> {
+    if !(0.0..=100.0).contains(&p) {
+        return Err(ErroEstatistica::PercentilInvalido(p));
+    }
+    if valores.is_empty() {
+        return Err(ErroEstatistica::ListaVazia);
+    }
+    Ok(percentil_ordenado(&ordenar(valores), p))
+}
+
+fn percentil_ordenado(ordenados: &[f64], p: f64) -> f64 {
+    let posicao = p / 100.0 * (ordenados.len() - 1) as f64;
+    let abaixo = posicao.floor() as usize;
+    let acima = posicao.ceil() as usize;
+    let fracao = posicao - abaixo as f64;
+    ordenados[abaixo] + (ordenados[acima] - ordenados[abaixo]) * fracao
+}
+
+// Todos os valores que aparecem o maior número de vezes, em ordem crescente
+fn modas_ordenado(ordenados: &[f64]) -> Vec<f64> {
+    let mut modas = Vec::new();
+    let mut maior = 1;
+    for grupo in ordenados.chunk_by(|a, b| a == b) {
+        if grupo.len() > maior {
+            maior = grupo.len();
+            modas.clear();
+        }
+        if grupo.len() == maior && maior > 1 {
+            modas.push(grupo[0]);
+        }
+    }
+    modas
+}
+
+// Reta de mínimos quadrados y = inclinacao * x + intercepto
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Regressao {
+    pub inclinacao: f64,
+    pub intercepto: f64,
+    // Coeficiente de correlação de Pearson (r); r² é a fração da variância
+    // de y explicada pela reta
+    pub correlacao: f64,
+    pub pontos: usize,
+}
+
+impl Regressao {
+    pub fn prever(&self, x: f64) -> f64 {
+        self.inclinacao * x + self.intercepto
+    }
+}
+
+impl fmt::Display for Regressao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sinal = if self.intercepto < 0.0 { '-' } else { '+' };
+        write!(f, "y = {}x {} {}", self.inclinacao, sinal, self.intercepto.abs())
+    }
+}
+
+// Também em uma única passada: as covariâncias são atualizadas como no
+// Acumulador, sem somar x·y diretamente
+pub fn regressao_linear(x: &[f64], y: &[f64]) -> Result<Regressao, ErroEstatistica> {
+    if x.len() != y.len() {
+        return Err(ErroEstatistica::TamanhosDiferentes { x: x.len(), y: y.len() });
+    }
+    if x.len() < 2 {
+        return Err(ErroEstatistica::PoucosValores { minimo: 2, obtidos: x.len() });
+    }
+
+    let (mut media_x, mut media_y) = (0.0, 0.0);
+    let (mut m2_x, mut m2_y, mut comomento) = (0.0, 0.0, 0.0);
+    for (n, (&xi, &yi)) in x.iter().zip(y).enumerate() {
+        let n = (n + 1) as f64;
+        let delta_x = xi - media_x;
+        let delta_y = yi - media_y;
+        media_x += delta_x / n;
+        media_y += delta_y / n;
+        m2_x += delta_x * (xi - media_x);
+        m2_y += delta_y * (yi - media_y);
+        comomento += delta_x * (yi - media_y);
+    }
+
+    if m2_x == 0.0 {
+        return Err(ErroEstatistica::XConstante);
+    }
+    let inclinacao = comomento / m2_x;
+    // y constante fica exatamente sobre a reta horizontal
+    let correlacao = if m2_y == 0.0 { 1.0 } else { comomento / (m2_x * m2_y).sqrt() };
+    Ok(Regressao {
+        inclinacao,
+        intercepto: media_y - inclinacao * media_x,
+        correlacao,
+        pontos: x.len(),
+    })
+}
+
+// Lista digitada pelo usuário: números separados por vírgula, ponto e
+// vírgula ou espaços (o separador decimal é sempre o ponto)
+pub fn ler_lista(texto: &str) -> Result<Vec<f64>, ErroEstatistica> {
+    texto
+        .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .filter(|parte| !parte.is_empty())
+        .map(|parte| ler_valor(parte).ok_or_else(|| ErroEstatistica::ValorInvalido(parte.to_string())))
+        .collect()
+}
+
+fn ler_valor(texto: &str) -> Option<f64> {
+    texto.parse::<f64>().ok().filter(|valor| valor.is_finite())
+}
+
+// Lê as colunas pedidas de um CSV. Cada coluna é indicada pelo nome no
+// cabeçalho ou pela posição (1, 2, ...). Linhas com alguma das células vazias
+// são ignoradas, para que os valores das colunas continuem pareados.
+pub fn colunas_csv(conteudo: &str, colunas: &[&str]) -> Result<Vec<Vec<f64>>, ErroEstatistica> {
+    let mut linhas = conteudo
+        .lines()
+        .enumerate()
+        .filter(|(_, linha)| !linha.trim().is_empty())
+        .peekable();
+    let Some(&(_, primeira)) = linhas.peek() else {
+        return Err(ErroEstatistica::ListaVazia);
+    };
+    // Arquivos exportados com vírgula decimal usam ';' entre as colunas
+    let separador = if primeira.contains(';') { ';' } else { ',' };
+    let campos = |linha: &'_ str| -> Vec<String> {
+        linha.split(separador).map(|campo| campo.trim().trim_matches('"').to_string()).collect()
+    };
+
+    let primeira = campos(primeira);
+    let tem_cabecalho = primeira.iter().any(|campo| !campo.is_empty() && ler_valor(campo).is_none());
+    let indices = colunas
+        .iter()
+        .map(|coluna| indice_coluna(coluna, &primeira, tem_cabecalho))
+        .collect::<Result<Vec<usize>, _>>()?;
+    if tem_cabecalho {
+        linhas.next();
+    }
+
+    let mut resultado = vec![Vec::new(); indices.len()];
+    for (numero, linha) in linhas {
+        let campos = campos(linha);
+        let celulas: Vec<&str> = indices
+            .iter()
+            .map(|&indice| campos.get(indice).map(String::as_str).unwrap_or(""))
+            .collect();
+        if celulas.iter().any(|celula| celula.is_empty()) {
+            continue;
+        }
+        for (valores, celula) in resultado.iter_mut().zip(celulas) {
+            let valor = ler_valor(celula).ok_or_else(|| ErroEstatistica::CelulaInvalida {
+                linha: numero + 1,
+                texto: celula.to_string(),
+            })?;
+            valores.push(valor);
+        }
+    }
+    Ok(resultado)
+}
+
+fn indice_coluna(coluna: &str, primeira: &[String], tem_cabecalho: bool) -> Result<usize, ErroEstatistica> {
+    let coluna = coluna.trim();
+    if let Ok(posicao) = coluna.parse::<usize>() {
+        if posicao >= 1 && posicao <= primeira.len() {
+            return Ok(posicao - 1);
+        }
+    }
+    if tem_cabecalho {
+        if let Some(indice) = primeira.iter().position(|nome| nome.eq_ignore_ascii_case(coluna)) {
+            return Ok(indice);
+        }
+    }
+    Err(ErroEstatistica::ColunaInexistente(coluna.to_string()))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErroEstatistica {
+    ListaVazia,
+    PoucosValores {
+        minimo: usize,
+        obtidos: usize,
+    },
+    PercentilInvalido(f64),
+    TamanhosDiferentes {
+        x: usize,
+        y: usize,
+    },
+    // Com todos os x iguais a reta seria vertical
+    XConstante,
+    ValorInvalido(String),
+    CelulaInvalida {
+        linha: usize,
+        texto: String,
+    },
+    ColunaInexistente(String),
+    // Falha ao abrir o arquivo CSV, já com o caminho na mensagem
+    Arquivo(String),
+}
+
+impl fmt::Display for ErroEstatistica {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroEstatistica::ListaVazia => write!(f, "Nenhum número informado"),
+            ErroEstatistica::PoucosValores { minimo, obtidos } => {
+                write!(f, "São necessários pelo menos {} valores (informados: {})", minimo, obtidos)
+            }
+            ErroEstatistica::PercentilInvalido(p) => {
+                write!(f, "Percentil deve estar entre 0 e 100, não {}", p)
+            }
+            ErroEstatistica::TamanhosDiferentes { x, y } => {
+                write!(f, "x e y devem ter o mesmo tamanho ({} e {} valores)", x, y)
+            }
+            ErroEstatistica::XConstante => {
+                write!(f, "Todos os valores de x são iguais; a regressão não está definida")
+            }
+            ErroEstatistica::ValorInvalido(texto) => write!(f, "Número inválido: {}", texto),
+            ErroEstatistica::CelulaInvalida { linha, texto } => {
+                write!(f, "Número inválido na linha {}: {}", linha, texto)
+            }
+            ErroEstatistica::ColunaInexistente(coluna) => write!(f, "Coluna não encontrada: {}", coluna),
+            ErroEstatistica::Arquivo(mensagem) => write!(f, "Não foi possível ler {}", mensagem),
+        }
+    }
+}
+
+impl std::error::Error for ErroEstatistica {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_welford_e_estavel() {
+        // A fórmula ingênua dá variância negativa ou zero para estes valores
+        let valores = [4.0, 7.0, 13.0, 16.0].map(|x| x + 1e9);
+        let acumulador: Acumulador = valores.into_iter().collect();
+        assert_eq!(acumulador.media(), Some(1e9 + 10.0));
+        assert_eq!(acumulador.variancia_amostral(), Some(30.0));
+        assert_eq!(acumulador.variancia_populacional(), Some(22.5));
+        assert_eq!(acumulador.minimo(), Some(1e9 + 4.0));
+        assert_eq!(acumulador.maximo(), Some(1e9 + 16.0));
+    }
+
+    #[test]
+    fn test_acumulador_com_poucos_valores() {
+        let vazio = Acumulador::default();
+        assert_eq!(vazio.media(), None);
+        assert_eq!(vazio.variancia_populacional(), None);
+
+        let um: Acumulador = [5.0].into_iter().collect();
+        assert_eq!(um.variancia_populacional(), Some(0.0));
+        assert_eq!(um.variancia_amostral(), None);
+    }
+
+    #[test]
+    fn test_mediana_e_percentis() {
+        assert_eq!(percentil(&[3.0, 1.0, 2.0], 50.0), Ok(2.0));
+        assert_eq!(percentil(&[4.0, 1.0, 3.0, 2.0], 50.0), Ok(2.5));
+        let valores = [15.0, 20.0, 35.0, 40.0, 50.0];
+        assert_eq!(percentil(&valores, 0.0), Ok(15.0));
+        assert_eq!(percentil(&valores, 40.0), Ok(29.0));
+        assert_eq!(percentil(&valores, 100.0), Ok(50.0));
+        assert_eq!(percentil(&valores, 101.0), Err(ErroEstatistica::PercentilInvalido(101.0)));
+        assert_eq!(percentil(&[], 50.0), Err(ErroEstatistica::ListaVazia));
+    }
+
+    #[test]
+    fn test_modas() {
+        let modas = |valores: &[f64]| resumir(valores).unwrap().modas;
+        assert_eq!(modas(&[1.0, 2.0, 2.0, 3.0]), vec![2.0]);
+        assert_eq!(modas(&[3.0, 1.0, 3.0, 1.0, 2.0]), vec![1.0, 3.0]);
+        assert_eq!(modas(&[1.0, 2.0, 3.0]), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn test_resumo() {
+        let resumo = resumir(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+        assert_eq!(resumo.acumulador.media(), Some(5.0));
+        assert_eq!(resumo.acumulador.desvio_padrao_populacional(), Some(2.0));
+        assert_eq!(resumo.mediana, 4.5);
+        assert_eq!(resumo.modas, vec![4.0]);
+        assert_eq!(resumo.quartis, [4.0, 4.5, 5.5]);
+    }
+
+    #[test]
+    fn test_regressao_linear() {
+        let regressao = regressao_linear(&[1.0, 2.0, 3.0, 4.0], &[3.0, 5.0, 7.0, 9.0]).unwrap();
+        assert_eq!(regressao.inclinacao, 2.0);
+        assert_eq!(regressao.intercepto, 1.0);
+        assert_eq!(regressao.correlacao, 1.0);
+        assert_eq!(regressao.prever(10.0), 21.0);
+        assert_eq!(regressao.to_string(), "y = 2x + 1");
+
+        let regressao = regressao_linear(&[1.0, 2.0, 3.0], &[2.0, 1.0, 3.0]).unwrap();
+        assert_eq!(regressao.inclinacao, 0.5);
+        assert_eq!(regressao.correlacao, 0.5);
+    }
+
+    #[test]
+    fn test_regressao_invalida() {
+        assert_eq!(regressao_linear(&[1.0, 2.0], &[1.0]), Err(ErroEstatistica::TamanhosDiferentes { x: 2, y: 1 }));
+        assert_eq!(regressao_linear(&[1.0], &[1.0]), Err(ErroEstatistica::PoucosValores { minimo: 2, obtidos: 1 }));
+        assert_eq!(regressao_linear(&[2.0, 2.0], &[1.0, 3.0]), Err(ErroEstatistica::XConstante));
+    }
+
+    #[test]
+    fn test_ler_lista() {
+        assert_eq!(ler_lista("1, 2.5;3  -4e1"), Ok(vec![1.0, 2.5, 3.0, -40.0]));
+        assert_eq!(ler_lista("1, dois"), Err(ErroEstatistica::ValorInvalido("dois".to_string())));
+        assert_eq!(ler_lista("1 inf"), Err(ErroEstatistica::ValorInvalido("inf".to_string())));
+    }
+
+    #[test]
+    fn test_colunas_csv() {
+        let csv = "nome,altura,peso\nana,1.60,55\nbia,,60\n\"caio\",1.80,80\n";
+        assert_eq!(colunas_csv(csv, &["altura"]), Ok(vec![vec![1.60, 1.80]]));
+        assert_eq!(colunas_csv(csv, &["3"]), Ok(vec![vec![55.0, 60.0, 80.0]]));
+        assert_eq!(colunas_csv(csv, &["Altura", "peso"]), Ok(vec![vec![1.60, 1.80], vec![55.0, 80.0]]));
+        assert_eq!(colunas_csv(csv, &["idade"]), Err(ErroEstatistica::ColunaInexistente("idade".to_string())));
+        assert_eq!(
+            colunas_csv(csv, &["nome"]),
+            Err(ErroEstatistica::CelulaInvalida { linha: 2, texto: "ana".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_colunas_csv_sem_cabecalho() {
+        assert_eq!(colunas_csv("1;10\n2;20\n", &["2"]), Ok(vec![vec![10.0, 20.0]]));
+        assert_eq!(colunas_csv("", &["1"]), Err(ErroEstatistica::ListaVazia));
+    }
+}
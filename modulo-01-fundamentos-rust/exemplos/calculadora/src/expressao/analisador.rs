@@ -0,0 +1,335 @@
+use super::arvore::{Comando, Expr, OpBinario, PRECEDENCIA_UNARIO};
+use super::erro::{ErroSintaxe, TipoErroSintaxe};
+use super::tokenizador::{tokenizar, TipoToken, Token};
+
+// Analisador por "precedence climbing": cada chamada de `expressao` consome
+// operadores binários com precedência maior ou igual à mínima recebida.
+struct Analisador {
+    tokens: Vec<Token>,
+    posicao: usize,
+}
+
+// Separam a expressão da unidade de destino: 5 km to mi, 100 °F em °C
+pub const PALAVRAS_CONVERSAO: [&str; 2] = ["to", "em"];
+
+pub fn analisar(texto: &str) -> Result<Expr, ErroSintaxe> {
+    let tokens = tokenizar(texto)?;
+    let mut analisador = Analisador { tokens, posicao: 0 };
+    analisador.expressao_completa()
+}
+
+pub fn analisar_comando(texto: &str) -> Result<Comando, ErroSintaxe> {
+    let tokens = tokenizar(texto)?;
+
+    if let [Token { tipo: TipoToken::Identificador(nome), .. }, Token { tipo: TipoToken::Igual, .. }, ..] =
+        tokens.as_slice()
+    {
+        let variavel = nome.clone();
+        let mut analisador = Analisador { tokens, posicao: 2 };
+        let expr = analisador.expressao_completa()?;
+        return Ok(Comando::Atribuicao { variavel, expr });
+    }
+
+    let mut analisador = Analisador { tokens, posicao: 0 };
+    let expr = analisador.expressao(0)?;
+    if analisador.palavra_de_conversao() {
+        analisador.avancar();
+        let inicio = analisador.posicao;
+        let destino = analisador.expressao_completa()?;
+        let unidade = analisador.tokens[inicio..analisador.posicao]
+            .iter()
+            .map(|token| token.tipo.descricao())
+            .collect();
+        return Ok(Comando::Conversao { expr, destino, unidade });
+    }
+    analisador.exigir_fim()?;
+    Ok(Comando::Expressao(expr))
+}
+
+impl Analisador {
+    fn expressao_completa(&mut self) -> Result<Expr, ErroSintaxe> {
+        let expr = self.expressao(0)?;
+        self.exigir_fim()?;
+        Ok(expr)
+    }
+
+    fn exigir_fim(&self) -> Result<(), ErroSintaxe> {
+        let token = self.atual();
+        if token.tipo != TipoToken::Fim {
+            return Err(self.inesperado(token));
+        }
+        Ok(())
+    }
+
+    fn palavra_de_conversao(&self) -> bool {
+        matches!(&self.atual().tipo, TipoToken::Identificador(nome) if PALAVRAS_CONVERSAO.contains(&nome.as_str()))
+    }
+
+    fn proximo(&self) -> TipoToken {
+        self.tokens
+            .get(self.posicao + 1)
+            .map_or(TipoToken::Fim, |token| token.tipo.clone())
+    }
+
+    // Um identificador logo depois de um valor é a sua unidade: 5 km, (1 + 2) h.
+    // A unidade pode ter expoente inteiro, que liga antes do valor: 3 m^2.
+    fn unidade_opcional(&mut self, valor: Expr) -> Result<Expr, ErroSintaxe> {
+        let nome = match self.atual().tipo {
+            TipoToken::Identificador(nome) if !self.palavra_de_conversao() && self.proximo() != TipoToken::AbreParentese => nome,
+            _ => return Ok(valor),
+        };
+        self.avancar();
+
+        let mut unidade = Expr::Variavel(nome);
+        if self.atual().tipo == TipoToken::Potencia {
+            self.avancar();
+            let expoente = self.expressao(PRECEDENCIA_UNARIO)?;
+            unidade = Expr::Binario {
+                op: OpBinario::Potencia,
+                esquerda: Box::new(unidade),
+                direita: Box::new(expoente),
+            };
+        }
+        Ok(Expr::ComUnidade { valor: Box::new(valor), unidade: Box::new(unidade) })
+    }
+
+    fn atual(&self) -> Token {
+        self.tokens[self.posicao].clone()
+    }
+
+    fn avancar(&mut self) -> Token {
+        let token = self.atual();
+        if token.tipo != TipoToken::Fim {
+            self.posicao += 1;
+        }
+        token
+    }
+
+    fn inesperado(&self, token: Token) -> ErroSintaxe {
+        let tipo = match token.tipo {
+            TipoToken::Fim => TipoErroSintaxe::FimInesperado,
+            outro => TipoErroSintaxe::TokenInesperado(outro.descricao()),
+        };
+        ErroSintaxe { coluna: token.coluna, tipo }
+    }
+
+    fn operador_binario(tipo: &TipoToken) -> Option<OpBinario> {
+        match tipo {
+            TipoToken::Mais => Some(OpBinario::Soma),
+            TipoToken::Menos => Some(OpBinario::Subtracao),
+            TipoToken::Vezes => Some(OpBinario::Multiplicacao),
+            TipoToken::Dividir => Some(OpBinario::Divisao),
+            TipoToken::Resto => Some(OpBinario::Resto),
+            TipoToken::Potencia => Some(OpBinario::Potencia),
+            TipoToken::Polar => Some(OpBinario::Polar),
+            _ => None,
+        }
+    }
+
+    fn expressao(&mut self, precedencia_minima: u8) -> Result<Expr, ErroSintaxe> {
+        let mut esquerda = self.unario()?;
+
+        while let Some(op) = Self::operador_binario(&self.atual().tipo) {
+            if op.precedencia() < precedencia_minima {
+                break;
+            }
+            self.avancar();
+
+            let proxima_minima = if op.associativo_a_direita() {
+                op.precedencia()
+            } else {
+                op.precedencia() + 1
+            };
+            let direita = self.expressao(proxima_minima)?;
+
+            esquerda = Expr::Binario {
+                op,
+                esquerda: Box::new(esquerda),
+                direita: Box::new(direita),
+            };
+        }
+
+        Ok(esquerda)
+    }
+
+    fn unario(&mut self) -> Result<Expr, ErroSintaxe> {
+        match self.atual().tipo {
+            TipoToken::Menos => {
+                self.avancar();
+                let operando = self.expressao(PRECEDENCIA_UNARIO)?;
+                Ok(Expr::Negacao(Box::new(operando)))
+            }
+            TipoToken::Mais => {
+                self.avancar();
+                self.expressao(PRECEDENCIA_UNARIO)
+            }
+            _ => self.primario(),
+        }
+    }
+
+    fn primario(&mut self) -> Result<Expr, ErroSintaxe> {
+        let token = self.avancar();
+        match token.tipo {
+            TipoToken::Numero(literal) => self.unidade_opcional(Expr::Numero(literal)),
+            TipoToken::Historico(indice) => self.unidade_opcional(Expr::Historico(indice)),
+            TipoToken::Identificador(nome) => {
+                if self.atual().tipo == TipoToken::AbreParentese {
+                    let abertura = self.avancar();
+                    let argumentos = self.argumentos(&abertura)?;
+                    self.unidade_opcional(Expr::Chamada { funcao: nome, argumentos })
+                } else {
+                    self.unidade_opcional(Expr::Variavel(nome))
+                }
+            }
+            TipoToken::AbreParentese => {
+                let expr = self.expressao(0)?;
+                self.fechar_parentese(&token)?;
+                self.unidade_opcional(expr)
+            }
+            _ => Err(self.inesperado(token)),
+        }
+    }
+
+    fn argumentos(&mut self, abertura: &Token) -> Result<Vec<Expr>, ErroSintaxe> {
+        let mut argumentos = Vec::new();
+
+        if self.atual().tipo == TipoToken::FechaParentese {
+            self.avancar();
+            return Ok(argumentos);
+        }
+
+        loop {
+            argumentos.push(self.expressao(0)?);
+            if self.atual().tipo == TipoToken::Virgula {
+                self.avancar();
+            } else {
+                break;
+            }
+        }
+
+        self.fechar_parentese(abertura)?;
+        Ok(argumentos)
+    }
+
+    fn fechar_parentese(&mut self, abertura: &Token) -> Result<(), ErroSintaxe> {
+        let token = self.avancar();
+        match token.tipo {
+            TipoToken::FechaParentese => Ok(()),
+            TipoToken::Fim => Err(ErroSintaxe {
+                coluna: abertura.coluna,
+                tipo: TipoErroSintaxe::ParenteseNaoFechado,
+            }),
+            _ => Err(self.inesperado(token)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forma(texto: &str) -> String {
+        analisar(texto).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_precedencia() {
+        assert_eq!(forma("1 + 2 * 3"), "(1 + (2 * 3))");
+        assert_eq!(forma("(1 + 2) * 3"), "((1 + 2) * 3)");
+        assert_eq!(forma("8 / 4 / 2"), "((8 / 4) / 2)");
+    }
+
+    #[test]
+    fn test_potencia_associativa_a_direita() {
+        assert_eq!(forma("2 ^ 3 ^ 2"), "(2 ^ (3 ^ 2))");
+    }
+
+    #[test]
+    fn test_menos_unario() {
+        assert_eq!(forma("-2 ^ 2"), "(-(2 ^ 2))");
+        assert_eq!(forma("-2 * 3"), "((-2) * 3)");
+        assert_eq!(forma("2 ^ -1"), "(2 ^ (-1))");
+    }
+
+    #[test]
+    fn test_chamada_de_funcao() {
+        assert_eq!(forma("sin(30)"), "sin(30)");
+        assert_eq!(forma("pow(2, 3 + 1)"), "pow(2, (3 + 1))");
+        assert_eq!(forma("2 * (3 + sin(30)) ^ 2 / 4"), "((2 * ((3 + sin(30)) ^ 2)) / 4)");
+    }
+
+    #[test]
+    fn test_erro_token_inesperado() {
+        let erro = analisar("2 + * 3").unwrap_err();
+        assert_eq!(erro.coluna, 5);
+        assert_eq!(erro.tipo, TipoErroSintaxe::TokenInesperado("*".to_string()));
+    }
+
+    #[test]
+    fn test_erro_fim_inesperado() {
+        let erro = analisar("2 +").unwrap_err();
+        assert_eq!(erro.coluna, 4);
+        assert_eq!(erro.tipo, TipoErroSintaxe::FimInesperado);
+    }
+
+    #[test]
+    fn test_erro_parentese_nao_fechado() {
+        let erro = analisar("2 * (3 + 4").unwrap_err();
+        assert_eq!(erro.coluna, 5);
+        assert_eq!(erro.tipo, TipoErroSintaxe::ParenteseNaoFechado);
+    }
+
+    #[test]
+    fn test_analisar_comando_atribuicao() {
+        let comando = analisar_comando("x = 2 * $1").unwrap();
+        match comando {
+            Comando::Atribuicao { variavel, expr } => {
+                assert_eq!(variavel, "x");
+                assert_eq!(expr.to_string(), "(2 * $1)");
+            }
+            outro => panic!("esperava atribuição, obteve {:?}", outro),
+        }
+        assert!(matches!(analisar_comando("x + 1").unwrap(), Comando::Expressao(_)));
+    }
+
+    #[test]
+    fn test_unidades() {
+        assert_eq!(forma("5 km"), "(5 km)");
+        assert_eq!(forma("2 km + 300 m"), "((2 km) + (300 m))");
+        assert_eq!(forma("3 m^2 * 2"), "((3 (m ^ 2)) * 2)");
+        assert_eq!(forma("60 km/h"), "((60 km) / h)");
+        assert_eq!(forma("-40 °F"), "(-(40 °F))");
+        assert_eq!(forma("(1 + 2) h"), "((1 + 2) h)");
+    }
+
+    #[test]
+    fn test_analisar_comando_conversao() {
+        match analisar_comando("100 °F to °C").unwrap() {
+            Comando::Conversao { expr, destino, unidade } => {
+                assert_eq!(expr.to_string(), "(100 °F)");
+                assert_eq!(destino.to_string(), "°C");
+                assert_eq!(unidade, "°C");
+            }
+            outro => panic!("esperava conversão, obteve {:?}", outro),
+        }
+        match analisar_comando("1 mi em km / h").unwrap() {
+            Comando::Conversao { unidade, .. } => assert_eq!(unidade, "km/h"),
+            outro => panic!("esperava conversão, obteve {:?}", outro),
+        }
+        let erro = analisar_comando("5 km to").unwrap_err();
+        assert_eq!(erro.tipo, TipoErroSintaxe::FimInesperado);
+    }
+
+    #[test]
+    fn test_erro_atribuicao_fora_do_inicio() {
+        let erro = analisar("1 + x = 2").unwrap_err();
+        assert_eq!(erro.coluna, 7);
+        assert_eq!(erro.tipo, TipoErroSintaxe::TokenInesperado("=".to_string()));
+    }
+
+    #[test]
+    fn test_erro_sobra_no_final() {
+        let erro = analisar("2 3").unwrap_err();
+        assert_eq!(erro.coluna, 3);
+    }
+}
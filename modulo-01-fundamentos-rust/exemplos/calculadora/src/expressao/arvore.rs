@@ -0,0 +1,183 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OpBinario {
+    Soma,
+    Subtracao,
+    Multiplicacao,
+    Divisao,
+    Resto,
+    Potencia,
+    // r∠θ: complexo de módulo r e ângulo θ
+    Polar,
+}
+
+impl OpBinario {
+    // Precedência usada pelo analisador: quanto maior, mais forte a ligação
+    pub fn precedencia(&self) -> u8 {
+        match self {
+            OpBinario::Soma | OpBinario::Subtracao => 1,
+            OpBinario::Multiplicacao | OpBinario::Divisao | OpBinario::Resto | OpBinario::Polar => 2,
+            OpBinario::Potencia => 4,
+        }
+    }
+
+    pub fn associativo_a_direita(&self) -> bool {
+        matches!(self, OpBinario::Potencia)
+    }
+
+    pub fn simbolo(&self) -> &'static str {
+        match self {
+            OpBinario::Soma => "+",
+            OpBinario::Subtracao => "-",
+            OpBinario::Multiplicacao => "*",
+            OpBinario::Divisao => "/",
+            OpBinario::Resto => "%",
+            OpBinario::Potencia => "^",
+            OpBinario::Polar => "∠",
+        }
+    }
+}
+
+// Menos unário fica entre multiplicação e potência: -2^2 = -(2^2)
+pub const PRECEDENCIA_UNARIO: u8 = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Numero(String),
+    Variavel(String),
+    Historico(usize),
+    Negacao(Box<Expr>),
+    Binario {
+        op: OpBinario,
+        esquerda: Box<Expr>,
+        direita: Box<Expr>,
+    },
+    Chamada {
+        funcao: String,
+        argumentos: Vec<Expr>,
+    },
+    // Valor seguido de unidade, ex: 5 km ou 3 m^2
+    ComUnidade {
+        valor: Box<Expr>,
+        unidade: Box<Expr>,
+    },
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Numero(n) => write!(f, "{}", n),
+            Expr::Variavel(nome) => write!(f, "{}", nome),
+            Expr::Historico(indice) => write!(f, "${}", indice),
+            Expr::Negacao(operando) => write!(f, "(-{})", operando),
+            Expr::Binario { op, esquerda, direita } => {
+                write!(f, "({} {} {})", esquerda, op.simbolo(), direita)
+            }
+            Expr::Chamada { funcao, argumentos } => {
+                write!(f, "{}(", funcao)?;
+                for (i, argumento) in argumentos.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", argumento)?;
+                }
+                write!(f, ")")
+            }
+            Expr::ComUnidade { valor, unidade } => write!(f, "({} {})", valor, unidade),
+        }
+    }
+}
+
+impl Expr {
+    pub fn contem(&self, nome: &str) -> bool {
+        match self {
+            Expr::Variavel(variavel) => variavel == nome,
+            Expr::Negacao(operando) => operando.contem(nome),
+            Expr::Binario { esquerda, direita, .. } => esquerda.contem(nome) || direita.contem(nome),
+            Expr::Chamada { argumentos, .. } => argumentos.iter().any(|argumento| argumento.contem(nome)),
+            Expr::ComUnidade { valor, unidade } => valor.contem(nome) || unidade.contem(nome),
+            Expr::Numero(_) | Expr::Historico(_) => false,
+        }
+    }
+
+    // O analisador lê `3x` e `2x^2` como valor seguido de unidade; quando a
+    // "unidade" contém `variavel`, isso é multiplicação
+    pub fn multiplicacao_implicita(self, variavel: &str) -> Expr {
+        let reescrever = |expr: Box<Expr>| Box::new(expr.multiplicacao_implicita(variavel));
+        match self {
+            Expr::ComUnidade { valor, unidade } if unidade.contem(variavel) => Expr::Binario {
+                op: OpBinario::Multiplicacao,
+                esquerda: reescrever(valor),
+                direita: unidade,
+            },
+            Expr::ComUnidade { valor, unidade } => Expr::ComUnidade { valor: reescrever(valor), unidade },
+            Expr::Negacao(operando) => Expr::Negacao(reescrever(operando)),
+            Expr::Binario { op, esquerda, direita } => {
+                Expr::Binario { op, esquerda: reescrever(esquerda), direita: reescrever(direita) }
+            }
+            Expr::Chamada { funcao, argumentos } => Expr::Chamada {
+                funcao,
+                argumentos: argumentos.into_iter().map(|argumento| argumento.multiplicacao_implicita(variavel)).collect(),
+            },
+            outra => outra,
+        }
+    }
+
+    // Forma para o usuário, só com os parênteses necessários. Ao contrário de
+    // Display, que mostra a árvore, o texto pode ser analisado de novo.
+    pub fn legivel(&self) -> String {
+        match self {
+            Expr::Negacao(operando) => format!("-{}", operando.legivel_com_precedencia(PRECEDENCIA_UNARIO)),
+            Expr::Binario { op, esquerda, direita } => {
+                // Operando do mesmo nível só dispensa parênteses do lado em
+                // que o operador associa: a - b - c, mas a - (b - c)
+                let (minima_esquerda, minima_direita) = if op.associativo_a_direita() {
+                    (op.precedencia() + 1, op.precedencia())
+                } else {
+                    (op.precedencia(), op.precedencia() + 1)
+                };
+                let direita = match **direita {
+                    Expr::Negacao(_) => format!("({})", direita.legivel()),
+                    _ => direita.legivel_com_precedencia(minima_direita),
+                };
+                let esquerda = esquerda.legivel_com_precedencia(minima_esquerda);
+                match op {
+                    OpBinario::Potencia => format!("{}^{}", esquerda, direita),
+                    _ => format!("{} {} {}", esquerda, op.simbolo(), direita),
+                }
+            }
+            Expr::Chamada { funcao, argumentos } => {
+                let argumentos: Vec<String> = argumentos.iter().map(Expr::legivel).collect();
+                format!("{}({})", funcao, argumentos.join(", "))
+            }
+            Expr::ComUnidade { valor, unidade } => {
+                format!("{} {}", valor.legivel_com_precedencia(PRECEDENCIA_UNARIO + 1), unidade.legivel())
+            }
+            Expr::Numero(_) | Expr::Variavel(_) | Expr::Historico(_) => self.to_string(),
+        }
+    }
+
+    fn legivel_com_precedencia(&self, minima: u8) -> String {
+        let precedencia = match self {
+            Expr::Negacao(_) => PRECEDENCIA_UNARIO,
+            Expr::Binario { op, .. } => op.precedencia(),
+            _ => u8::MAX,
+        };
+        if precedencia < minima {
+            format!("({})", self.legivel())
+        } else {
+            self.legivel()
+        }
+    }
+}
+
+// Uma linha digitada pelo usuário: uma expressão, uma atribuição `x = ...`
+// ou uma conversão de unidades `5 km to mi`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Comando {
+    Expressao(Expr),
+    Atribuicao { variavel: String, expr: Expr },
+    // `unidade` é o destino como foi digitado, para exibir junto do resultado
+    Conversao { expr: Expr, destino: Expr, unidade: String },
+}
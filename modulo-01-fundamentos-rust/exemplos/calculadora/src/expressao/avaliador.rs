@@ -0,0 +1,334 @@
+use super::arvore::{Expr, OpBinario};
+use super::conversao;
+use super::erro::ErroAvaliacao;
+use crate::angulo::UnidadeAngulo;
+use crate::erro::{CalcError, Resultado};
+use crate::numero::{Complexo, Numero, Valor};
+use crate::operacoes;
+use crate::operacoes_complexas;
+use crate::registro;
+use crate::unidades::ErroUnidade;
+
+// De onde vêm os valores de variáveis e do histórico durante a avaliação
+pub trait Contexto {
+    fn variavel(&self, nome: &str) -> Option<Valor>;
+    fn historico(&self, indice: usize) -> Option<Valor>;
+
+    // Unidade dos ângulos recebidos por sin/cos/tan e devolvidos por asin/acos/atan
+    fn unidade_angulo(&self) -> UnidadeAngulo {
+        UnidadeAngulo::default()
+    }
+}
+
+// Contexto vazio: apenas números, constantes e funções embutidas
+pub struct SemContexto;
+
+impl Contexto for SemContexto {
+    fn variavel(&self, _nome: &str) -> Option<Valor> {
+        None
+    }
+
+    fn historico(&self, _indice: usize) -> Option<Valor> {
+        None
+    }
+}
+
+// Contexto com uma variável a mais, que esconde a de mesmo nome no contexto
+// original. Usado para avaliar f(x) em vários pontos sem mexer na sessão.
+pub struct ComVariavel<'a, C> {
+    pub contexto: &'a C,
+    pub nome: &'a str,
+    pub valor: f64,
+}
+
+impl<C: Contexto> Contexto for ComVariavel<'_, C> {
+    fn variavel(&self, nome: &str) -> Option<Valor> {
+        if nome == self.nome {
+            return Some(Valor::Real(self.valor));
+        }
+        self.contexto.variavel(nome)
+    }
+
+    fn historico(&self, indice: usize) -> Option<Valor> {
+        self.contexto.historico(indice)
+    }
+
+    fn unidade_angulo(&self) -> UnidadeAngulo {
+        self.contexto.unidade_angulo()
+    }
+}
+
+// Unidade imaginária; fora do modo complexo usá-la é um erro
+pub const UNIDADE_IMAGINARIA: &str = "i";
+
+// `4i` e `3 i^2` chegam do analisador como valor seguido de unidade
+fn eh_imaginario(unidade: &Expr) -> bool {
+    match unidade {
+        Expr::Variavel(nome) => nome == UNIDADE_IMAGINARIA,
+        Expr::Binario { op: OpBinario::Potencia, esquerda, .. } => eh_imaginario(esquerda),
+        _ => false,
+    }
+}
+
+pub fn buscar_constante(nome: &str) -> Option<f64> {
+    match nome {
+        "pi" => Some(std::f64::consts::PI),
+        "e" => Some(std::f64::consts::E),
+        _ => None,
+    }
+}
+
+// Nomes de variáveis na ordem em que aparecem, sem constantes, `i` e `ans`
+pub fn variaveis(expr: &Expr) -> Vec<String> {
+    fn coletar(expr: &Expr, nomes: &mut Vec<String>) {
+        match expr {
+            Expr::Variavel(nome) => {
+                let reservado = nome == UNIDADE_IMAGINARIA || nome == "ans" || buscar_constante(nome).is_some();
+                if !reservado && !nomes.contains(nome) {
+                    nomes.push(nome.clone());
+                }
+            }
+            Expr::Negacao(operando) => coletar(operando, nomes),
+            Expr::Binario { esquerda, direita, .. } => {
+                coletar(esquerda, nomes);
+                coletar(direita, nomes);
+            }
+            Expr::Chamada { argumentos, .. } => argumentos.iter().for_each(|argumento| coletar(argumento, nomes)),
+            Expr::ComUnidade { valor, unidade } => {
+                coletar(valor, nomes);
+                coletar(unidade, nomes);
+            }
+            Expr::Numero(_) | Expr::Historico(_) => {}
+        }
+    }
+
+    let mut nomes = Vec::new();
+    coletar(expr, &mut nomes);
+    nomes
+}
+
+fn aplicar_binario<N: Numero>(op: OpBinario, a: N, b: N, unidade: UnidadeAngulo) -> Resultado<N> {
+    match op {
+        OpBinario::Soma => operacoes::somar(a, b),
+        OpBinario::Subtracao => operacoes::subtrair(a, b),
+        OpBinario::Multiplicacao => operacoes::multiplicar(a, b),
+        OpBinario::Divisao => operacoes::dividir(a, b),
+        OpBinario::Resto => operacoes::modulo(a, b),
+        OpBinario::Potencia => operacoes::potencia(a, b),
+        OpBinario::Polar => operacoes_complexas::polar(a, b, unidade),
+    }
+}
+
+pub fn avaliar<N: Numero>(expr: &Expr, contexto: &impl Contexto) -> Result<N, ErroAvaliacao> {
+    match avaliar_numeros(expr, contexto) {
+        // Em 1 m + 1 s ou 2x o problema não é a falta do `to`
+        Err(ErroAvaliacao::Unidade(ErroUnidade::SemConversao)) => Err(conversao::erro_sem_conversao(expr, contexto)),
+        resultado => resultado,
+    }
+}
+
+// Avalia sem unidades: usada pela conversão, que trata as unidades por conta própria
+pub(super) fn avaliar_numeros<N: Numero>(expr: &Expr, contexto: &impl Contexto) -> Result<N, ErroAvaliacao> {
+    match expr {
+        Expr::Numero(literal) => {
+            Ok(N::de_texto(literal)?)
+        }
+        Expr::Variavel(nome) if nome == UNIDADE_IMAGINARIA => {
+            Ok(N::de_complexo(Complexo::I).ok_or(CalcError::Imaginario)?)
+        }
+        Expr::Variavel(nome) => match buscar_constante(nome) {
+            Some(constante) => {
+                N::de_f64(constante).ok_or(ErroAvaliacao::Calculo(CalcError::NaoExato { operacao: "constante" }))
+            }
+            None => {
+                let valor = contexto
+                    .variavel(nome)
+                    .ok_or_else(|| ErroAvaliacao::VariavelDesconhecida(nome.clone()))?;
+                Ok(valor.converter()?)
+            }
+        },
+        Expr::Historico(indice) => {
+            let valor = contexto
+                .historico(*indice)
+                .ok_or(ErroAvaliacao::HistoricoInexistente(*indice))?;
+            Ok(valor.converter()?)
+        }
+        Expr::Negacao(operando) => Ok(operacoes::subtrair(N::zero(), avaliar_numeros(operando, contexto)?)?),
+        Expr::Binario { op, esquerda, direita } => {
+            let a = avaliar_numeros(esquerda, contexto)?;
+            let b = avaliar_numeros(direita, contexto)?;
+            Ok(aplicar_binario(*op, a, b, contexto.unidade_angulo())?)
+        }
+        Expr::Chamada { funcao, argumentos } => {
+            let operacao = registro::buscar_por_identificador(funcao)
+                .ok_or_else(|| ErroAvaliacao::FuncaoDesconhecida(funcao.clone()))?;
+
+            let esperado = operacao.aridade();
+            if argumentos.len() != esperado {
+                return Err(ErroAvaliacao::NumeroArgumentos {
+                    funcao: funcao.clone(),
+                    esperado,
+                    recebido: argumentos.len(),
+                });
+            }
+
+            let valores = argumentos
+                .iter()
+                .map(|argumento| avaliar_numeros::<N>(argumento, contexto))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(operacao.aplicar(valores, contexto.unidade_angulo())?)
+        }
+        Expr::ComUnidade { valor, unidade } if eh_imaginario(unidade) => {
+            let valor = avaliar_numeros(valor, contexto)?;
+            Ok(operacoes::multiplicar(valor, avaliar_numeros(unidade, contexto)?)?)
+        }
+        // Unidades só fazem sentido em conversões, calculadas por `converter`
+        Expr::ComUnidade { .. } => Err(ErroUnidade::SemConversao.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{analisar, calcular, ErroExpressao};
+    use super::*;
+    use crate::numero::Decimal;
+
+    fn aproximado(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn em_modo<N: Numero>(texto: &str) -> Result<N, ErroAvaliacao> {
+        avaliar(&analisar(texto).unwrap(), &SemContexto)
+    }
+
+    #[test]
+    fn test_avaliar_aritmetica() {
+        assert_eq!(calcular("1 + 2 * 3").unwrap(), 7.0);
+        assert_eq!(calcular("(1 + 2) * 3").unwrap(), 9.0);
+        assert_eq!(calcular("2 ^ 3 ^ 2").unwrap(), 512.0);
+        assert_eq!(calcular("-2 ^ 2").unwrap(), -4.0);
+        assert_eq!(calcular("7 % 4").unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_avaliar_funcoes_embutidas() {
+        assert!(aproximado(calcular("2 * (3 + sin(30)) ^ 2 / 4").unwrap(), 6.125));
+        assert_eq!(calcular("sqrt(16) + pow(2, 3)").unwrap(), 12.0);
+        assert!(aproximado(calcular("ln(e)").unwrap(), 1.0));
+    }
+
+    #[test]
+    fn test_avaliar_erro_de_calculo() {
+        assert_eq!(
+            calcular("1 / (2 - 2)").unwrap_err(),
+            ErroExpressao::Avaliacao(ErroAvaliacao::Calculo(CalcError::DivisaoPorZero))
+        );
+        assert_eq!(
+            calcular("1e400").unwrap_err(),
+            ErroExpressao::Avaliacao(ErroAvaliacao::Calculo(CalcError::Overflow))
+        );
+        assert!(matches!(
+            calcular("sqrt(-4)").unwrap_err(),
+            ErroExpressao::Avaliacao(ErroAvaliacao::Calculo(CalcError::Dominio { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_avaliar_em_outros_modos() {
+        use num_bigint::BigInt;
+        use num_rational::BigRational;
+
+        assert_eq!(em_modo::<BigRational>("0.1 + 0.2").unwrap().to_string(), "3/10");
+        assert_eq!(em_modo::<BigInt>("fact(30)").unwrap().to_string(), "265252859812191058636308480000000");
+        assert_eq!(em_modo::<Decimal>("1 / 3 * 3").unwrap().to_string(), "0.99999999999999999999");
+        assert_eq!(
+            em_modo::<BigInt>("2.5"),
+            Err(ErroAvaliacao::Calculo(CalcError::NaoExato { operacao: "número" }))
+        );
+        assert_eq!(
+            em_modo::<BigRational>("pi"),
+            Err(ErroAvaliacao::Calculo(CalcError::NaoExato { operacao: "constante" }))
+        );
+    }
+
+    #[test]
+    fn test_avaliar_trigonometria_em_graus() {
+        assert_eq!(calcular("sin(90) + cos(180)").unwrap(), 0.0);
+        assert!(aproximado(calcular("asin(0.5) * 2").unwrap(), 60.0));
+        assert!(aproximado(calcular("atan2(1, 1)").unwrap(), 45.0));
+        assert!(aproximado(calcular("cosh(0) + tanh(0)").unwrap(), 1.0));
+        assert_eq!(
+            calcular("tan(90)").unwrap_err(),
+            ErroExpressao::Avaliacao(ErroAvaliacao::Calculo(CalcError::Assintota { operacao: "tangente" }))
+        );
+        assert!(matches!(
+            calcular("acos(2)").unwrap_err(),
+            ErroExpressao::Avaliacao(ErroAvaliacao::Calculo(CalcError::Dominio { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_avaliar_unidade_sem_conversao() {
+        assert_eq!(
+            calcular("5 km + 1 m"),
+            Err(ErroExpressao::Avaliacao(ErroAvaliacao::Unidade(ErroUnidade::SemConversao)))
+        );
+        for texto in ["5 km + 1", "1 m + 1 s"] {
+            assert!(matches!(
+                calcular(texto),
+                Err(ErroExpressao::Avaliacao(ErroAvaliacao::Unidade(ErroUnidade::Incompativel { .. })))
+            ));
+        }
+        assert_eq!(
+            calcular("2x"),
+            Err(ErroExpressao::Avaliacao(ErroAvaliacao::Unidade(ErroUnidade::Desconhecida("x".to_string()))))
+        );
+    }
+
+    #[test]
+    fn test_avaliar_funcao_desconhecida() {
+        let erro = avaliar::<f64>(&Expr::Chamada { funcao: "foo".to_string(), argumentos: vec![] }, &SemContexto)
+            .unwrap_err();
+        assert_eq!(erro, ErroAvaliacao::FuncaoDesconhecida("foo".to_string()));
+    }
+
+    #[test]
+    fn test_avaliar_numero_de_argumentos() {
+        let erro = avaliar::<f64>(
+            &Expr::Chamada {
+                funcao: "sqrt".to_string(),
+                argumentos: vec![Expr::Numero("1".to_string()), Expr::Numero("2".to_string())],
+            },
+            &SemContexto,
+        )
+        .unwrap_err();
+        assert_eq!(
+            erro,
+            ErroAvaliacao::NumeroArgumentos { funcao: "sqrt".to_string(), esperado: 1, recebido: 2 }
+        );
+    }
+
+    #[test]
+    fn test_avaliar_variavel_desconhecida() {
+        let erro = avaliar::<f64>(&Expr::Variavel("x".to_string()), &SemContexto).unwrap_err();
+        assert_eq!(erro, ErroAvaliacao::VariavelDesconhecida("x".to_string()));
+    }
+
+    #[test]
+    fn test_avaliar_em_modo_complexo() {
+        assert_eq!(em_modo::<Complexo>("sqrt(-4)"), Ok(Complexo::new(0.0, 2.0)));
+        assert_eq!(em_modo::<Complexo>("(3 + 4i) * (3 - 4i)"), Ok(Complexo::new(25.0, 0.0)));
+        assert_eq!(em_modo::<Complexo>("2i^2 + abs(3 + 4i)"), Ok(Complexo::new(3.0, 0.0)));
+        assert_eq!(em_modo::<Complexo>("2∠90 + 1"), Ok(Complexo::new(1.0, 2.0)));
+        assert_eq!(em_modo::<Complexo>("e^(i * pi)"), Ok(Complexo::new(-1.0, 0.0)));
+        assert_eq!(em_modo::<Complexo>("conj(1 - i) / i"), Ok(Complexo::new(1.0, -1.0)));
+    }
+
+    #[test]
+    fn test_avaliar_imaginario_fora_do_modo_complexo() {
+        assert_eq!(em_modo::<f64>("2∠180"), Ok(-2.0));
+        assert_eq!(em_modo::<f64>("3i"), Err(ErroAvaliacao::Calculo(CalcError::Imaginario)));
+        assert_eq!(em_modo::<f64>("2∠45"), Err(ErroAvaliacao::Calculo(CalcError::Imaginario)));
+        assert!(matches!(em_modo::<f64>("sqrt(-4)"), Err(ErroAvaliacao::Calculo(CalcError::Dominio { .. }))));
+    }
+}
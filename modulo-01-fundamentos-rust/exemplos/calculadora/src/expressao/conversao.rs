@@ -0,0 +1,245 @@
+use super::arvore::{Expr, OpBinario};
+use super::avaliador::{avaliar_numeros, buscar_constante, Contexto};
+use super::erro::ErroAvaliacao;
+use crate::erro::CalcError;
+use crate::operacoes;
+use crate::unidades::{buscar_unidade, Dimensao, ErroUnidade, Grandeza, Unidade};
+
+// Conversões são calculadas em f64, qualquer que seja o modo numérico:
+// os fatores de conversão (0.3048, 5/9...) já são aproximações.
+
+// Valor de `expr` convertido para a unidade `destino`, ex: 5 km to mi
+pub fn converter(expr: &Expr, destino: &Expr, contexto: &impl Contexto) -> Result<f64, ErroAvaliacao> {
+    let origem = match temperatura_absoluta(expr, contexto)? {
+        Some(grandeza) => grandeza,
+        None => avaliar_grandeza(expr, contexto)?,
+    };
+
+    // Destino simples (inclusive °C e °F): usa a escala da própria unidade
+    if let Expr::Variavel(nome) = destino {
+        let unidade = buscar_unidade(nome).ok_or_else(|| ErroUnidade::Desconhecida(nome.clone()))?;
+        origem.mesma_dimensao(&Grandeza { valor: 0.0, dimensao: unidade.dimensao })?;
+        return Ok(arredondar_ruido(unidade.de_si(origem.valor)));
+    }
+
+    let destino = avaliar_unidade(destino, contexto)?;
+    origem.mesma_dimensao(&destino)?;
+    Ok(arredondar_ruido(operacoes::dividir(origem.valor, destino.valor)?))
+}
+
+// Erro de uma expressão com unidades avaliada sem `to`: quando as unidades
+// nem combinam entre si, esse erro vem antes do pedido de conversão
+pub(super) fn erro_sem_conversao(expr: &Expr, contexto: &impl Contexto) -> ErroAvaliacao {
+    let combinadas = match temperatura_absoluta(expr, contexto) {
+        Ok(None) => avaliar_grandeza(expr, contexto).map(|_| ()),
+        outro => outro.map(|_| ()),
+    };
+    match combinadas {
+        Err(erro) => erro,
+        Ok(_) => ErroUnidade::SemConversao.into(),
+    }
+}
+
+// Os fatores passam pelo SI e acumulam erro binário (1 ft to in daria
+// 12.000000000000002); 15 algarismos significativos bastam para conversões
+fn arredondar_ruido(valor: f64) -> f64 {
+    format!("{:.14e}", valor).parse().unwrap_or(valor)
+}
+
+// `20 °C` ou `-40 °F` sozinhos: a única forma em que escalas com zero
+// deslocado são aceitas, já convertidas para kelvin
+fn temperatura_absoluta(expr: &Expr, contexto: &impl Contexto) -> Result<Option<Grandeza>, ErroAvaliacao> {
+    let (valor, unidade, negado) = match expr {
+        Expr::ComUnidade { valor, unidade } => (valor, unidade, false),
+        Expr::Negacao(operando) => match operando.as_ref() {
+            Expr::ComUnidade { valor, unidade } => (valor, unidade, true),
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+    let unidade = match unidade.as_ref() {
+        Expr::Variavel(nome) => buscar_unidade(nome).filter(|unidade| unidade.tem_deslocamento()),
+        _ => None,
+    };
+    let Some(unidade) = unidade else {
+        return Ok(None);
+    };
+
+    let numero = avaliar_grandeza(valor, contexto)?.exigir_adimensional()?;
+    let numero = if negado { -numero } else { numero };
+    Ok(Some(Grandeza { valor: unidade.para_si(numero), dimensao: unidade.dimensao }))
+}
+
+fn unitaria(unidade: &Unidade) -> Result<Grandeza, ErroAvaliacao> {
+    Ok(Grandeza::unitaria(unidade)?)
+}
+
+// Avalia uma expressão em que números podem carregar unidades. Nomes que não
+// são variáveis nem constantes são procurados na tabela de unidades: em
+// `60 km/h`, o `h` é a hora.
+fn avaliar_grandeza(expr: &Expr, contexto: &impl Contexto) -> Result<Grandeza, ErroAvaliacao> {
+    match expr {
+        Expr::Variavel(nome) => {
+            if buscar_constante(nome).is_none() && contexto.variavel(nome).is_none() {
+                if let Some(unidade) = buscar_unidade(nome) {
+                    return unitaria(unidade);
+                }
+            }
+            Ok(Grandeza::numero(avaliar_numeros::<f64>(expr, contexto)?))
+        }
+        Expr::Numero(_) | Expr::Historico(_) => Ok(Grandeza::numero(avaliar_numeros::<f64>(expr, contexto)?)),
+        Expr::Negacao(operando) => {
+            let grandeza = avaliar_grandeza(operando, contexto)?;
+            Ok(Grandeza { valor: -grandeza.valor, ..grandeza })
+        }
+        Expr::Binario { op, esquerda, direita } => {
+            let a = avaliar_grandeza(esquerda, contexto)?;
+            let b = avaliar_grandeza(direita, contexto)?;
+            combinar(*op, a, b)
+        }
+        Expr::Chamada { argumentos, .. } => {
+            // Funções só recebem números puros: sin(3 m) não faz sentido
+            for argumento in argumentos {
+                avaliar_grandeza(argumento, contexto)?.exigir_adimensional()?;
+            }
+            Ok(Grandeza::numero(avaliar_numeros::<f64>(expr, contexto)?))
+        }
+        Expr::ComUnidade { valor, unidade } => {
+            let valor = avaliar_grandeza(valor, contexto)?;
+            let unidade = avaliar_unidade(unidade, contexto)?;
+            combinar(OpBinario::Multiplicacao, valor, unidade)
+        }
+    }
+}
+
+// Depois de um número ou de `to`, nomes são sempre unidades, mesmo que
+// exista uma variável com o mesmo nome
+fn avaliar_unidade(expr: &Expr, contexto: &impl Contexto) -> Result<Grandeza, ErroAvaliacao> {
+    match expr {
+        Expr::Variavel(nome) => {
+            let unidade = buscar_unidade(nome).ok_or_else(|| ErroUnidade::Desconhecida(nome.clone()))?;
+            unitaria(unidade)
+        }
+        Expr::Numero(_) => Ok(Grandeza::numero(avaliar_numeros::<f64>(expr, contexto)?)),
+        Expr::Binario { op: op @ (OpBinario::Multiplicacao | OpBinario::Divisao), esquerda, direita } => {
+            let a = avaliar_unidade(esquerda, contexto)?;
+            let b = avaliar_unidade(direita, contexto)?;
+            combinar(*op, a, b)
+        }
+        Expr::Binario { op: OpBinario::Potencia, esquerda, direita } => {
+            let base = avaliar_unidade(esquerda, contexto)?;
+            let expoente = avaliar_grandeza(direita, contexto)?;
+            combinar(OpBinario::Potencia, base, expoente)
+        }
+        outra => Err(ErroUnidade::Desconhecida(outra.to_string()).into()),
+    }
+}
+
+fn combinar(op: OpBinario, a: Grandeza, b: Grandeza) -> Result<Grandeza, ErroAvaliacao> {
+    let (valor, dimensao) = match op {
+        OpBinario::Soma => (operacoes::somar(a.valor, b.valor)?, a.mesma_dimensao(&b)?),
+        OpBinario::Subtracao => (operacoes::subtrair(a.valor, b.valor)?, a.mesma_dimensao(&b)?),
+        OpBinario::Resto => (operacoes::modulo(a.valor, b.valor)?, a.mesma_dimensao(&b)?),
+        OpBinario::Multiplicacao => {
+            let dimensao = a.dimensao.multiplicar(&b.dimensao).ok_or(ErroUnidade::ExpoenteInvalido)?;
+            (operacoes::multiplicar(a.valor, b.valor)?, dimensao)
+        }
+        OpBinario::Divisao => {
+            let dimensao = a.dimensao.dividir(&b.dimensao).ok_or(ErroUnidade::ExpoenteInvalido)?;
+            (operacoes::dividir(a.valor, b.valor)?, dimensao)
+        }
+        OpBinario::Potencia => {
+            let expoente = b.exigir_adimensional()?;
+            let dimensao = if a.dimensao.eh_adimensional() {
+                Dimensao::ADIMENSIONAL
+            } else if expoente.fract() == 0.0 && expoente.abs() <= i8::MAX as f64 {
+                a.dimensao.potencia(expoente as i8).ok_or(ErroUnidade::ExpoenteInvalido)?
+            } else {
+                return Err(ErroUnidade::ExpoenteInvalido.into());
+            };
+            (operacoes::potencia(a.valor, expoente)?, dimensao)
+        }
+        OpBinario::Polar => return Err(CalcError::SoReal { operacao: "conversão de unidades" }.into()),
+    };
+    Ok(Grandeza { valor, dimensao })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{analisar_comando, Comando, SemContexto};
+    use super::*;
+    use crate::numero::Valor;
+
+    fn converter_texto(texto: &str) -> Result<f64, ErroAvaliacao> {
+        match analisar_comando(texto).unwrap() {
+            Comando::Conversao { expr, destino, .. } => converter(&expr, &destino, &SemContexto),
+            outro => panic!("esperava conversão, obteve {:?}", outro),
+        }
+    }
+
+    fn aproximado(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-9 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn test_conversoes_simples() {
+        assert!(aproximado(converter_texto("5 km to mi").unwrap(), 3.106855961186669));
+        assert!(aproximado(converter_texto("100 °F to °C").unwrap(), 37.77777777777778));
+        assert!(aproximado(converter_texto("-40 °C em °F").unwrap(), -40.0));
+        assert!(aproximado(converter_texto("1 GiB to MB").unwrap(), 1073.741824));
+    }
+
+    #[test]
+    fn test_conversoes_compostas() {
+        assert!(aproximado(converter_texto("2 km + 300 m to m").unwrap(), 2300.0));
+        assert!(aproximado(converter_texto("100 km/h to m/s").unwrap(), 27.77777777777778));
+        assert!(aproximado(converter_texto("3 m * 4 m to ha").unwrap(), 0.0012));
+        assert!(aproximado(converter_texto("1 L to cm^3").unwrap(), 1000.0));
+        assert!(aproximado(converter_texto("1 kWh to kJ").unwrap(), 3600.0));
+        assert!(aproximado(converter_texto("(1 + 1) h to min").unwrap(), 120.0));
+    }
+
+    #[test]
+    fn test_dimensoes_incompativeis() {
+        assert_eq!(
+            converter_texto("5 km + 3 kg to m"),
+            Err(ErroAvaliacao::Unidade(ErroUnidade::Incompativel {
+                esquerda: Dimensao::COMPRIMENTO,
+                direita: Dimensao::MASSA
+            }))
+        );
+        assert!(matches!(converter_texto("5 km to s"), Err(ErroAvaliacao::Unidade(ErroUnidade::Incompativel { .. }))));
+        assert!(matches!(converter_texto("2 m ^ 0.5 to m"), Err(ErroAvaliacao::Unidade(ErroUnidade::ExpoenteInvalido))));
+        // Expoentes de dimensão fora de i8: erro, nem pânico nem m^127
+        for texto in ["1 m / (1 / m^100 / m^100) to m", "m^100 * m^100 to m"] {
+            assert_eq!(converter_texto(texto), Err(ErroAvaliacao::Unidade(ErroUnidade::ExpoenteInvalido)), "{}", texto);
+        }
+        assert!(matches!(converter_texto("5 km to xyz"), Err(ErroAvaliacao::Unidade(ErroUnidade::Desconhecida(_)))));
+    }
+
+    #[test]
+    fn test_temperatura_em_contas() {
+        assert_eq!(
+            converter_texto("10 °C + 10 °C to K"),
+            Err(ErroAvaliacao::Unidade(ErroUnidade::TemperaturaEmOperacao("°C")))
+        );
+        assert!(aproximado(converter_texto("10 K + 10 K to K").unwrap(), 20.0));
+    }
+
+    #[test]
+    fn test_variaveis_nao_escondem_unidades_apos_numeros() {
+        struct ComM;
+        impl Contexto for ComM {
+            fn variavel(&self, nome: &str) -> Option<Valor> {
+                (nome == "m").then_some(Valor::Real(3.0))
+            }
+            fn historico(&self, _indice: usize) -> Option<Valor> {
+                None
+            }
+        }
+        let Comando::Conversao { expr, destino, .. } = analisar_comando("m km to m").unwrap() else {
+            panic!("esperava conversão");
+        };
+        assert!(aproximado(converter(&expr, &destino, &ComM).unwrap(), 3000.0));
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt;
+
+use crate::erro::CalcError;
+use crate::unidades::ErroUnidade;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TipoErroSintaxe {
+    CaractereInvalido(char),
+    NumeroInvalido(String),
+    TokenInesperado(String),
+    FimInesperado,
+    ParenteseNaoFechado,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErroSintaxe {
+    // Coluna (começando em 1) onde o problema foi encontrado
+    pub coluna: usize,
+    pub tipo: TipoErroSintaxe,
+}
+
+impl fmt::Display for ErroSintaxe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Erro de sintaxe na coluna {}: ", self.coluna)?;
+        match &self.tipo {
+            TipoErroSintaxe::CaractereInvalido(c) => write!(f, "caractere inválido '{}'", c),
+            TipoErroSintaxe::NumeroInvalido(literal) => write!(f, "número inválido '{}'", literal),
+            TipoErroSintaxe::TokenInesperado(token) => write!(f, "'{}' inesperado", token),
+            TipoErroSintaxe::FimInesperado => write!(f, "expressão terminou inesperadamente"),
+            TipoErroSintaxe::ParenteseNaoFechado => write!(f, "parêntese aberto aqui não foi fechado"),
+        }
+    }
+}
+
+impl std::error::Error for ErroSintaxe {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErroAvaliacao {
+    FuncaoDesconhecida(String),
+    VariavelDesconhecida(String),
+    HistoricoInexistente(usize),
+    // Tentativa de atribuir a uma constante, função ou a `ans`
+    NomeReservado(String),
+    NumeroArgumentos {
+        funcao: String,
+        esperado: usize,
+        recebido: usize,
+    },
+    Calculo(CalcError),
+    Unidade(ErroUnidade),
+}
+
+impl fmt::Display for ErroAvaliacao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroAvaliacao::FuncaoDesconhecida(nome) => write!(f, "Função desconhecida: {}", nome),
+            ErroAvaliacao::VariavelDesconhecida(nome) => write!(f, "Variável desconhecida: {}", nome),
+            ErroAvaliacao::HistoricoInexistente(indice) => {
+                write!(f, "Não existe a entrada ${} no histórico", indice)
+            }
+            ErroAvaliacao::NomeReservado(nome) => write!(f, "O nome '{}' é reservado", nome),
+            ErroAvaliacao::NumeroArgumentos { funcao, esperado, recebido } => write!(
+                f,
+                "{} espera {} argumento(s), mas recebeu {}",
+                funcao, esperado, recebido
+            ),
+            ErroAvaliacao::Calculo(erro) => write!(f, "{}", erro),
+            ErroAvaliacao::Unidade(erro) => write!(f, "{}", erro),
+        }
+    }
+}
+
+impl std::error::Error for ErroAvaliacao {}
+
+impl From<CalcError> for ErroAvaliacao {
+    fn from(erro: CalcError) -> Self {
+        ErroAvaliacao::Calculo(erro)
+    }
+}
+
+impl From<ErroUnidade> for ErroAvaliacao {
+    fn from(erro: ErroUnidade) -> Self {
+        ErroAvaliacao::Unidade(erro)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErroExpressao {
+    Sintaxe(ErroSintaxe),
+    Avaliacao(ErroAvaliacao),
+}
+
+impl fmt::Display for ErroExpressao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroExpressao::Sintaxe(erro) => write!(f, "{}", erro),
+            ErroExpressao::Avaliacao(erro) => write!(f, "{}", erro),
+        }
+    }
+}
+
+impl std::error::Error for ErroExpressao {}
+
+impl From<ErroSintaxe> for ErroExpressao {
+    fn from(erro: ErroSintaxe) -> Self {
+        ErroExpressao::Sintaxe(erro)
+    }
+}
+
+impl From<ErroAvaliacao> for ErroExpressao {
+    fn from(erro: ErroAvaliacao) -> Self {
+        ErroExpressao::Avaliacao(erro)
+    }
+}
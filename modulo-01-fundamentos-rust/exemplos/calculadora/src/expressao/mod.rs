@@ -0,0 +1,21 @@
+mod analisador;
+mod arvore;
+mod avaliador;
+mod conversao;
+mod erro;
+mod tokenizador;
+
+pub use analisador::{analisar, analisar_comando, PALAVRAS_CONVERSAO};
+pub use arvore::{Comando, Expr, OpBinario};
+pub use avaliador::{avaliar, buscar_constante, variaveis, ComVariavel, Contexto, SemContexto, UNIDADE_IMAGINARIA};
+pub use conversao::converter;
+pub use erro::{ErroAvaliacao, ErroExpressao};
+
+// Avalia uma expressão ou conversão isolada, sem variáveis nem histórico
+pub fn calcular(texto: &str) -> Result<f64, ErroExpressao> {
+    if let Comando::Conversao { expr, destino, .. } = analisar_comando(texto)? {
+        return Ok(converter(&expr, &destino, &SemContexto)?);
+    }
+    let expr = analisar(texto)?;
+    Ok(avaliar(&expr, &SemContexto)?)
+}
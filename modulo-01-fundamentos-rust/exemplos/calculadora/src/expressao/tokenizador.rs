@@ -0,0 +1,248 @@
+use super::erro::{ErroSintaxe, TipoErroSintaxe};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TipoToken {
+    // O literal é guardado como texto para que cada modo numérico o
+    // interprete sem passar por f64 (0.1 continua exato no modo racional)
+    Numero(String),
+    Identificador(String),
+    // Referência a uma entrada do histórico, ex: $3
+    Historico(usize),
+    Mais,
+    Menos,
+    Vezes,
+    Dividir,
+    Resto,
+    Potencia,
+    // Forma polar de complexos: 2∠45
+    Polar,
+    AbreParentese,
+    FechaParentese,
+    Virgula,
+    Igual,
+    Fim,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub tipo: TipoToken,
+    // Coluna (começando em 1) onde o token começa no texto original
+    pub coluna: usize,
+}
+
+impl TipoToken {
+    pub fn descricao(&self) -> String {
+        match self {
+            TipoToken::Numero(literal) => literal.clone(),
+            TipoToken::Identificador(nome) => nome.clone(),
+            TipoToken::Historico(indice) => format!("${}", indice),
+            TipoToken::Mais => "+".to_string(),
+            TipoToken::Menos => "-".to_string(),
+            TipoToken::Vezes => "*".to_string(),
+            TipoToken::Dividir => "/".to_string(),
+            TipoToken::Resto => "%".to_string(),
+            TipoToken::Potencia => "^".to_string(),
+            TipoToken::Polar => "∠".to_string(),
+            TipoToken::AbreParentese => "(".to_string(),
+            TipoToken::FechaParentese => ")".to_string(),
+            TipoToken::Virgula => ",".to_string(),
+            TipoToken::Igual => "=".to_string(),
+            TipoToken::Fim => "fim da expressão".to_string(),
+        }
+    }
+}
+
+pub fn tokenizar(texto: &str) -> Result<Vec<Token>, ErroSintaxe> {
+    let caracteres: Vec<char> = texto.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < caracteres.len() {
+        let c = caracteres[i];
+        let coluna = i + 1;
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c.is_ascii_digit() || c == '.' {
+            let inicio = i;
+            while i < caracteres.len() && (caracteres[i].is_ascii_digit() || caracteres[i] == '.') {
+                i += 1;
+            }
+            // Notação científica: 1e3, 2.5E-4
+            if i < caracteres.len() && (caracteres[i] == 'e' || caracteres[i] == 'E') {
+                let mut j = i + 1;
+                if j < caracteres.len() && (caracteres[j] == '+' || caracteres[j] == '-') {
+                    j += 1;
+                }
+                if j < caracteres.len() && caracteres[j].is_ascii_digit() {
+                    while j < caracteres.len() && caracteres[j].is_ascii_digit() {
+                        j += 1;
+                    }
+                    i = j;
+                }
+            }
+
+            let literal: String = caracteres[inicio..i].iter().collect();
+            if literal.parse::<f64>().is_err() {
+                return Err(ErroSintaxe {
+                    coluna,
+                    tipo: TipoErroSintaxe::NumeroInvalido(literal),
+                });
+            }
+            tokens.push(Token { tipo: TipoToken::Numero(literal), coluna });
+            continue;
+        }
+
+        // '°' só aparece no início de unidades de temperatura: °C, °F
+        if c.is_alphabetic() || c == '_' || c == '°' {
+            let inicio = i;
+            i += 1;
+            while i < caracteres.len() && (caracteres[i].is_alphanumeric() || caracteres[i] == '_') {
+                i += 1;
+            }
+            let nome: String = caracteres[inicio..i].iter().collect();
+            tokens.push(Token { tipo: TipoToken::Identificador(nome), coluna });
+            continue;
+        }
+
+        if c == '$' && i + 1 < caracteres.len() && caracteres[i + 1].is_ascii_digit() {
+            let inicio = i + 1;
+            i = inicio;
+            while i < caracteres.len() && caracteres[i].is_ascii_digit() {
+                i += 1;
+            }
+            let literal: String = caracteres[inicio..i].iter().collect();
+            let indice = literal.parse().map_err(|_| ErroSintaxe {
+                coluna,
+                tipo: TipoErroSintaxe::NumeroInvalido(literal.clone()),
+            })?;
+            tokens.push(Token { tipo: TipoToken::Historico(indice), coluna });
+            continue;
+        }
+
+        let tipo = match c {
+            '+' => TipoToken::Mais,
+            '-' | '−' => TipoToken::Menos,
+            '*' | '×' => TipoToken::Vezes,
+            '/' | '÷' => TipoToken::Dividir,
+            '%' => TipoToken::Resto,
+            '^' => TipoToken::Potencia,
+            '∠' => TipoToken::Polar,
+            '(' => TipoToken::AbreParentese,
+            ')' => TipoToken::FechaParentese,
+            ',' => TipoToken::Virgula,
+            '=' => TipoToken::Igual,
+            _ => {
+                return Err(ErroSintaxe {
+                    coluna,
+                    tipo: TipoErroSintaxe::CaractereInvalido(c),
+                })
+            }
+        };
+        tokens.push(Token { tipo, coluna });
+        i += 1;
+    }
+
+    tokens.push(Token { tipo: TipoToken::Fim, coluna: caracteres.len() + 1 });
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tipos(texto: &str) -> Vec<TipoToken> {
+        tokenizar(texto).unwrap().into_iter().map(|t| t.tipo).collect()
+    }
+
+    #[test]
+    fn test_tokenizar_expressao_simples() {
+        assert_eq!(
+            tipos("2 + 3.5"),
+            vec![
+                TipoToken::Numero("2".to_string()),
+                TipoToken::Mais,
+                TipoToken::Numero("3.5".to_string()),
+                TipoToken::Fim,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenizar_funcao_e_notacao_cientifica() {
+        assert_eq!(
+            tipos("sin(1e2)"),
+            vec![
+                TipoToken::Identificador("sin".to_string()),
+                TipoToken::AbreParentese,
+                TipoToken::Numero("1e2".to_string()),
+                TipoToken::FechaParentese,
+                TipoToken::Fim,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenizar_registra_colunas() {
+        let tokens = tokenizar("12 *  x").unwrap();
+        let colunas: Vec<usize> = tokens.iter().map(|t| t.coluna).collect();
+        assert_eq!(colunas, vec![1, 4, 7, 8]);
+    }
+
+    #[test]
+    fn test_tokenizar_caractere_invalido() {
+        let erro = tokenizar("2 + $").unwrap_err();
+        assert_eq!(erro.coluna, 5);
+        assert_eq!(erro.tipo, TipoErroSintaxe::CaractereInvalido('$'));
+    }
+
+    #[test]
+    fn test_tokenizar_atribuicao_e_historico() {
+        assert_eq!(
+            tipos("x = $12"),
+            vec![
+                TipoToken::Identificador("x".to_string()),
+                TipoToken::Igual,
+                TipoToken::Historico(12),
+                TipoToken::Fim,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenizar_unidades_de_temperatura() {
+        assert_eq!(
+            tipos("100 °F to °C"),
+            vec![
+                TipoToken::Numero("100".to_string()),
+                TipoToken::Identificador("°F".to_string()),
+                TipoToken::Identificador("to".to_string()),
+                TipoToken::Identificador("°C".to_string()),
+                TipoToken::Fim,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenizar_forma_polar() {
+        assert_eq!(
+            tipos("2∠45"),
+            vec![
+                TipoToken::Numero("2".to_string()),
+                TipoToken::Polar,
+                TipoToken::Numero("45".to_string()),
+                TipoToken::Fim,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenizar_numero_invalido() {
+        let erro = tokenizar("1.2.3").unwrap_err();
+        assert_eq!(erro.coluna, 1);
+        assert_eq!(erro.tipo, TipoErroSintaxe::NumeroInvalido("1.2.3".to_string()));
+    }
+}
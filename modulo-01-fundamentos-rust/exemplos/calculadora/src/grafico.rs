@@ -0,0 +1,422 @@
+use std::fmt;
+
+// Tamanho da área de desenho em caracteres
+pub const LARGURA_PADRAO: usize = 64;
+pub const ALTURA_PADRAO: usize = 16;
+
+// Saltos entre amostras vizinhas maiores que essa fração da faixa típica da
+// função são investigados como possíveis descontinuidades
+const LIMIAR_SALTO: f64 = 0.1;
+const BISSECOES_SALTO: usize = 40;
+
+// Bits de cada ponto de um caractere braille (U+2800), por [linha][coluna]
+const BRAILLE: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+// Um marcador por função no modo ASCII
+const MARCADORES: [char; 6] = ['*', '+', 'o', 'x', '#', '@'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Estilo {
+    // 2×4 pontos por caractere: mais resolução, mas as funções têm a mesma cor
+    #[default]
+    Braille,
+    // Um ponto por caractere, com um marcador diferente para cada função
+    Ascii,
+}
+
+impl Estilo {
+    // Pontos por caractere (colunas, linhas)
+    fn resolucao(&self) -> (usize, usize) {
+        match self {
+            Estilo::Braille => (2, 4),
+            Estilo::Ascii => (1, 1),
+        }
+    }
+
+    // Eixo x, eixo y, cruzamento, borda com rótulo, canto e marca de descontinuidade
+    fn molduras(&self) -> [char; 6] {
+        match self {
+            Estilo::Braille => ['─', '│', '┼', '┤', '└', '┊'],
+            Estilo::Ascii => ['-', '|', '+', '+', '+', ':'],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Serie {
+    pub nome: String,
+    // Uma amostra por coluna de pontos; None fora do domínio
+    pub valores: Vec<Option<f64>>,
+    pub descontinuidades: Vec<f64>,
+}
+
+// Gráfico de uma ou mais funções de x em [inicio, fim], todas amostradas
+// nos mesmos pontos (os mesmos exportados em CSV)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grafico {
+    inicio: f64,
+    fim: f64,
+    largura: usize,
+    altura: usize,
+    estilo: Estilo,
+    series: Vec<Serie>,
+}
+
+impl Grafico {
+    pub fn novo(inicio: f64, fim: f64, largura: usize, altura: usize, estilo: Estilo) -> Result<Grafico, ErroGrafico> {
+        if !inicio.is_finite() || !fim.is_finite() || inicio >= fim {
+            return Err(ErroGrafico::IntervaloInvalido { inicio, fim });
+        }
+        Ok(Grafico { inicio, fim, largura: largura.max(2), altura: altura.max(2), estilo, series: Vec::new() })
+    }
+
+    pub fn series(&self) -> &[Serie] {
+        &self.series
+    }
+
+    fn amostras(&self) -> usize {
+        self.largura * self.estilo.resolucao().0
+    }
+
+    fn x(&self, indice: usize) -> f64 {
+        self.inicio + (self.fim - self.inicio) * indice as f64 / (self.amostras() - 1) as f64
+    }
+
+    // Amostra `f` e procura descontinuidades. Pontos onde `f` falha (domínio)
+    // ficam em branco; se falhar em todos, o erro é o da última tentativa.
+    pub fn adicionar<F, E>(&mut self, nome: &str, f: F) -> Result<(), ErroGrafico>
+    where
+        F: Fn(f64) -> Result<f64, E>,
+        E: fmt::Display,
+    {
+        let mut ultimo_erro = String::new();
+        let valores: Vec<Option<f64>> = (0..self.amostras())
+            .map(|i| match f(self.x(i)) {
+                Ok(y) if y.is_finite() => Some(y),
+                Ok(_) => None,
+                Err(erro) => {
+                    ultimo_erro = erro.to_string();
+                    None
+                }
+            })
+            .collect();
+        if valores.iter().all(Option::is_none) {
+            return Err(ErroGrafico::SemPontos { funcao: nome.to_string(), erro: ultimo_erro });
+        }
+
+        let (p10, p90) = (quantil(&valores, 0.1), quantil(&valores, 0.9));
+        let limiar = LIMIAR_SALTO * if p90 > p10 { p90 - p10 } else { 1.0 };
+        let mut descontinuidades = Vec::new();
+        for i in 0..valores.len() - 1 {
+            match (valores[i], valores[i + 1]) {
+                (Some(y1), Some(y2)) if (y2 - y1).abs() > limiar => {
+                    descontinuidades.extend(localizar_salto(&f, (self.x(i), y1), (self.x(i + 1), y2)));
+                }
+                // Amostra isolada fora do domínio: o polo caiu bem em cima dela (1/x em 0)
+                (None, _) if i > 0 && valores[i - 1].is_some() && valores[i + 1].is_some() => {
+                    descontinuidades.push(self.x(i));
+                }
+                _ => {}
+            }
+        }
+
+        self.series.push(Serie { nome: nome.to_string(), valores, descontinuidades });
+        Ok(())
+    }
+
+    // Faixa de y com todas as amostras, exceto as vizinhas de descontinuidades,
+    // que perto de um polo esmagariam o resto do gráfico
+    fn faixa_y(&self) -> (f64, f64) {
+        let passo = (self.fim - self.inicio) / (self.amostras() - 1) as f64;
+        let mut faixa: Option<(f64, f64)> = None;
+        for serie in &self.series {
+            for (i, valor) in serie.valores.iter().enumerate() {
+                let perto_de_salto = serie.descontinuidades.iter().any(|d| (self.x(i) - d).abs() < 1.5 * passo);
+                if let (Some(y), false) = (valor, perto_de_salto) {
+                    faixa = Some(faixa.map_or((*y, *y), |(min, max)| (min.min(*y), max.max(*y))));
+                }
+            }
+        }
+        match faixa {
+            Some((min, max)) if min < max => (min, max),
+            Some((y, _)) => (y - 1.0, y + 1.0),
+            None => (-1.0, 1.0),
+        }
+    }
+
+    pub fn desenhar(&self) -> String {
+        let (px, py) = self.estilo.resolucao();
+        let (colunas, linhas) = (self.largura * px, self.altura * py);
+        let (y_min, y_max) = self.faixa_y();
+        let linha_de = |y: f64| ((y_max - y) / (y_max - y_min) * (linhas - 1) as f64).round() as i64;
+        let coluna_de = |x: f64| ((x - self.inicio) / (self.fim - self.inicio) * (colunas - 1) as f64).round() as usize;
+
+        // pontos[linha][coluna] = índice da série que passou por ali
+        let mut pontos = vec![vec![None; colunas]; linhas];
+        let mut marcar = |coluna: usize, de: i64, ate: i64, s: usize| {
+            let (de, ate) = (de.min(ate), de.max(ate));
+            if ate < 0 || de >= linhas as i64 {
+                return;
+            }
+            for linha in &mut pontos[de.max(0) as usize..=(ate as usize).min(linhas - 1)] {
+                linha[coluna] = Some(s);
+            }
+        };
+        for (s, serie) in self.series.iter().enumerate() {
+            for i in 0..colunas {
+                let Some(y) = serie.valores[i] else { continue };
+                let atual = linha_de(y);
+                marcar(i, atual, atual, s);
+                // Liga à amostra anterior, menos através de um salto: metade do
+                // traço vertical em cada coluna, para a curva não engrossar
+                let salto = |d: &f64| *d >= self.x(i.saturating_sub(1)) && *d <= self.x(i);
+                if let Some(anterior) = i.checked_sub(1).and_then(|j| serie.valores[j]) {
+                    let anterior = linha_de(anterior);
+                    if (atual - anterior).abs() > 1 && !serie.descontinuidades.iter().any(salto) {
+                        let meio = anterior + (atual - anterior) / 2;
+                        let passo = (atual - anterior).signum();
+                        marcar(i - 1, anterior, meio, s);
+                        marcar(i, meio + passo, atual, s);
+                    }
+                }
+            }
+        }
+
+        let [eixo_x, eixo_y, cruzamento, borda, canto, salto] = self.estilo.molduras();
+        let linha_do_zero = (y_min..=y_max).contains(&0.0).then(|| linha_de(0.0) as usize / py);
+        let coluna_do_zero = (self.inicio..=self.fim).contains(&0.0).then(|| coluna_de(0.0) / px);
+        let colunas_de_salto: Vec<usize> = self
+            .series
+            .iter()
+            .flat_map(|serie| serie.descontinuidades.iter().map(|&d| coluna_de(d) / px))
+            .collect();
+
+        let (rotulo_max, rotulo_min) = (rotulo(y_max), rotulo(y_min));
+        let margem = rotulo_max.chars().count().max(rotulo_min.chars().count());
+        let mut texto = String::new();
+        for linha in 0..self.altura {
+            let (rotulo, lateral) = match linha {
+                0 => (rotulo_max.as_str(), borda),
+                _ if linha == self.altura - 1 => (rotulo_min.as_str(), borda),
+                _ => ("", eixo_y),
+            };
+            texto.push_str(&format!("{:>margem$} {}", rotulo, lateral, margem = margem));
+            for coluna in 0..self.largura {
+                let mut bits = 0;
+                let mut dono = None;
+                for dy in 0..py {
+                    for dx in 0..px {
+                        if let Some(s) = pontos[linha * py + dy][coluna * px + dx] {
+                            bits |= BRAILLE[dy][dx];
+                            dono = Some(s);
+                        }
+                    }
+                }
+                let c = match (dono, self.estilo) {
+                    (Some(_), Estilo::Braille) => char::from_u32(0x2800 + bits).unwrap_or(' '),
+                    (Some(s), Estilo::Ascii) => MARCADORES[s % MARCADORES.len()],
+                    _ if colunas_de_salto.contains(&coluna) => salto,
+                    _ if Some(linha) == linha_do_zero && Some(coluna) == coluna_do_zero => cruzamento,
+                    _ if Some(linha) == linha_do_zero => eixo_x,
+                    _ if Some(coluna) == coluna_do_zero => eixo_y,
+                    _ => ' ',
+                };
+                texto.push(c);
+            }
+            texto.push('\n');
+        }
+
+        texto.push_str(&format!("{:margem$} {}{}\n", "", canto, eixo_x.to_string().repeat(self.largura), margem = margem));
+        let (inicio, fim) = (rotulo(self.inicio), rotulo(self.fim));
+        let espaco = self.largura.saturating_sub(inicio.chars().count() + fim.chars().count()).max(1);
+        texto.push_str(&format!("{:margem$}  {}{}{}\n", "", inicio, " ".repeat(espaco), fim, margem = margem));
+
+        for (s, serie) in self.series.iter().enumerate() {
+            let marcador = match self.estilo {
+                Estilo::Braille => '⠿',
+                Estilo::Ascii => MARCADORES[s % MARCADORES.len()],
+            };
+            texto.push_str(&format!("{} {}", marcador, serie.nome));
+            if !serie.descontinuidades.is_empty() {
+                let pontos: Vec<String> = serie.descontinuidades.iter().map(|&d| rotulo(d)).collect();
+                texto.push_str(&format!(" (descontínua em x ≈ {})", pontos.join(", ")));
+            }
+            texto.push('\n');
+        }
+        texto
+    }
+
+    // Uma linha por amostra: x e o valor de cada função (vazio fora do domínio)
+    pub fn csv(&self) -> String {
+        let mut texto = String::from("x");
+        for serie in &self.series {
+            texto.push(',');
+            texto.push_str(&campo_csv(&serie.nome));
+        }
+        texto.push('\n');
+        for i in 0..self.amostras() {
+            texto.push_str(&self.x(i).to_string());
+            for serie in &self.series {
+                texto.push(',');
+                if let Some(y) = serie.valores[i] {
+                    texto.push_str(&y.to_string());
+                }
+            }
+            texto.push('\n');
+        }
+        texto
+    }
+}
+
+// Bissecta o intervalo seguindo a metade com o maior salto. Em uma função
+// contínua o salto encolhe junto com o intervalo; em um polo ou degrau, não.
+fn localizar_salto<F, E>(f: &F, (x1, y1): (f64, f64), (x2, y2): (f64, f64)) -> Option<f64>
+where
+    F: Fn(f64) -> Result<f64, E>,
+{
+    let salto_inicial = (y2 - y1).abs();
+    let (mut a, mut ya, mut b, mut yb) = (x1, y1, x2, y2);
+    for _ in 0..BISSECOES_SALTO {
+        let meio = a + (b - a) / 2.0;
+        let y = match f(meio) {
+            Ok(y) if y.is_finite() => y,
+            // Buraco no domínio entre dois pontos definidos
+            _ => return Some(meio),
+        };
+        if (y - ya).abs() >= (yb - y).abs() {
+            (b, yb) = (meio, y);
+        } else {
+            (a, ya) = (meio, y);
+        }
+    }
+    ((yb - ya).abs() >= 0.5 * salto_inicial).then_some(a + (b - a) / 2.0)
+}
+
+// Quantil das amostras definidas (sem interpolação: só decide o limiar de salto)
+fn quantil(valores: &[Option<f64>], p: f64) -> f64 {
+    let mut definidos: Vec<f64> = valores.iter().flatten().copied().collect();
+    definidos.sort_by(f64::total_cmp);
+    definidos[((definidos.len() - 1) as f64 * p).round() as usize]
+}
+
+// Números curtos para os eixos: 3 casas decimais ou notação científica
+fn rotulo(x: f64) -> String {
+    if x != 0.0 && (x.abs() >= 1e5 || x.abs() < 1e-3) {
+        return format!("{:.2e}", x);
+    }
+    let texto = format!("{:.3}", x);
+    let texto = texto.trim_end_matches('0').trim_end_matches('.');
+    if texto == "-0" {
+        "0".to_string()
+    } else {
+        texto.to_string()
+    }
+}
+
+fn campo_csv(texto: &str) -> String {
+    if texto.contains([',', '"', '\n']) {
+        format!("\"{}\"", texto.replace('"', "\"\""))
+    } else {
+        texto.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErroGrafico {
+    IntervaloInvalido { inicio: f64, fim: f64 },
+    // A função não está definida em nenhum ponto amostrado
+    SemPontos { funcao: String, erro: String },
+}
+
+impl fmt::Display for ErroGrafico {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroGrafico::IntervaloInvalido { inicio, fim } => {
+                write!(f, "Intervalo inválido: [{}, {}] (o início deve ser menor que o fim)", inicio, fim)
+            }
+            ErroGrafico::SemPontos { funcao, erro } => {
+                write!(f, "{} não está definida em nenhum ponto do intervalo: {}", funcao, erro)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErroGrafico {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Funcao = fn(f64) -> Result<f64, String>;
+
+    fn grafico(inicio: f64, fim: f64, largura: usize, altura: usize, estilo: Estilo, funcoes: &[(&str, Funcao)]) -> Grafico {
+        let mut grafico = Grafico::novo(inicio, fim, largura, altura, estilo).unwrap();
+        for (nome, f) in funcoes {
+            grafico.adicionar(nome, f).unwrap();
+        }
+        grafico
+    }
+
+    #[test]
+    fn test_desenhar_ascii() {
+        let identidade: Funcao = |x| Ok(x);
+        let desenho = grafico(-2.0, 2.0, 5, 5, Estilo::Ascii, &[("x", identidade)]).desenhar();
+        let esperado = " 2 +  | *\n   |  |* \n   |--*--\n   | *|  \n-2 +* |  \n   +-----\n    -2  2\n* x\n";
+        assert_eq!(desenho, esperado);
+    }
+
+    #[test]
+    fn test_desenhar_braille() {
+        let constante: Funcao = |_| Ok(1.0);
+        let desenho = grafico(1.0, 2.0, 3, 2, Estilo::Braille, &[("1", constante)]).desenhar();
+        // Faixa [0, 2]: a reta y = 1 passa pela linha de pontos do meio
+        assert_eq!(desenho.lines().next(), Some("2 ┤   "));
+        assert_eq!(desenho.lines().nth(1), Some("0 ┤⠉⠉⠉"));
+    }
+
+    #[test]
+    fn test_descontinuidades() {
+        let tangente: Funcao = |x| Ok(x.tan());
+        let grafico = grafico(-3.0, 3.0, 60, 16, Estilo::Braille, &[("tan(x)", tangente)]);
+        let descontinuidades = &grafico.series()[0].descontinuidades;
+        assert_eq!(descontinuidades.len(), 2);
+        for (d, esperado) in descontinuidades.iter().zip([-std::f64::consts::FRAC_PI_2, std::f64::consts::FRAC_PI_2]) {
+            assert!((d - esperado).abs() < 1e-6);
+        }
+        // A escala ignora as amostras vizinhas dos polos
+        assert!(grafico.faixa_y().1 < 20.0);
+
+        // Subida íngreme, mas contínua
+        let degrau_suave: Funcao = |x| Ok((50.0 * x).tanh());
+        let grafico = grafico_simples(degrau_suave);
+        assert!(grafico.series()[0].descontinuidades.is_empty());
+
+        let degrau: Funcao = |x| Ok(if x < 0.3 { 0.0 } else { 1.0 });
+        let grafico = grafico_simples(degrau);
+        assert_eq!(grafico.series()[0].descontinuidades.len(), 1);
+        assert!((grafico.series()[0].descontinuidades[0] - 0.3).abs() < 1e-6);
+    }
+
+    fn grafico_simples(f: Funcao) -> Grafico {
+        grafico(-1.0, 1.0, 30, 10, Estilo::Ascii, &[("f", f)])
+    }
+
+    #[test]
+    fn test_csv_e_erros() {
+        let raiz: Funcao = |x| if x < 0.0 { Err("domínio".to_string()) } else { Ok(x.sqrt()) };
+        let quadrado: Funcao = |x| Ok(x * x);
+        let grafico = grafico(-1.0, 1.0, 3, 2, Estilo::Ascii, &[("sqrt(x)", raiz), ("pow(x, 2)", quadrado)]);
+        assert_eq!(grafico.csv(), "x,sqrt(x),\"pow(x, 2)\"\n-1,,1\n0,0,0\n1,1,1\n");
+
+        let mut vazio = Grafico::novo(0.0, 1.0, 10, 5, Estilo::Ascii).unwrap();
+        let nunca: Funcao = |_| Err("Variável desconhecida: y".to_string());
+        assert_eq!(
+            vazio.adicionar("y", nunca),
+            Err(ErroGrafico::SemPontos { funcao: "y".to_string(), erro: "Variável desconhecida: y".to_string() })
+        );
+        assert_eq!(
+            Grafico::novo(1.0, 1.0, 10, 5, Estilo::Ascii),
+            Err(ErroGrafico::IntervaloInvalido { inicio: 1.0, fim: 1.0 })
+        );
+    }
+}
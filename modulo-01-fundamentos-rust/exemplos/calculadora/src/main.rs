@@ -1,25 +1,60 @@
+mod angulo;
 mod calculadora;
+mod calculo;
+mod cli;
+mod equacoes;
+mod erro;
+mod estatistica;
+mod expressao;
+mod grafico;
+mod matriz;
+mod numero;
 mod operacoes;
+mod operacoes_complexas;
+mod programador;
+mod registro;
+mod sessao;
+mod terminal;
+mod unidades;
 
-use std::io;
+use std::env;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    // Com argumentos, roda em modo não interativo (scripts, pipelines, CI)
+    let argumentos: Vec<String> = env::args().skip(1).collect();
+    if !argumentos.is_empty() {
+        return cli::executar(&argumentos).into();
+    }
 
-fn main() {
     println!("=== Calculadora Rust ===");
     println!("Bem-vindo à calculadora desenvolvida em Rust!");
-    
+
+    let mut sessao = sessao::Sessao::nova();
+
     loop {
         mostrar_menu();
         
-        let escolha = ler_escolha();
-        
+        // As primeiras opções vêm do registro; as últimas são fixas
+        let total = registro::OPERACOES.len() as u32;
+        let escolha = ler_escolha(total + 11);
+
         match escolha {
-            1 => calculadora::executar_operacao("soma", operacoes::somar),
-            2 => calculadora::executar_operacao("subtração", operacoes::subtrair),
-            3 => calculadora::executar_operacao("multiplicação", operacoes::multiplicar),
-            4 => calculadora::executar_operacao("divisão", operacoes::dividir),
-            5 => calculadora::executar_operacao("potenciação", operacoes::potencia),
-            6 => calculadora::executar_operacao("raiz quadrada", operacoes::raiz_quadrada),
-            7 => {
+            n if n >= 1 && n <= total => {
+                calculadora::executar(&registro::OPERACOES[n as usize - 1], &mut sessao)
+            }
+            n if n == total + 1 => calculadora::executar_expressao(&mut sessao),
+            n if n == total + 2 => calculadora::executar_conversao(&mut sessao),
+            n if n == total + 3 => calculadora::executar_estatistica(&mut sessao),
+            n if n == total + 4 => calculadora::executar_matrizes(&mut sessao),
+            n if n == total + 5 => calculadora::executar_equacoes(&mut sessao),
+            n if n == total + 6 => calculadora::executar_calculo(&mut sessao),
+            n if n == total + 7 => calculadora::executar_grafico(&mut sessao),
+            n if n == total + 8 => calculadora::executar_programador(&mut sessao),
+            n if n == total + 9 => calculadora::escolher_modo(&mut sessao),
+            n if n == total + 10 => calculadora::escolher_unidade_angulo(&mut sessao),
+            n if n == total + 11 => {
+                terminal::salvar_historico();
                 println!("Obrigado por usar a calculadora!");
                 break;
             }
@@ -31,25 +66,30 @@ fn main() {
         
         println!(); // Linha em branco para separar operações
     }
+
+    ExitCode::SUCCESS
 }
 
 fn mostrar_menu() {
     println!("\nEscolha uma operação:");
-    println!("1. Soma");
-    println!("2. Subtração");
-    println!("3. Multiplicação");
-    println!("4. Divisão");
-    println!("5. Potenciação");
-    println!("6. Raiz Quadrada");
-    println!("7. Sair");
-    print!("Digite sua escolha (1-7): ");
+    for (i, operacao) in registro::OPERACOES.iter().enumerate() {
+        println!("{}. {}", i + 1, operacao.nome);
+    }
+    let total = registro::OPERACOES.len();
+    println!("{}. Expressão", total + 1);
+    println!("{}. Conversão de Unidades", total + 2);
+    println!("{}. Estatística", total + 3);
+    println!("{}. Matrizes", total + 4);
+    println!("{}. Equações", total + 5);
+    println!("{}. Cálculo", total + 6);
+    println!("{}. Gráfico", total + 7);
+    println!("{}. Modo Programador", total + 8);
+    println!("{}. Modo Numérico", total + 9);
+    println!("{}. Unidade de Ângulo", total + 10);
+    println!("{}. Sair", total + 11);
 }
 
-fn ler_escolha() -> u32 {
-    let mut entrada = String::new();
-    io::stdin()
-        .read_line(&mut entrada)
-        .expect("Falha ao ler entrada");
-    
+fn ler_escolha(total: u32) -> u32 {
+    let entrada = terminal::ler_linha(&format!("Digite sua escolha (1-{}): ", total));
     entrada.trim().parse().unwrap_or(0)
 }
@@ -0,0 +1,458 @@
+use std::fmt;
+
+// Álgebra linear em f64: matrizes densas guardadas linha a linha. Vetores são
+// matrizes de uma coluna, para que A·x use a mesma multiplicação.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matriz {
+    linhas: usize,
+    colunas: usize,
+    dados: Vec<f64>,
+}
+
+impl Matriz {
+    pub fn zeros(linhas: usize, colunas: usize) -> Self {
+        Matriz { linhas, colunas, dados: vec![0.0; linhas * colunas] }
+    }
+
+    pub fn identidade(ordem: usize) -> Self {
+        let mut identidade = Matriz::zeros(ordem, ordem);
+        for i in 0..ordem {
+            identidade[(i, i)] = 1.0;
+        }
+        identidade
+    }
+
+    // Todas as linhas precisam ter o mesmo número de elementos
+    pub fn de_linhas(linhas: Vec<Vec<f64>>) -> Result<Self, ErroMatriz> {
+        let colunas = linhas.first().map_or(0, Vec::len);
+        if colunas == 0 {
+            return Err(ErroMatriz::Vazia);
+        }
+        let mut dados = Vec::with_capacity(linhas.len() * colunas);
+        for (i, linha) in linhas.iter().enumerate() {
+            if linha.len() != colunas {
+                return Err(ErroMatriz::LinhaIrregular { linha: i + 1, esperado: colunas, obtido: linha.len() });
+            }
+            dados.extend_from_slice(linha);
+        }
+        Ok(Matriz { linhas: linhas.len(), colunas, dados })
+    }
+
+    pub fn coluna(valores: &[f64]) -> Self {
+        Matriz { linhas: valores.len(), colunas: 1, dados: valores.to_vec() }
+    }
+
+    pub fn dimensoes(&self) -> (usize, usize) {
+        (self.linhas, self.colunas)
+    }
+
+    pub fn eh_quadrada(&self) -> bool {
+        self.linhas == self.colunas
+    }
+
+    fn exigir_quadrada(&self) -> Result<(), ErroMatriz> {
+        if self.eh_quadrada() {
+            Ok(())
+        } else {
+            Err(ErroMatriz::NaoQuadrada { linhas: self.linhas, colunas: self.colunas })
+        }
+    }
+
+    pub fn somar(&self, outra: &Matriz) -> Result<Matriz, ErroMatriz> {
+        if self.dimensoes() != outra.dimensoes() {
+            return Err(ErroMatriz::DimensoesIncompativeis {
+                operacao: "soma",
+                esquerda: self.dimensoes(),
+                direita: outra.dimensoes(),
+            });
+        }
+        let dados = self.dados.iter().zip(&outra.dados).map(|(a, b)| a + b).collect();
+        Ok(Matriz { dados, ..*self })
+    }
+
+    pub fn multiplicar(&self, outra: &Matriz) -> Result<Matriz, ErroMatriz> {
+        if self.colunas != outra.linhas {
+            return Err(ErroMatriz::DimensoesIncompativeis {
+                operacao: "multiplicação",
+                esquerda: self.dimensoes(),
+                direita: outra.dimensoes(),
+            });
+        }
+        let mut produto = Matriz::zeros(self.linhas, outra.colunas);
+        for i in 0..self.linhas {
+            for k in 0..self.colunas {
+                let a = self[(i, k)];
+                for j in 0..outra.colunas {
+                    produto[(i, j)] += a * outra[(k, j)];
+                }
+            }
+        }
+        Ok(produto)
+    }
+
+    pub fn transposta(&self) -> Matriz {
+        let mut transposta = Matriz::zeros(self.colunas, self.linhas);
+        for i in 0..self.linhas {
+            for j in 0..self.colunas {
+                transposta[(j, i)] = self[(i, j)];
+            }
+        }
+        transposta
+    }
+
+    pub fn determinante(&self) -> Result<f64, ErroMatriz> {
+        let lu = Lu::decompor(self)?;
+        if lu.eh_singular() {
+            return Ok(0.0);
+        }
+        Ok((0..self.linhas).fold(lu.sinal, |det, i| det * lu.fatores[(i, i)]))
+    }
+
+    pub fn inversa(&self) -> Result<Matriz, ErroMatriz> {
+        let lu = Lu::decompor(self)?;
+        lu.resolver(&Matriz::identidade(self.linhas))
+    }
+
+    // Resolve A·x = b; `b` pode ter várias colunas (um sistema por coluna)
+    pub fn resolver(&self, b: &Matriz) -> Result<Matriz, ErroMatriz> {
+        let lu = Lu::decompor(self)?;
+        lu.resolver(b)
+    }
+
+    // Número de linhas linearmente independentes, por eliminação gaussiana
+    // com pivoteamento parcial. Funciona também para matrizes retangulares.
+    pub fn posto(&self) -> usize {
+        let mut a = self.clone();
+        let tolerancia = a.tolerancia();
+        let mut posto = 0;
+        for coluna in 0..a.colunas {
+            if posto == a.linhas {
+                break;
+            }
+            let pivo = (posto..a.linhas)
+                .max_by(|&i, &j| a[(i, coluna)].abs().total_cmp(&a[(j, coluna)].abs()))
+                .expect("ainda restam linhas");
+            if a[(pivo, coluna)].abs() <= tolerancia {
+                continue;
+            }
+            a.trocar_linhas(posto, pivo);
+            for i in posto + 1..a.linhas {
+                let fator = a[(i, coluna)] / a[(posto, coluna)];
+                for j in coluna..a.colunas {
+                    let valor = a[(posto, j)];
+                    a[(i, j)] -= fator * valor;
+                }
+            }
+            posto += 1;
+        }
+        posto
+    }
+
+    // Pivôs menores que isso são tratados como zero: acumulam só o erro de
+    // arredondamento da eliminação
+    fn tolerancia(&self) -> f64 {
+        let maior = self.dados.iter().fold(0.0_f64, |maior, x| maior.max(x.abs()));
+        self.linhas.max(self.colunas) as f64 * f64::EPSILON * maior
+    }
+
+    fn trocar_linhas(&mut self, a: usize, b: usize) {
+        if a != b {
+            for j in 0..self.colunas {
+                self.dados.swap(a * self.colunas + j, b * self.colunas + j);
+            }
+        }
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for Matriz {
+    type Output = f64;
+
+    fn index(&self, (i, j): (usize, usize)) -> &f64 {
+        &self.dados[i * self.colunas + j]
+    }
+}
+
+impl std::ops::IndexMut<(usize, usize)> for Matriz {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut f64 {
+        &mut self.dados[i * self.colunas + j]
+    }
+}
+
+// Resíduos de arredondamento como 0.49999999999999994 aparecem como 0.5
+fn formatar_elemento(x: f64) -> String {
+    let arredondado: f64 = format!("{:.11e}", x).parse().unwrap_or(x);
+    (arredondado + 0.0).to_string()
+}
+
+// Colunas alinhadas à direita entre colchetes, uma linha da matriz por linha
+impl fmt::Display for Matriz {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let textos: Vec<String> = self.dados.iter().map(|&x| formatar_elemento(x)).collect();
+        let larguras: Vec<usize> = (0..self.colunas)
+            .map(|j| (0..self.linhas).map(|i| textos[i * self.colunas + j].chars().count()).max().unwrap_or(0))
+            .collect();
+        for i in 0..self.linhas {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[")?;
+            for (j, largura) in larguras.iter().enumerate() {
+                write!(f, " {:>largura$}", textos[i * self.colunas + j], largura = largura)?;
+            }
+            write!(f, " ]")?;
+        }
+        Ok(())
+    }
+}
+
+// Decomposição P·A = L·U com pivoteamento parcial. L (diagonal 1, implícita)
+// e U ficam na mesma matriz `fatores`.
+struct Lu {
+    fatores: Matriz,
+    // permutacao[i] = linha de A que foi parar na linha i
+    permutacao: Vec<usize>,
+    // +1 ou -1 conforme o número de trocas de linha, para o determinante
+    sinal: f64,
+    tolerancia: f64,
+}
+
+impl Lu {
+    fn decompor(a: &Matriz) -> Result<Lu, ErroMatriz> {
+        a.exigir_quadrada()?;
+        let n = a.linhas;
+        let mut fatores = a.clone();
+        let mut permutacao: Vec<usize> = (0..n).collect();
+        let mut sinal = 1.0;
+
+        for k in 0..n {
+            // O maior pivô disponível limita o crescimento dos erros
+            let pivo = (k..n)
+                .max_by(|&i, &j| fatores[(i, k)].abs().total_cmp(&fatores[(j, k)].abs()))
+                .expect("k < n");
+            if pivo != k {
+                fatores.trocar_linhas(k, pivo);
+                permutacao.swap(k, pivo);
+                sinal = -sinal;
+            }
+            if fatores[(k, k)] == 0.0 {
+                continue;
+            }
+            for i in k + 1..n {
+                let fator = fatores[(i, k)] / fatores[(k, k)];
+                fatores[(i, k)] = fator;
+                for j in k + 1..n {
+                    let valor = fatores[(k, j)];
+                    fatores[(i, j)] -= fator * valor;
+                }
+            }
+        }
+
+        Ok(Lu { fatores, permutacao, sinal, tolerancia: a.tolerancia() })
+    }
+
+    fn eh_singular(&self) -> bool {
+        (0..self.fatores.linhas).any(|i| self.fatores[(i, i)].abs() <= self.tolerancia)
+    }
+
+    fn resolver(&self, b: &Matriz) -> Result<Matriz, ErroMatriz> {
+        let n = self.fatores.linhas;
+        if b.linhas != n {
+            return Err(ErroMatriz::DimensoesIncompativeis {
+                operacao: "sistema A·x = b",
+                esquerda: self.fatores.dimensoes(),
+                direita: b.dimensoes(),
+            });
+        }
+        if self.eh_singular() {
+            return Err(ErroMatriz::Singular);
+        }
+
+        let mut x = Matriz::zeros(n, b.colunas);
+        for coluna in 0..b.colunas {
+            // L·y = P·b (substituição direta), depois U·x = y (retroativa)
+            let mut y: Vec<f64> = self.permutacao.iter().map(|&i| b[(i, coluna)]).collect();
+            for i in 0..n {
+                for j in 0..i {
+                    y[i] -= self.fatores[(i, j)] * y[j];
+                }
+            }
+            for i in (0..n).rev() {
+                for j in i + 1..n {
+                    y[i] -= self.fatores[(i, j)] * y[j];
+                }
+                y[i] /= self.fatores[(i, i)];
+            }
+            for (i, valor) in y.into_iter().enumerate() {
+                x[(i, coluna)] = valor;
+            }
+        }
+        Ok(x)
+    }
+}
+
+// Matriz digitada: linhas separadas por ';' ou quebra de linha, elementos
+// por vírgula ou espaço. Colchetes são ignorados: [1, 2; 3, 4]
+pub fn ler_matriz(texto: &str) -> Result<Matriz, ErroMatriz> {
+    let linhas = texto
+        .split([';', '\n'])
+        .map(|linha| linha.trim_matches(|c: char| c.is_whitespace() || c == '[' || c == ']'))
+        .filter(|linha| !linha.is_empty())
+        .map(ler_vetor)
+        .collect::<Result<Vec<_>, _>>()?;
+    Matriz::de_linhas(linhas)
+}
+
+pub fn ler_vetor(texto: &str) -> Result<Vec<f64>, ErroMatriz> {
+    texto
+        .split(|c: char| c == ',' || c.is_whitespace() || c == '[' || c == ']')
+        .filter(|parte| !parte.is_empty())
+        .map(|parte| {
+            parte
+                .parse::<f64>()
+                .ok()
+                .filter(|x| x.is_finite())
+                .ok_or_else(|| ErroMatriz::ValorInvalido(parte.to_string()))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErroMatriz {
+    Vazia,
+    LinhaIrregular {
+        linha: usize,
+        esperado: usize,
+        obtido: usize,
+    },
+    ValorInvalido(String),
+    DimensoesIncompativeis {
+        operacao: &'static str,
+        esquerda: (usize, usize),
+        direita: (usize, usize),
+    },
+    // Determinante, inversa e sistemas exigem matriz quadrada
+    NaoQuadrada {
+        linhas: usize,
+        colunas: usize,
+    },
+    // Sem inversa: o sistema não tem solução única
+    Singular,
+}
+
+impl fmt::Display for ErroMatriz {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroMatriz::Vazia => write!(f, "Matriz vazia"),
+            ErroMatriz::LinhaIrregular { linha, esperado, obtido } => write!(
+                f,
+                "A linha {} tem {} elementos, mas a primeira tem {}",
+                linha, obtido, esperado
+            ),
+            ErroMatriz::ValorInvalido(texto) => write!(f, "Número inválido: {}", texto),
+            ErroMatriz::DimensoesIncompativeis { operacao, esquerda, direita } => write!(
+                f,
+                "Dimensões incompatíveis para {}: {}x{} e {}x{}",
+                operacao, esquerda.0, esquerda.1, direita.0, direita.1
+            ),
+            ErroMatriz::NaoQuadrada { linhas, colunas } => {
+                write!(f, "A matriz precisa ser quadrada (é {}x{})", linhas, colunas)
+            }
+            ErroMatriz::Singular => write!(f, "Matriz singular: não tem inversa"),
+        }
+    }
+}
+
+impl std::error::Error for ErroMatriz {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn m(texto: &str) -> Matriz {
+        ler_matriz(texto).unwrap()
+    }
+
+    #[track_caller]
+    fn assert_aprox(resultado: &Matriz, esperado: &Matriz) {
+        assert_eq!(resultado.dimensoes(), esperado.dimensoes());
+        for (a, b) in resultado.dados.iter().zip(&esperado.dados) {
+            assert!((a - b).abs() <= 1e-12 * b.abs().max(1.0), "{}\nnão é aproximadamente\n{}", resultado, esperado);
+        }
+    }
+
+    #[test]
+    fn test_ler_matriz() {
+        assert_eq!(m("[1, 2; 3, 4]"), Matriz::de_linhas(vec![vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap());
+        assert_eq!(m("1 2\n3 4\n"), m("1,2;3,4"));
+        assert_eq!(ler_matriz("1 2; 3"), Err(ErroMatriz::LinhaIrregular { linha: 2, esperado: 2, obtido: 1 }));
+        assert_eq!(ler_matriz("1 x"), Err(ErroMatriz::ValorInvalido("x".to_string())));
+        assert_eq!(ler_matriz(" ; "), Err(ErroMatriz::Vazia));
+    }
+
+    #[test]
+    fn test_soma_e_multiplicacao() {
+        assert_eq!(m("1 2; 3 4").somar(&m("10 20; 30 40")), Ok(m("11 22; 33 44")));
+        assert_eq!(m("1 2; 3 4").multiplicar(&m("5 6; 7 8")), Ok(m("19 22; 43 50")));
+        assert_eq!(m("1 2 3").multiplicar(&Matriz::coluna(&[4.0, 5.0, 6.0])), Ok(m("32")));
+        assert_eq!(
+            m("1 2; 3 4").multiplicar(&m("1 2 3")),
+            Err(ErroMatriz::DimensoesIncompativeis { operacao: "multiplicação", esquerda: (2, 2), direita: (1, 3) })
+        );
+        assert!(matches!(m("1 2").somar(&m("1; 2")), Err(ErroMatriz::DimensoesIncompativeis { operacao: "soma", .. })));
+    }
+
+    #[test]
+    fn test_transposta() {
+        assert_eq!(m("1 2 3; 4 5 6").transposta(), m("1 4; 2 5; 3 6"));
+    }
+
+    #[test]
+    fn test_determinante() {
+        assert_eq!(m("4 7; 2 6").determinante(), Ok(10.0));
+        // Exige troca de linhas: o primeiro pivô é zero
+        assert_eq!(m("0 1; 1 0").determinante(), Ok(-1.0));
+        assert_eq!(m("1 2 3; 4 5 6; 7 8 9").determinante(), Ok(0.0));
+        assert_eq!(m("1 2 3").determinante(), Err(ErroMatriz::NaoQuadrada { linhas: 1, colunas: 3 }));
+    }
+
+    #[test]
+    fn test_inversa() {
+        let a = m("4 7; 2 6");
+        let inversa = a.inversa().unwrap();
+        assert_aprox(&inversa, &m("0.6 -0.7; -0.2 0.4"));
+        assert_aprox(&a.multiplicar(&inversa).unwrap(), &Matriz::identidade(2));
+        assert_eq!(m("1 2; 2 4").inversa(), Err(ErroMatriz::Singular));
+    }
+
+    #[test]
+    fn test_resolver_sistema() {
+        // 2x + y - z = 8, -3x - y + 2z = -11, -2x + y + 2z = -3
+        let a = m("2 1 -1; -3 -1 2; -2 1 2");
+        let x = a.resolver(&Matriz::coluna(&[8.0, -11.0, -3.0])).unwrap();
+        assert_aprox(&x, &Matriz::coluna(&[2.0, 3.0, -1.0]));
+        assert_eq!(
+            a.resolver(&Matriz::coluna(&[1.0, 2.0])),
+            Err(ErroMatriz::DimensoesIncompativeis {
+                operacao: "sistema A·x = b",
+                esquerda: (3, 3),
+                direita: (2, 1)
+            })
+        );
+    }
+
+    #[test]
+    fn test_posto() {
+        assert_eq!(m("1 2; 3 4").posto(), 2);
+        assert_eq!(m("1 2 3; 4 5 6; 7 8 9").posto(), 2);
+        assert_eq!(m("1 2 3; 2 4 6").posto(), 1);
+        assert_eq!(m("0 0; 0 0").posto(), 0);
+        assert_eq!(m("1 0; 0 1; 1 1").posto(), 2);
+    }
+
+    #[test]
+    fn test_exibir() {
+        assert_eq!(m("1 -2.5; 30 4").to_string(), "[  1 -2.5 ]\n[ 30    4 ]");
+        assert_eq!(formatar_elemento(0.49999999999999994), "0.5");
+        assert_eq!(formatar_elemento(-0.0), "0");
+    }
+}
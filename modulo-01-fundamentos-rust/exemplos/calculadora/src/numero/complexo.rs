@@ -0,0 +1,342 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use num_complex::Complex64;
+use num_rational::BigRational;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use super::{FuncaoBinaria, FuncaoUnaria, Modo, Numero, Valor};
+use crate::angulo::UnidadeAngulo;
+use crate::erro::{verificar_finito, CalcError, Resultado};
+use crate::operacoes;
+
+// Número complexo a + bi com as duas partes em f64. Só é ordenável quando as
+// duas partes imaginárias são zero: as verificações de domínio de `operacoes`
+// não valem aqui, e cada função tem a sua versão em `operacoes_complexas`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Complexo(pub Complex64);
+
+impl Complexo {
+    pub const I: Complexo = Complexo(Complex64::new(0.0, 1.0));
+
+    pub fn new(re: f64, im: f64) -> Self {
+        Complexo(Complex64::new(re, im))
+    }
+
+    pub fn re(&self) -> f64 {
+        self.0.re
+    }
+
+    pub fn im(&self) -> f64 {
+        self.0.im
+    }
+
+    pub fn eh_real(&self) -> bool {
+        self.0.im == 0.0
+    }
+
+    // Cos e sen das versões reais: 2∠90° dá exatamente 2i
+    pub fn de_polar(modulo: f64, radianos: f64) -> Resultado<Complexo> {
+        let re = operacoes::cosseno(radianos)?;
+        let im = operacoes::seno(radianos)?;
+        verificar_complexo(Complex64::new(modulo * re, modulo * im), &[Complexo::new(modulo, radianos)])
+    }
+
+    // r∠θ, com θ na unidade de ângulo da sessão
+    pub fn polar(&self, unidade: UnidadeAngulo) -> String {
+        format!("{}∠{}", sem_zero_negativo(self.0.norm()), sem_zero_negativo(unidade.de_radianos(self.0.arg())))
+    }
+
+    pub fn mapear_partes(self, f: impl Fn(f64) -> f64) -> Complexo {
+        Complexo::new(f(self.0.re), f(self.0.im))
+    }
+
+    // Parte muito menor que a outra é resíduo de arredondamento das funções
+    // transcendentais: e^(iπ) daria -1 + 1.2e-16i
+    pub fn limpar(self) -> Complexo {
+        let (re, im) = (self.0.re, self.0.im);
+        let ruido = |parte: f64, outra: f64| parte.abs() <= 4.0 * f64::EPSILON * outra.abs();
+        if ruido(im, re) {
+            Complexo::new(re, 0.0)
+        } else if ruido(re, im) {
+            Complexo::new(0.0, im)
+        } else {
+            self
+        }
+    }
+
+    fn formatar(&self, parte: impl Fn(f64) -> String) -> String {
+        let (re, im) = (sem_zero_negativo(self.0.re), sem_zero_negativo(self.0.im));
+        let imaginaria = |x: f64| if x == 1.0 { "i".to_string() } else { format!("{}i", parte(x)) };
+        match (re, im) {
+            (_, 0.0) => parte(re),
+            (0.0, im) if im < 0.0 => format!("-{}", imaginaria(-im)),
+            (0.0, im) => imaginaria(im),
+            (re, im) if im < 0.0 => format!("{} - {}", parte(re), imaginaria(-im)),
+            (re, im) => format!("{} + {}", parte(re), imaginaria(im)),
+        }
+    }
+}
+
+fn sem_zero_negativo(x: f64) -> f64 {
+    x + 0.0
+}
+
+// Converte partes NaN/infinitas em erro, como `verificar_finito` faz com f64
+pub fn verificar_complexo(resultado: Complex64, entradas: &[Complexo]) -> Resultado<Complexo> {
+    let partes: Vec<f64> = entradas.iter().flat_map(|z| [z.0.re, z.0.im]).collect();
+    verificar_finito(resultado.re, &partes)?;
+    verificar_finito(resultado.im, &partes)?;
+    Ok(Complexo(resultado))
+}
+
+impl PartialOrd for Complexo {
+    fn partial_cmp(&self, outro: &Self) -> Option<Ordering> {
+        if self.eh_real() && outro.eh_real() {
+            self.0.re.partial_cmp(&outro.0.re)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Complexo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.formatar(|x| x.to_string()))
+    }
+}
+
+// Forma retangular digitada: 3, 2i, -i, 3+4i, 1.5 - 2e-3i
+impl FromStr for Complexo {
+    type Err = ();
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        let texto: String = texto.chars().filter(|c| !c.is_whitespace()).collect();
+        let parte = |texto: &str| texto.parse::<f64>().ok().filter(|x| x.is_finite()).ok_or(());
+        let Some(sem_i) = texto.strip_suffix('i') else {
+            return Ok(Complexo::new(parte(&texto)?, 0.0));
+        };
+
+        // O sinal que separa as partes não pode ser o do expoente (2e-3i)
+        let bytes = sem_i.as_bytes();
+        let separador = (1..bytes.len())
+            .rev()
+            .find(|&i| matches!(bytes[i], b'+' | b'-') && !matches!(bytes[i - 1], b'e' | b'E'));
+        let (re, im) = match separador {
+            Some(i) => (parte(&sem_i[..i])?, &sem_i[i..]),
+            None => (0.0, sem_i),
+        };
+        let im = match im {
+            "" | "+" => 1.0,
+            "-" => -1.0,
+            im => parte(im)?,
+        };
+        Ok(Complexo::new(re, im))
+    }
+}
+
+// Como os complexos aparecem para o usuário
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FormaComplexa {
+    #[default]
+    Retangular,
+    Polar,
+}
+
+impl FormaComplexa {
+    pub const TODAS: [FormaComplexa; 2] = [FormaComplexa::Retangular, FormaComplexa::Polar];
+
+    pub fn descricao(&self) -> &'static str {
+        match self {
+            FormaComplexa::Retangular => "a + bi (padrão)",
+            FormaComplexa::Polar => "r∠θ, com θ na unidade de ângulo",
+        }
+    }
+}
+
+impl fmt::Display for FormaComplexa {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nome = match self {
+            FormaComplexa::Retangular => "retangular",
+            FormaComplexa::Polar => "polar",
+        };
+        write!(f, "{}", nome)
+    }
+}
+
+impl FromStr for FormaComplexa {
+    type Err = String;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        FormaComplexa::TODAS
+            .into_iter()
+            .find(|forma| forma.to_string() == texto.trim().to_lowercase())
+            .ok_or_else(|| format!("Forma desconhecida: {}", texto))
+    }
+}
+
+impl Numero for Complexo {
+    const MODO: Modo = Modo::Complexo;
+
+    fn zero() -> Self {
+        Complexo::new(0.0, 0.0)
+    }
+
+    fn um() -> Self {
+        Complexo::new(1.0, 0.0)
+    }
+
+    fn de_texto(literal: &str) -> Resultado<Self> {
+        literal.parse().map_err(|_| CalcError::NaoExato { operacao: "número" })
+    }
+
+    fn de_f64(valor: f64) -> Option<Self> {
+        Some(valor).filter(|x| x.is_finite()).map(|x| Complexo::new(x, 0.0))
+    }
+
+    fn de_racional(valor: &BigRational) -> Option<Self> {
+        valor.to_f64().and_then(Self::de_f64)
+    }
+
+    fn para_racional(&self) -> Option<BigRational> {
+        if self.eh_real() {
+            self.0.re.para_racional()
+        } else {
+            None
+        }
+    }
+
+    // Só a parte real; quem precisa das duas usa `para_complexo`
+    fn para_f64(&self) -> f64 {
+        self.0.re
+    }
+
+    fn para_complexo(&self) -> Complexo {
+        *self
+    }
+
+    fn de_complexo(valor: Complexo) -> Option<Self> {
+        Some(valor)
+    }
+
+    fn para_valor(self) -> Valor {
+        Valor::Complexo(self)
+    }
+
+    fn eh_inteiro(&self) -> bool {
+        self.eh_real() && self.0.re.fract() == 0.0
+    }
+
+    fn somar(&self, outro: &Self) -> Resultado<Self> {
+        verificar_complexo(self.0 + outro.0, &[*self, *outro])
+    }
+
+    fn subtrair(&self, outro: &Self) -> Resultado<Self> {
+        verificar_complexo(self.0 - outro.0, &[*self, *outro])
+    }
+
+    fn multiplicar(&self, outro: &Self) -> Resultado<Self> {
+        verificar_complexo(self.0 * outro.0, &[*self, *outro])
+    }
+
+    fn dividir(&self, outro: &Self) -> Resultado<Self> {
+        verificar_complexo(self.0 / outro.0, &[*self, *outro])
+    }
+
+    fn resto(&self, outro: &Self) -> Resultado<Self> {
+        if !self.eh_real() || !outro.eh_real() {
+            return Err(CalcError::SoReal { operacao: "resto" });
+        }
+        Ok(Complexo::new(self.0.re.resto(&outro.0.re)?, 0.0))
+    }
+
+    // Chamada com base diferente de zero
+    fn potencia(&self, expoente: &Self) -> Resultado<Self> {
+        let resultado = if self.eh_real() && self.0.re > 0.0 && expoente.eh_real() {
+            Complex64::new(self.0.re.powf(expoente.0.re), 0.0)
+        } else if expoente.eh_inteiro() && expoente.0.re.abs() <= i32::MAX as f64 {
+            // Multiplicações sucessivas: (1 + i)^2 dá exatamente 2i
+            self.0.powi(expoente.0.re as i32)
+        } else {
+            self.0.powc(expoente.0)
+        };
+        verificar_complexo(resultado, &[*self, *expoente]).map(Complexo::limpar)
+    }
+
+    // Negativos têm raiz exata no eixo imaginário: sqrt(-4) = 2i
+    fn raiz_quadrada(&self) -> Resultado<Self> {
+        let resultado = match (self.eh_real(), self.0.re) {
+            (true, re) if re >= 0.0 => Complex64::new(re.sqrt(), 0.0),
+            (true, re) => Complex64::new(0.0, (-re).sqrt()),
+            _ => self.0.sqrt(),
+        };
+        verificar_complexo(resultado, &[*self])
+    }
+
+    fn unaria(funcao: &FuncaoUnaria) -> fn(Self) -> Resultado<Self> {
+        funcao.complexo
+    }
+
+    fn binaria(funcao: &FuncaoBinaria) -> fn(Self, Self) -> Resultado<Self> {
+        funcao.complexo
+    }
+
+    fn exibir(&self) -> String {
+        self.formatar(|x| format!("{:.2}", x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ler_forma_retangular() {
+        assert_eq!("3+4i".parse(), Ok(Complexo::new(3.0, 4.0)));
+        assert_eq!("1.5 - 2e-3i".parse(), Ok(Complexo::new(1.5, -0.002)));
+        assert_eq!("-i".parse(), Ok(Complexo::new(0.0, -1.0)));
+        assert_eq!("2i".parse(), Ok(Complexo::new(0.0, 2.0)));
+        assert_eq!("-7".parse(), Ok(Complexo::new(-7.0, 0.0)));
+        assert_eq!("3+i".parse(), Ok(Complexo::new(3.0, 1.0)));
+        assert!("3+4j".parse::<Complexo>().is_err());
+        assert!("i+3".parse::<Complexo>().is_err());
+    }
+
+    #[test]
+    fn test_exibir() {
+        assert_eq!(Complexo::new(0.0, 2.0).to_string(), "2i");
+        assert_eq!(Complexo::new(3.0, -4.0).to_string(), "3 - 4i");
+        assert_eq!(Complexo::new(-0.0, -1.0).to_string(), "-i");
+        assert_eq!(Complexo::new(-1.0, 0.0).to_string(), "-1");
+        assert_eq!(Complexo::new(0.5, 1.0).exibir(), "0.50 + i");
+        for texto in ["2i", "3 - 4i", "-i", "-1", "0.5 + 1.25i"] {
+            assert_eq!(texto.parse::<Complexo>().unwrap().to_string(), texto);
+        }
+    }
+
+    #[test]
+    fn test_forma_polar() {
+        assert_eq!(Complexo::de_polar(2.0, std::f64::consts::FRAC_PI_2), Ok(Complexo::new(0.0, 2.0)));
+        assert_eq!(Complexo::new(0.0, 2.0).polar(UnidadeAngulo::Graus), "2∠90");
+        assert_eq!(Complexo::new(-1.0, 0.0).polar(UnidadeAngulo::Radianos), format!("1∠{}", std::f64::consts::PI));
+        assert_eq!("polar".parse(), Ok(FormaComplexa::Polar));
+    }
+
+    #[test]
+    fn test_raiz_e_potencia() {
+        assert_eq!(Complexo::new(-4.0, 0.0).raiz_quadrada(), Ok(Complexo::new(0.0, 2.0)));
+        assert_eq!(Complexo::new(1.0, 1.0).potencia(&Complexo::new(2.0, 0.0)), Ok(Complexo::new(0.0, 2.0)));
+        assert_eq!(Complexo::I.multiplicar(&Complexo::I), Ok(Complexo::new(-1.0, 0.0)));
+        let e = Complexo::new(std::f64::consts::E, 0.0);
+        let i_pi = Complexo::new(0.0, std::f64::consts::PI);
+        assert_eq!(e.potencia(&i_pi), Ok(Complexo::new(-1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_ordem_so_entre_reais() {
+        assert!(Complexo::new(1.0, 0.0) < Complexo::new(2.0, 0.0));
+        assert_eq!(Complexo::I.partial_cmp(&Complexo::zero()), None);
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Signed, ToPrimitive, Zero};
+use serde::{Deserialize, Serialize};
+
+use super::inteiro::verificar_tamanho;
+use super::{racional_de_literal, FuncaoBinaria, FuncaoUnaria, Modo, Numero, Valor};
+use crate::erro::{verificar_finito, CalcError, Resultado};
+
+pub const CASAS_DECIMAIS: u32 = 20;
+
+fn escala() -> BigInt {
+    BigInt::from(10).pow(CASAS_DECIMAIS)
+}
+
+// Número decimal com quantidade fixa de casas: valor = mantissa / 10^CASAS_DECIMAIS.
+// Somas e subtrações são exatas; produtos e quocientes arredondam na última casa.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Decimal {
+    mantissa: BigInt,
+}
+
+impl Decimal {
+    // Arredonda para a casa mais próxima (metade se afasta do zero)
+    fn arredondar(valor: &BigRational) -> Self {
+        let escalado = valor * BigRational::from_integer(escala());
+        Decimal { mantissa: escalado.round().to_integer() }
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digitos = self.mantissa.abs().to_string();
+        let casas = CASAS_DECIMAIS as usize;
+        let digitos = format!("{:0>largura$}", digitos, largura = casas + 1);
+        let (inteira, fracionaria) = digitos.split_at(digitos.len() - casas);
+        let fracionaria = fracionaria.trim_end_matches('0');
+
+        if self.mantissa.is_negative() {
+            write!(f, "-")?;
+        }
+        if fracionaria.is_empty() {
+            write!(f, "{}", inteira)
+        } else {
+            write!(f, "{}.{}", inteira, fracionaria)
+        }
+    }
+}
+
+impl Numero for Decimal {
+    const MODO: Modo = Modo::Decimal;
+
+    fn zero() -> Self {
+        Decimal { mantissa: BigInt::from(0) }
+    }
+
+    fn um() -> Self {
+        Decimal { mantissa: escala() }
+    }
+
+    fn de_texto(literal: &str) -> Resultado<Self> {
+        racional_de_literal(literal).map(|r| Self::arredondar(&r))
+    }
+
+    fn de_f64(valor: f64) -> Option<Self> {
+        valor.para_racional().map(|r| Self::arredondar(&r))
+    }
+
+    fn de_racional(valor: &BigRational) -> Option<Self> {
+        Some(Self::arredondar(valor))
+    }
+
+    fn para_racional(&self) -> Option<BigRational> {
+        Some(BigRational::new(self.mantissa.clone(), escala()))
+    }
+
+    fn para_f64(&self) -> f64 {
+        BigRational::new(self.mantissa.clone(), escala()).to_f64().unwrap_or(f64::NAN)
+    }
+
+    fn para_valor(self) -> Valor {
+        Valor::Decimal(self)
+    }
+
+    fn eh_inteiro(&self) -> bool {
+        (&self.mantissa % escala()).is_zero()
+    }
+
+    fn somar(&self, outro: &Self) -> Resultado<Self> {
+        Ok(Decimal { mantissa: &self.mantissa + &outro.mantissa })
+    }
+
+    fn subtrair(&self, outro: &Self) -> Resultado<Self> {
+        Ok(Decimal { mantissa: &self.mantissa - &outro.mantissa })
+    }
+
+    fn multiplicar(&self, outro: &Self) -> Resultado<Self> {
+        let produto = BigRational::new(&self.mantissa * &outro.mantissa, escala() * escala());
+        Ok(Self::arredondar(&produto))
+    }
+
+    fn dividir(&self, outro: &Self) -> Resultado<Self> {
+        Ok(Self::arredondar(&BigRational::new(self.mantissa.clone(), outro.mantissa.clone())))
+    }
+
+    fn resto(&self, outro: &Self) -> Resultado<Self> {
+        Ok(Decimal { mantissa: &self.mantissa % &outro.mantissa })
+    }
+
+    fn potencia(&self, expoente: &Self) -> Resultado<Self> {
+        if expoente.eh_inteiro() {
+            let expoente = &expoente.mantissa / escala();
+            let base = BigRational::new(self.mantissa.clone(), escala());
+            verificar_tamanho(base.numer().bits() + base.denom().bits(), &expoente.abs())?;
+            let expoente = expoente.to_i32().ok_or(CalcError::Overflow)?;
+            return Ok(Self::arredondar(&base.pow(expoente)));
+        }
+        // Expoente fracionário: não há forma exata, então calcula em f64
+        let (base, expoente) = (self.para_f64(), expoente.para_f64());
+        let resultado = verificar_finito(base.powf(expoente), &[base, expoente])?;
+        Self::de_f64(resultado).ok_or(CalcError::NaoFinito)
+    }
+
+    fn raiz_quadrada(&self) -> Resultado<Self> {
+        // sqrt(m / 10^c) = sqrt(m * 10^c) / 10^c, truncado na última casa
+        Ok(Decimal { mantissa: (&self.mantissa * escala()).sqrt() })
+    }
+
+    fn unaria(funcao: &FuncaoUnaria) -> fn(Self) -> Resultado<Self> {
+        funcao.decimal
+    }
+
+    fn binaria(funcao: &FuncaoBinaria) -> fn(Self, Self) -> Resultado<Self> {
+        funcao.decimal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(texto: &str) -> Decimal {
+        Decimal::de_texto(texto).unwrap()
+    }
+
+    #[test]
+    fn test_exibicao() {
+        assert_eq!(d("0.1").to_string(), "0.1");
+        assert_eq!(d("-12.50").to_string(), "-12.5");
+        assert_eq!(d("3").to_string(), "3");
+        assert_eq!(d("-0.001").to_string(), "-0.001");
+    }
+
+    #[test]
+    fn test_soma_sem_erro_binario() {
+        assert_eq!(d("0.1").somar(&d("0.2")).unwrap(), d("0.3"));
+    }
+
+    #[test]
+    fn test_divisao_arredonda_na_ultima_casa() {
+        assert_eq!(d("1").dividir(&d("3")).unwrap().to_string(), "0.33333333333333333333");
+        assert_eq!(d("2").dividir(&d("3")).unwrap().to_string(), "0.66666666666666666667");
+    }
+
+    #[test]
+    fn test_potencia_e_raiz() {
+        assert_eq!(d("1.1").potencia(&d("2")).unwrap(), d("1.21"));
+        assert_eq!(d("2").raiz_quadrada().unwrap().to_string(), "1.4142135623730950488");
+    }
+}
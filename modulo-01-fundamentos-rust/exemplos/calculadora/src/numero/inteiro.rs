@@ -0,0 +1,166 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, One, Signed, ToPrimitive, Zero};
+
+use super::{racional_de_literal, FuncaoBinaria, FuncaoUnaria, Modo, Numero, Valor};
+use crate::erro::{CalcError, Resultado};
+
+// Limite de tamanho dos resultados (em bits, cerca de 300 mil dígitos)
+// para que uma potência gigante não trave a calculadora
+const LIMITE_BITS: u64 = 1 << 20;
+
+pub(super) fn verificar_tamanho(bits_base: u64, expoente: &BigInt) -> Result<(), CalcError> {
+    let expoente = expoente.to_u64().ok_or(CalcError::Overflow)?;
+    if bits_base.saturating_mul(expoente) > LIMITE_BITS {
+        return Err(CalcError::Overflow);
+    }
+    Ok(())
+}
+
+impl Numero for BigInt {
+    const MODO: Modo = Modo::Inteiro;
+
+    fn zero() -> Self {
+        Zero::zero()
+    }
+
+    fn um() -> Self {
+        One::one()
+    }
+
+    fn de_texto(literal: &str) -> Resultado<Self> {
+        let racional = racional_de_literal(literal)?;
+        Self::de_racional(&racional).ok_or(CalcError::NaoExato { operacao: "número" })
+    }
+
+    fn de_f64(valor: f64) -> Option<Self> {
+        if valor.is_finite() && valor.fract() == 0.0 {
+            BigInt::from_f64(valor)
+        } else {
+            None
+        }
+    }
+
+    fn de_racional(valor: &BigRational) -> Option<Self> {
+        if valor.is_integer() {
+            Some(valor.to_integer())
+        } else {
+            None
+        }
+    }
+
+    fn para_racional(&self) -> Option<BigRational> {
+        Some(BigRational::from_integer(self.clone()))
+    }
+
+    fn para_f64(&self) -> f64 {
+        self.to_f64().unwrap_or(f64::NAN)
+    }
+
+    fn para_valor(self) -> Valor {
+        Valor::Inteiro(self)
+    }
+
+    fn eh_inteiro(&self) -> bool {
+        true
+    }
+
+    fn somar(&self, outro: &Self) -> Resultado<Self> {
+        Ok(self + outro)
+    }
+
+    fn subtrair(&self, outro: &Self) -> Resultado<Self> {
+        Ok(self - outro)
+    }
+
+    fn multiplicar(&self, outro: &Self) -> Resultado<Self> {
+        Ok(self * outro)
+    }
+
+    // Só divide quando o resultado é inteiro; 7 / 2 pede o modo racional
+    fn dividir(&self, outro: &Self) -> Resultado<Self> {
+        let (quociente, resto) = self.div_rem(outro);
+        if resto.is_zero() {
+            Ok(quociente)
+        } else {
+            Err(CalcError::NaoExato { operacao: "divisão" })
+        }
+    }
+
+    fn resto(&self, outro: &Self) -> Resultado<Self> {
+        Ok(self % outro)
+    }
+
+    fn potencia(&self, expoente: &Self) -> Resultado<Self> {
+        if expoente.is_negative() {
+            // Só 1 e -1 têm inverso inteiro
+            return if self.is_one() || (-self).is_one() {
+                Ok(if expoente.is_even() { One::one() } else { self.clone() })
+            } else {
+                Err(CalcError::NaoExato { operacao: "potência com expoente negativo" })
+            };
+        }
+        verificar_tamanho(self.bits(), expoente)?;
+        let expoente = expoente.to_u32().ok_or(CalcError::Overflow)?;
+        Ok(num_traits::pow(self.clone(), expoente as usize))
+    }
+
+    fn raiz_quadrada(&self) -> Resultado<Self> {
+        let raiz = self.sqrt();
+        if &(&raiz * &raiz) == self {
+            Ok(raiz)
+        } else {
+            Err(CalcError::NaoExato { operacao: "raiz quadrada" })
+        }
+    }
+
+    fn unaria(funcao: &FuncaoUnaria) -> fn(Self) -> Resultado<Self> {
+        funcao.inteiro
+    }
+
+    fn binaria(funcao: &FuncaoBinaria) -> fn(Self, Self) -> Resultado<Self> {
+        funcao.inteiro
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(texto: &str) -> BigInt {
+        BigInt::de_texto(texto).unwrap()
+    }
+
+    #[test]
+    fn test_de_texto() {
+        assert_eq!(n("42"), BigInt::from(42));
+        assert_eq!(n("1e3"), BigInt::from(1000));
+        assert!(BigInt::de_texto("2.5").is_err());
+    }
+
+    #[test]
+    fn test_potencia_grande_sem_perda() {
+        let resultado = n("2").potencia(&n("100")).unwrap();
+        assert_eq!(resultado.to_string(), "1267650600228229401496703205376");
+    }
+
+    #[test]
+    fn test_potencia_limites() {
+        assert_eq!(n("-1").potencia(&n("-3")).unwrap(), n("-1"));
+        assert!(matches!(n("2").potencia(&n("-1")), Err(CalcError::NaoExato { .. })));
+        assert_eq!(n("10").potencia(&n("10000000")), Err(CalcError::Overflow));
+    }
+
+    #[test]
+    fn test_divisao_exata() {
+        assert_eq!(n("12").dividir(&n("4")).unwrap(), n("3"));
+        assert!(matches!(n("7").dividir(&n("2")), Err(CalcError::NaoExato { .. })));
+    }
+
+    #[test]
+    fn test_raiz_quadrada() {
+        assert_eq!(n("144").raiz_quadrada().unwrap(), n("12"));
+        assert!(matches!(n("2").raiz_quadrada(), Err(CalcError::NaoExato { .. })));
+    }
+}
@@ -0,0 +1,266 @@
+mod complexo;
+mod decimal;
+mod inteiro;
+mod racional;
+mod real;
+
+use std::fmt;
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use serde::{Deserialize, Serialize};
+
+use crate::erro::{CalcError, Resultado};
+
+pub use complexo::{verificar_complexo, Complexo, FormaComplexa};
+pub use decimal::Decimal;
+pub use racional::racional_de_literal;
+
+// Modos numéricos que o usuário pode alternar durante a sessão
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Modo {
+    #[default]
+    Real,
+    Inteiro,
+    Racional,
+    Decimal,
+    Complexo,
+}
+
+impl Modo {
+    pub const TODOS: [Modo; 5] = [Modo::Real, Modo::Inteiro, Modo::Racional, Modo::Decimal, Modo::Complexo];
+
+    pub fn descricao(&self) -> &'static str {
+        match self {
+            Modo::Real => "ponto flutuante f64 (padrão)",
+            Modo::Inteiro => "inteiros de precisão arbitrária",
+            Modo::Racional => "frações exatas",
+            Modo::Decimal => "decimal fixo com 20 casas",
+            Modo::Complexo => "números complexos a + bi",
+        }
+    }
+}
+
+impl fmt::Display for Modo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nome = match self {
+            Modo::Real => "real",
+            Modo::Inteiro => "inteiro",
+            Modo::Racional => "racional",
+            Modo::Decimal => "decimal",
+            Modo::Complexo => "complexo",
+        };
+        write!(f, "{}", nome)
+    }
+}
+
+impl FromStr for Modo {
+    type Err = String;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        Modo::TODOS
+            .into_iter()
+            .find(|modo| modo.to_string() == texto.trim().to_lowercase())
+            .ok_or_else(|| format!("Modo desconhecido: {}", texto))
+    }
+}
+
+// Executa `$corpo` com `$n` apontando para o tipo numérico do modo escolhido
+macro_rules! no_modo {
+    ($modo:expr, $n:ident => $corpo:expr) => {
+        match $modo {
+            $crate::numero::Modo::Real => {
+                type $n = f64;
+                $corpo
+            }
+            $crate::numero::Modo::Inteiro => {
+                type $n = ::num_bigint::BigInt;
+                $corpo
+            }
+            $crate::numero::Modo::Racional => {
+                type $n = ::num_rational::BigRational;
+                $corpo
+            }
+            $crate::numero::Modo::Decimal => {
+                type $n = $crate::numero::Decimal;
+                $corpo
+            }
+            $crate::numero::Modo::Complexo => {
+                type $n = $crate::numero::Complexo;
+                $corpo
+            }
+        }
+    };
+}
+pub(crate) use no_modo;
+
+// Uma mesma função instanciada para cada tipo numérico. O registro guarda
+// estas tabelas e cada tipo escolhe a sua instância com `Numero::unaria`.
+#[derive(Debug, Clone, Copy)]
+pub struct FuncaoUnaria {
+    pub real: fn(f64) -> Resultado<f64>,
+    pub inteiro: fn(BigInt) -> Resultado<BigInt>,
+    pub racional: fn(BigRational) -> Resultado<BigRational>,
+    pub decimal: fn(Decimal) -> Resultado<Decimal>,
+    pub complexo: fn(Complexo) -> Resultado<Complexo>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FuncaoBinaria {
+    pub real: fn(f64, f64) -> Resultado<f64>,
+    pub inteiro: fn(BigInt, BigInt) -> Resultado<BigInt>,
+    pub racional: fn(BigRational, BigRational) -> Resultado<BigRational>,
+    pub decimal: fn(Decimal, Decimal) -> Resultado<Decimal>,
+    pub complexo: fn(Complexo, Complexo) -> Resultado<Complexo>,
+}
+
+// Operações primitivas que cada representação numérica precisa oferecer.
+// As verificações comuns (divisão por zero, domínio) ficam em `operacoes`.
+pub trait Numero: Clone + PartialOrd + fmt::Display + fmt::Debug + Sized {
+    const MODO: Modo;
+
+    fn zero() -> Self;
+    fn um() -> Self;
+
+    // Literal decimal digitado pelo usuário, ex: "0.1" ou "2.5e-3"
+    fn de_texto(literal: &str) -> Resultado<Self>;
+    // Resultado de um cálculo em f64 (funções transcendentais, constantes)
+    fn de_f64(valor: f64) -> Option<Self>;
+    fn de_racional(valor: &BigRational) -> Option<Self>;
+    fn para_racional(&self) -> Option<BigRational>;
+    fn para_f64(&self) -> f64;
+    fn para_valor(self) -> Valor;
+
+    // Usadas pelo modo complexo e pela forma polar r∠θ; nos modos reais só
+    // complexos sem parte imaginária voltam para o tipo do modo
+    fn para_complexo(&self) -> Complexo {
+        Complexo::new(self.para_f64(), 0.0)
+    }
+
+    fn de_complexo(valor: Complexo) -> Option<Self> {
+        if valor.eh_real() {
+            Self::de_f64(valor.re())
+        } else {
+            None
+        }
+    }
+
+    fn eh_inteiro(&self) -> bool;
+
+    fn somar(&self, outro: &Self) -> Resultado<Self>;
+    fn subtrair(&self, outro: &Self) -> Resultado<Self>;
+    fn multiplicar(&self, outro: &Self) -> Resultado<Self>;
+    // Chamadas apenas com divisor diferente de zero
+    fn dividir(&self, outro: &Self) -> Resultado<Self>;
+    fn resto(&self, outro: &Self) -> Resultado<Self>;
+    fn potencia(&self, expoente: &Self) -> Resultado<Self>;
+    // Chamada apenas com valores não negativos
+    fn raiz_quadrada(&self) -> Resultado<Self>;
+
+    fn unaria(funcao: &FuncaoUnaria) -> fn(Self) -> Resultado<Self>;
+    fn binaria(funcao: &FuncaoBinaria) -> fn(Self, Self) -> Resultado<Self>;
+
+    // Forma curta usada nos resultados do menu
+    fn exibir(&self) -> String {
+        self.to_string()
+    }
+}
+
+// Valor guardado na sessão, independente do modo em que foi calculado
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Valor {
+    Real(f64),
+    Inteiro(BigInt),
+    Racional(BigRational),
+    Decimal(Decimal),
+    Complexo(Complexo),
+}
+
+impl Valor {
+    fn para_racional(&self) -> Option<BigRational> {
+        match self {
+            Valor::Real(x) => x.para_racional(),
+            Valor::Inteiro(x) => x.para_racional(),
+            Valor::Racional(x) => x.para_racional(),
+            Valor::Decimal(x) => x.para_racional(),
+            Valor::Complexo(x) => x.para_racional(),
+        }
+    }
+
+    // Converte para o tipo do modo atual passando por uma fração exata
+    pub fn converter<N: Numero>(&self) -> Resultado<N> {
+        if let Valor::Complexo(z) = self {
+            if !z.eh_real() {
+                return N::de_complexo(*z).ok_or(CalcError::Imaginario);
+            }
+        }
+        self.para_racional()
+            .and_then(|racional| N::de_racional(&racional))
+            .ok_or(CalcError::NaoExato { operacao: "conversão de modo" })
+    }
+}
+
+impl fmt::Display for Valor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Valor::Real(x) => write!(f, "{}", x),
+            Valor::Inteiro(x) => write!(f, "{}", x),
+            Valor::Racional(x) => write!(f, "{}", x),
+            Valor::Decimal(x) => write!(f, "{}", x),
+            Valor::Complexo(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+impl From<f64> for Valor {
+    fn from(valor: f64) -> Self {
+        Valor::Real(valor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modo_texto() {
+        for modo in Modo::TODOS {
+            assert_eq!(modo.to_string().parse::<Modo>(), Ok(modo));
+        }
+        assert_eq!("Complexo".parse::<Modo>(), Ok(Modo::Complexo));
+        assert!("quaternio".parse::<Modo>().is_err());
+    }
+
+    #[test]
+    fn test_converter_entre_modos() {
+        let decimal: Decimal = Valor::Real(0.1).converter().unwrap();
+        assert_eq!(decimal.to_string(), "0.1");
+
+        let racional: BigRational = Valor::Real(0.25).converter().unwrap();
+        assert_eq!(racional.to_string(), "1/4");
+
+        let inteiro: BigInt = Valor::Racional(BigRational::from_integer(BigInt::from(7))).converter().unwrap();
+        assert_eq!(inteiro, BigInt::from(7));
+
+        assert_eq!(
+            Valor::Real(2.5).converter::<BigInt>(),
+            Err(CalcError::NaoExato { operacao: "conversão de modo" })
+        );
+
+        let complexo: Complexo = Valor::Real(0.5).converter().unwrap();
+        assert_eq!(complexo, Complexo::new(0.5, 0.0));
+        let real: f64 = Valor::Complexo(Complexo::new(3.0, 0.0)).converter().unwrap();
+        assert_eq!(real, 3.0);
+        assert_eq!(Valor::Complexo(Complexo::I).converter::<f64>(), Err(CalcError::Imaginario));
+    }
+
+    #[test]
+    fn test_no_modo_escolhe_tipo() {
+        let soma = |modo| {
+            no_modo!(modo, N => N::de_texto("0.1").unwrap().somar(&N::de_texto("0.2").unwrap()).unwrap().para_valor())
+        };
+        assert_eq!(soma(Modo::Racional).to_string(), "3/10");
+        assert_eq!(soma(Modo::Real).to_string(), "0.30000000000000004");
+    }
+}
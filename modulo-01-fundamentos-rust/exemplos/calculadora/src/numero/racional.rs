@@ -0,0 +1,184 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use super::inteiro::verificar_tamanho;
+use super::{FuncaoBinaria, FuncaoUnaria, Modo, Numero, Valor};
+use crate::erro::{CalcError, Resultado};
+
+// Converte "12.5", "-3", "1e-3" ou "2.5E+4" em uma fração exata. Texto que
+// não é um número dá o mesmo erro de literal inválido do avaliador.
+pub fn racional_de_literal(texto: &str) -> Resultado<BigRational> {
+    let invalido = CalcError::NaoExato { operacao: "número" };
+    let texto = texto.trim();
+    let (mantissa, expoente) = match texto.find(['e', 'E']) {
+        Some(i) => (&texto[..i], texto[i + 1..].parse::<i32>().map_err(|_| invalido.clone())?),
+        None => (texto, 0),
+    };
+
+    let (negativo, mantissa) = match mantissa.strip_prefix('-') {
+        Some(resto) => (true, resto),
+        None => (false, mantissa.strip_prefix('+').unwrap_or(mantissa)),
+    };
+    let (parte_inteira, parte_fracionaria) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    let digitos = format!("{}{}", parte_inteira, parte_fracionaria);
+    if digitos.is_empty() || !digitos.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalido);
+    }
+
+    let mut numerador: BigInt = digitos.parse().map_err(|_| invalido)?;
+    if negativo {
+        numerador = -numerador;
+    }
+    let escala = i32::try_from(parte_fracionaria.len())
+        .ok()
+        .and_then(|casas| expoente.checked_sub(casas))
+        .ok_or(CalcError::Overflow)?;
+    // "1e999999999" montaria uma potência de dez com quase um bilhão de dígitos
+    let dez = BigInt::from(10);
+    verificar_tamanho(dez.bits(), &BigInt::from(escala.unsigned_abs()))?;
+    let potencia_dez = dez.pow(escala.unsigned_abs());
+
+    Ok(if escala >= 0 {
+        BigRational::from_integer(numerador * potencia_dez)
+    } else {
+        BigRational::new(numerador, potencia_dez)
+    })
+}
+
+impl Numero for BigRational {
+    const MODO: Modo = Modo::Racional;
+
+    fn zero() -> Self {
+        Zero::zero()
+    }
+
+    fn um() -> Self {
+        One::one()
+    }
+
+    fn de_texto(literal: &str) -> Resultado<Self> {
+        racional_de_literal(literal)
+    }
+
+    // Só aceita resultados inteiros: o modo racional não inventa frações
+    // a partir de aproximações em ponto flutuante
+    fn de_f64(valor: f64) -> Option<Self> {
+        if valor.is_finite() && valor.fract() == 0.0 {
+            BigRational::from_float(valor)
+        } else {
+            None
+        }
+    }
+
+    fn de_racional(valor: &BigRational) -> Option<Self> {
+        Some(valor.clone())
+    }
+
+    fn para_racional(&self) -> Option<BigRational> {
+        Some(self.clone())
+    }
+
+    fn para_f64(&self) -> f64 {
+        self.to_f64().unwrap_or(f64::NAN)
+    }
+
+    fn para_valor(self) -> Valor {
+        Valor::Racional(self)
+    }
+
+    fn eh_inteiro(&self) -> bool {
+        self.is_integer()
+    }
+
+    fn somar(&self, outro: &Self) -> Resultado<Self> {
+        Ok(self + outro)
+    }
+
+    fn subtrair(&self, outro: &Self) -> Resultado<Self> {
+        Ok(self - outro)
+    }
+
+    fn multiplicar(&self, outro: &Self) -> Resultado<Self> {
+        Ok(self * outro)
+    }
+
+    fn dividir(&self, outro: &Self) -> Resultado<Self> {
+        Ok(self / outro)
+    }
+
+    fn resto(&self, outro: &Self) -> Resultado<Self> {
+        Ok(self % outro)
+    }
+
+    fn potencia(&self, expoente: &Self) -> Resultado<Self> {
+        if !expoente.is_integer() {
+            return Err(CalcError::NaoExato { operacao: "potência com expoente fracionário" });
+        }
+        let expoente = expoente.to_integer();
+        let bits = self.numer().bits() + self.denom().bits();
+        verificar_tamanho(bits, &expoente.abs())?;
+        let expoente = expoente.to_i32().ok_or(CalcError::Overflow)?;
+        Ok(self.pow(expoente))
+    }
+
+    fn raiz_quadrada(&self) -> Resultado<Self> {
+        let numerador = self.numer().sqrt();
+        let denominador = self.denom().sqrt();
+        let raiz = BigRational::new(numerador, denominador);
+        if &(&raiz * &raiz) == self {
+            Ok(raiz)
+        } else {
+            Err(CalcError::NaoExato { operacao: "raiz quadrada" })
+        }
+    }
+
+    fn unaria(funcao: &FuncaoUnaria) -> fn(Self) -> Resultado<Self> {
+        funcao.racional
+    }
+
+    fn binaria(funcao: &FuncaoBinaria) -> fn(Self, Self) -> Resultado<Self> {
+        funcao.racional
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(texto: &str) -> BigRational {
+        racional_de_literal(texto).unwrap()
+    }
+
+    #[test]
+    fn test_racional_de_literal() {
+        assert_eq!(r("0.1").to_string(), "1/10");
+        assert_eq!(r("-2.50").to_string(), "-5/2");
+        assert_eq!(r("1e3").to_string(), "1000");
+        assert_eq!(r("1.5E-2").to_string(), "3/200");
+        assert!(racional_de_literal("1.2.3").is_err());
+        assert!(racional_de_literal("abc").is_err());
+        // Expoente gigante: erro em vez de travar montando a potência de dez
+        assert_eq!(racional_de_literal("1e999999999"), Err(CalcError::Overflow));
+        assert_eq!(racional_de_literal("1e-999999999"), Err(CalcError::Overflow));
+    }
+
+    #[test]
+    fn test_soma_exata() {
+        assert_eq!(r("0.1").somar(&r("0.2")).unwrap(), r("0.3"));
+    }
+
+    #[test]
+    fn test_potencia_inteira() {
+        assert_eq!(r("1.5").potencia(&r("2")).unwrap().to_string(), "9/4");
+        assert_eq!(r("2").potencia(&r("-2")).unwrap().to_string(), "1/4");
+        assert!(matches!(r("2").potencia(&r("0.5")), Err(CalcError::NaoExato { .. })));
+    }
+
+    #[test]
+    fn test_raiz_quadrada_exata() {
+        assert_eq!(r("2.25").raiz_quadrada().unwrap().to_string(), "3/2");
+        assert!(matches!(r("2").raiz_quadrada(), Err(CalcError::NaoExato { .. })));
+    }
+}
@@ -0,0 +1,95 @@
+use num_rational::BigRational;
+use num_traits::ToPrimitive;
+
+use super::{racional_de_literal, FuncaoBinaria, FuncaoUnaria, Modo, Numero, Valor};
+use crate::erro::{verificar_finito, CalcError, Resultado};
+
+impl Numero for f64 {
+    const MODO: Modo = Modo::Real;
+
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn um() -> Self {
+        1.0
+    }
+
+    fn de_texto(literal: &str) -> Resultado<Self> {
+        match literal.parse::<f64>() {
+            Ok(x) if x.is_finite() => Ok(x),
+            // 1e400 passa do maior f64 e vira infinito; "inf" digitado não é overflow
+            Ok(x) if x.is_infinite() && literal.bytes().any(|c| c.is_ascii_digit()) => Err(CalcError::Overflow),
+            _ => Err(CalcError::NaoExato { operacao: "número" }),
+        }
+    }
+
+    fn de_f64(valor: f64) -> Option<Self> {
+        Some(valor).filter(|x| x.is_finite())
+    }
+
+    fn de_racional(valor: &BigRational) -> Option<Self> {
+        valor.to_f64().filter(|x| x.is_finite())
+    }
+
+    fn para_racional(&self) -> Option<BigRational> {
+        // A menor representação decimal de um f64 volta exatamente ao mesmo f64
+        if self.is_finite() {
+            racional_de_literal(&format!("{:e}", self)).ok()
+        } else {
+            None
+        }
+    }
+
+    fn para_f64(&self) -> f64 {
+        *self
+    }
+
+    fn para_valor(self) -> Valor {
+        Valor::Real(self)
+    }
+
+    fn eh_inteiro(&self) -> bool {
+        self.fract() == 0.0
+    }
+
+    fn somar(&self, outro: &Self) -> Resultado<Self> {
+        verificar_finito(self + outro, &[*self, *outro])
+    }
+
+    fn subtrair(&self, outro: &Self) -> Resultado<Self> {
+        verificar_finito(self - outro, &[*self, *outro])
+    }
+
+    fn multiplicar(&self, outro: &Self) -> Resultado<Self> {
+        verificar_finito(self * outro, &[*self, *outro])
+    }
+
+    fn dividir(&self, outro: &Self) -> Resultado<Self> {
+        verificar_finito(self / outro, &[*self, *outro])
+    }
+
+    fn resto(&self, outro: &Self) -> Resultado<Self> {
+        verificar_finito(self % outro, &[*self, *outro])
+    }
+
+    fn potencia(&self, expoente: &Self) -> Resultado<Self> {
+        verificar_finito(self.powf(*expoente), &[*self, *expoente])
+    }
+
+    fn raiz_quadrada(&self) -> Resultado<Self> {
+        verificar_finito(self.sqrt(), &[*self])
+    }
+
+    fn unaria(funcao: &FuncaoUnaria) -> fn(Self) -> Resultado<Self> {
+        funcao.real
+    }
+
+    fn binaria(funcao: &FuncaoBinaria) -> fn(Self, Self) -> Resultado<Self> {
+        funcao.real
+    }
+
+    fn exibir(&self) -> String {
+        format!("{:.2}", self)
+    }
+}
@@ -1,128 +1,407 @@
-pub fn somar(a: f64, b: f64) -> f64 {
-    a + b
+use std::f64::consts::FRAC_PI_2;
+
+use crate::erro::{verificar_finito, CalcError, Resultado};
+use crate::numero::Numero;
+
+// As operações são genéricas sobre `Numero`: a mesma função atende f64,
+// inteiros grandes, frações exatas e decimais de precisão fixa.
+
+pub fn somar<N: Numero>(a: N, b: N) -> Resultado<N> {
+    a.somar(&b)
 }
 
-pub fn subtrair(a: f64, b: f64) -> f64 {
-    a - b
+pub fn subtrair<N: Numero>(a: N, b: N) -> Resultado<N> {
+    a.subtrair(&b)
 }
 
-pub fn multiplicar(a: f64, b: f64) -> f64 {
-    a * b
+pub fn multiplicar<N: Numero>(a: N, b: N) -> Resultado<N> {
+    a.multiplicar(&b)
 }
 
-pub fn dividir(a: f64, b: f64) -> f64 {
-    if b != 0.0 {
-        a / b
-    } else {
-        println!("Erro: Divisão por zero!");
-        0.0
+pub fn dividir<N: Numero>(a: N, b: N) -> Resultado<N> {
+    if b == N::zero() {
+        return Err(CalcError::DivisaoPorZero);
+    }
+    a.dividir(&b)
+}
+
+pub fn potencia<N: Numero>(base: N, expoente: N) -> Resultado<N> {
+    if base == N::zero() && expoente < N::zero() {
+        return Err(CalcError::DivisaoPorZero);
+    }
+    if base < N::zero() && !expoente.eh_inteiro() {
+        return Err(CalcError::Dominio {
+            operacao: "potência com expoente fracionário",
+            valor: base.para_f64(),
+        });
     }
+    base.potencia(&expoente)
 }
 
-pub fn potencia(base: f64, expoente: f64) -> f64 {
-    base.powf(expoente)
+pub fn raiz_quadrada<N: Numero>(numero: N) -> Resultado<N> {
+    if numero < N::zero() {
+        return Err(CalcError::Dominio { operacao: "raiz quadrada", valor: numero.para_f64() });
+    }
+    numero.raiz_quadrada()
 }
 
-pub fn raiz_quadrada(numero: f64) -> f64 {
-    if numero >= 0.0 {
-        numero.sqrt()
-    } else {
-        println!("Erro: Raiz quadrada de número negativo!");
-        0.0
+pub fn modulo<N: Numero>(a: N, b: N) -> Resultado<N> {
+    if b == N::zero() {
+        return Err(CalcError::DivisaoPorZero);
     }
+    a.resto(&b)
 }
 
-pub fn modulo(a: f64, b: f64) -> f64 {
-    if b != 0.0 {
-        a % b
-    } else {
-        println!("Erro: Módulo por zero!");
-        0.0
+// Maior fatorial aceito; acima disso o resultado seria grande demais até
+// para os inteiros de precisão arbitrária
+const MAIOR_FATORIAL: u32 = 10_000;
+
+pub fn fatorial<N: Numero>(numero: N) -> Resultado<N> {
+    if numero < N::zero() || !numero.eh_inteiro() {
+        return Err(CalcError::Dominio { operacao: "fatorial", valor: numero.para_f64() });
     }
+    if numero.para_f64() > MAIOR_FATORIAL as f64 {
+        return Err(CalcError::Overflow);
+    }
+
+    let mut resultado = N::um();
+    let mut fator = N::um();
+    while fator <= numero {
+        resultado = resultado.multiplicar(&fator)?;
+        fator = fator.somar(&N::um())?;
+    }
+    Ok(resultado)
+}
+
+// Funções transcendentais não têm forma exata: calculam em f64 e cada modo
+// decide se aceita o resultado (o modo racional só aceita valores inteiros)
+fn via_f64<N: Numero>(operacao: &'static str, numero: &N, f: fn(f64) -> f64) -> Resultado<N> {
+    let x = numero.para_f64();
+    let resultado = verificar_finito(f(x), &[x])?;
+    N::de_f64(resultado).ok_or(CalcError::NaoExato { operacao })
+}
+
+pub fn logaritmo<N: Numero>(numero: N) -> Resultado<N> {
+    if numero <= N::zero() {
+        return Err(CalcError::Dominio { operacao: "logaritmo", valor: numero.para_f64() });
+    }
+    via_f64("logaritmo", &numero, f64::ln)
 }
 
-pub fn logaritmo(numero: f64) -> f64 {
-    if numero > 0.0 {
-        numero.ln()
+// Índice do múltiplo de π/2 mais próximo quando o ângulo (em radianos) cai
+// exatamente sobre um deles. Assim sin(180°) dá 0 e não 1.2e-16, e tan(90°)
+// é reconhecida como assíntota em vez de virar um número enorme.
+fn multiplo_de_meio_pi(radianos: f64) -> Option<i64> {
+    let quartos = radianos / FRAC_PI_2;
+    let mais_proximo = quartos.round();
+    let tolerancia = 8.0 * f64::EPSILON * quartos.abs();
+    if mais_proximo != 0.0 && (quartos - mais_proximo).abs() <= tolerancia {
+        Some(mais_proximo as i64)
     } else {
-        println!("Erro: Logaritmo de número não positivo!");
-        0.0
+        None
+    }
+}
+
+// Ângulos em radianos; a unidade escolhida na sessão é convertida pelo registro
+pub fn seno<N: Numero>(angulo: N) -> Resultado<N> {
+    via_f64("seno", &angulo, |x| match multiplo_de_meio_pi(x).map(|k| k.rem_euclid(4)) {
+        Some(0) | Some(2) => 0.0,
+        Some(1) => 1.0,
+        Some(_) => -1.0,
+        None => x.sin(),
+    })
+}
+
+pub fn cosseno<N: Numero>(angulo: N) -> Resultado<N> {
+    via_f64("cosseno", &angulo, |x| match multiplo_de_meio_pi(x).map(|k| k.rem_euclid(4)) {
+        Some(1) | Some(3) => 0.0,
+        Some(0) => 1.0,
+        Some(_) => -1.0,
+        None => x.cos(),
+    })
+}
+
+pub fn tangente<N: Numero>(angulo: N) -> Resultado<N> {
+    match multiplo_de_meio_pi(angulo.para_f64()) {
+        Some(k) if k % 2 != 0 => Err(CalcError::Assintota { operacao: "tangente" }),
+        Some(_) => Ok(N::zero()),
+        None => via_f64("tangente", &angulo, f64::tan),
+    }
+}
+
+// Inversas: o resultado é um ângulo em radianos
+pub fn arco_seno<N: Numero>(numero: N) -> Resultado<N> {
+    if numero.para_f64().abs() > 1.0 {
+        return Err(CalcError::Dominio { operacao: "arco seno", valor: numero.para_f64() });
+    }
+    via_f64("arco seno", &numero, f64::asin)
+}
+
+pub fn arco_cosseno<N: Numero>(numero: N) -> Resultado<N> {
+    if numero.para_f64().abs() > 1.0 {
+        return Err(CalcError::Dominio { operacao: "arco cosseno", valor: numero.para_f64() });
+    }
+    via_f64("arco cosseno", &numero, f64::acos)
+}
+
+pub fn arco_tangente<N: Numero>(numero: N) -> Resultado<N> {
+    via_f64("arco tangente", &numero, f64::atan)
+}
+
+// Ângulo do ponto (x, y) em relação ao eixo x, considerando o quadrante
+pub fn arco_tangente2<N: Numero>(y: N, x: N) -> Resultado<N> {
+    if y == N::zero() && x == N::zero() {
+        return Err(CalcError::Dominio { operacao: "atan2", valor: 0.0 });
+    }
+    let (y, x) = (y.para_f64(), x.para_f64());
+    let resultado = verificar_finito(y.atan2(x), &[y, x])?;
+    N::de_f64(resultado).ok_or(CalcError::NaoExato { operacao: "atan2" })
+}
+
+pub fn seno_hiperbolico<N: Numero>(numero: N) -> Resultado<N> {
+    via_f64("seno hiperbólico", &numero, f64::sinh)
+}
+
+pub fn cosseno_hiperbolico<N: Numero>(numero: N) -> Resultado<N> {
+    via_f64("cosseno hiperbólico", &numero, f64::cosh)
+}
+
+pub fn tangente_hiperbolica<N: Numero>(numero: N) -> Resultado<N> {
+    via_f64("tangente hiperbólica", &numero, f64::tanh)
+}
+
+pub fn arco_seno_hiperbolico<N: Numero>(numero: N) -> Resultado<N> {
+    via_f64("arco seno hiperbólico", &numero, f64::asinh)
+}
+
+pub fn arco_cosseno_hiperbolico<N: Numero>(numero: N) -> Resultado<N> {
+    if numero < N::um() {
+        return Err(CalcError::Dominio { operacao: "arco cosseno hiperbólico", valor: numero.para_f64() });
+    }
+    via_f64("arco cosseno hiperbólico", &numero, f64::acosh)
+}
+
+pub fn arco_tangente_hiperbolica<N: Numero>(numero: N) -> Resultado<N> {
+    let x = numero.para_f64();
+    if x.abs() == 1.0 {
+        return Err(CalcError::Assintota { operacao: "arco tangente hiperbólica" });
+    }
+    if x.abs() > 1.0 {
+        return Err(CalcError::Dominio { operacao: "arco tangente hiperbólica", valor: x });
+    }
+    via_f64("arco tangente hiperbólica", &numero, f64::atanh)
+}
+
+// Funções do conjunto complexo (abs, arg, conj, re, im) nos modos reais
+pub fn valor_absoluto<N: Numero>(numero: N) -> Resultado<N> {
+    if numero < N::zero() {
+        return N::zero().subtrair(&numero);
+    }
+    Ok(numero)
+}
+
+// Ângulo em radianos: 0 para positivos, π para negativos
+pub fn argumento<N: Numero>(numero: N) -> Resultado<N> {
+    if numero == N::zero() {
+        return Err(CalcError::Dominio { operacao: "argumento", valor: 0.0 });
     }
+    via_f64("argumento", &numero, |x| if x > 0.0 { 0.0 } else { std::f64::consts::PI })
 }
 
-pub fn seno(angulo_graus: f64) -> f64 {
-    let angulo_radianos = angulo_graus.to_radians();
-    angulo_radianos.sin()
+pub fn conjugado<N: Numero>(numero: N) -> Resultado<N> {
+    Ok(numero)
 }
 
-pub fn cosseno(angulo_graus: f64) -> f64 {
-    let angulo_radianos = angulo_graus.to_radians();
-    angulo_radianos.cos()
+pub fn parte_real<N: Numero>(numero: N) -> Resultado<N> {
+    Ok(numero)
 }
 
-pub fn tangente(angulo_graus: f64) -> f64 {
-    let angulo_radianos = angulo_graus.to_radians();
-    angulo_radianos.tan()
+pub fn parte_imaginaria<N: Numero>(_numero: N) -> Resultado<N> {
+    Ok(N::zero())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use num_bigint::BigInt;
+    use num_rational::BigRational;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    // Compara resultados em f64 com tolerância relativa em vez de igualdade exata
+    #[track_caller]
+    fn assert_aprox(resultado: Resultado, esperado: f64) {
+        match resultado {
+            Ok(valor) => assert!(
+                (valor - esperado).abs() <= 1e-12 * esperado.abs().max(1.0),
+                "{} não é aproximadamente {}",
+                valor,
+                esperado
+            ),
+            Err(erro) => panic!("esperado {}, mas deu erro: {}", esperado, erro),
+        }
+    }
 
     #[test]
     fn test_somar() {
-        assert_eq!(somar(2.0, 3.0), 5.0);
-        assert_eq!(somar(-1.0, 1.0), 0.0);
-        assert_eq!(somar(0.0, 0.0), 0.0);
+        assert_aprox(somar(2.0, 3.0), 5.0);
+        assert_aprox(somar(-1.0, 1.0), 0.0);
+        assert_aprox(somar(0.0, 0.0), 0.0);
     }
 
     #[test]
     fn test_subtrair() {
-        assert_eq!(subtrair(5.0, 3.0), 2.0);
-        assert_eq!(subtrair(1.0, 1.0), 0.0);
-        assert_eq!(subtrair(0.0, 5.0), -5.0);
+        assert_aprox(subtrair(5.0, 3.0), 2.0);
+        assert_aprox(subtrair(1.0, 1.0), 0.0);
+        assert_aprox(subtrair(0.0, 5.0), -5.0);
     }
 
     #[test]
     fn test_multiplicar() {
-        assert_eq!(multiplicar(4.0, 5.0), 20.0);
-        assert_eq!(multiplicar(-2.0, 3.0), -6.0);
-        assert_eq!(multiplicar(0.0, 100.0), 0.0);
+        assert_aprox(multiplicar(4.0, 5.0), 20.0);
+        assert_aprox(multiplicar(-2.0, 3.0), -6.0);
+        assert_aprox(multiplicar(0.0, 100.0), 0.0);
     }
 
     #[test]
     fn test_dividir() {
-        assert_eq!(dividir(10.0, 2.0), 5.0);
-        assert_eq!(dividir(15.0, 3.0), 5.0);
-        assert_eq!(dividir(7.0, 0.0), 0.0); // Divisão por zero retorna 0
+        assert_aprox(dividir(10.0, 2.0), 5.0);
+        assert_aprox(dividir(15.0, 3.0), 5.0);
+        assert_eq!(dividir(7.0, 0.0), Err(CalcError::DivisaoPorZero));
     }
 
     #[test]
     fn test_potencia() {
-        assert_eq!(potencia(2.0, 3.0), 8.0);
-        assert_eq!(potencia(5.0, 2.0), 25.0);
-        assert_eq!(potencia(10.0, 0.0), 1.0);
+        assert_aprox(potencia(2.0, 3.0), 8.0);
+        assert_aprox(potencia(5.0, 2.0), 25.0);
+        assert_aprox(potencia(10.0, 0.0), 1.0);
     }
 
     #[test]
     fn test_raiz_quadrada() {
-        assert_eq!(raiz_quadrada(9.0), 3.0);
-        assert_eq!(raiz_quadrada(16.0), 4.0);
-        assert_eq!(raiz_quadrada(0.0), 0.0);
-        assert_eq!(raiz_quadrada(-1.0), 0.0); // Número negativo retorna 0
+        assert_aprox(raiz_quadrada(9.0), 3.0);
+        assert_aprox(raiz_quadrada(16.0), 4.0);
+        assert_aprox(raiz_quadrada(0.0), 0.0);
+        assert_eq!(
+            raiz_quadrada(-1.0),
+            Err(CalcError::Dominio { operacao: "raiz quadrada", valor: -1.0 })
+        );
     }
 
     #[test]
     fn test_seno() {
-        assert_eq!(seno(0.0), 0.0);
-        assert_eq!(seno(90.0), 1.0);
-        assert_eq!(seno(180.0), 0.0);
+        assert_aprox(seno(0.0), 0.0);
+        assert_aprox(seno(FRAC_PI_2), 1.0);
+        assert_aprox(seno(PI / 6.0), 0.5);
+        assert_aprox(seno(-FRAC_PI_2), -1.0);
+        // Múltiplos de π/2 dão valores exatos, sem resíduo de arredondamento
+        assert_eq!(seno(PI), Ok(0.0));
+        assert_eq!(seno(3.0 * PI), Ok(0.0));
     }
 
     #[test]
     fn test_cosseno() {
-        assert_eq!(cosseno(0.0), 1.0);
-        assert_eq!(cosseno(90.0), 0.0);
-        assert_eq!(cosseno(180.0), -1.0);
+        assert_aprox(cosseno(0.0), 1.0);
+        assert_aprox(cosseno(PI / 3.0), 0.5);
+        assert_aprox(cosseno(PI), -1.0);
+        assert_eq!(cosseno(FRAC_PI_2), Ok(0.0));
+        assert_eq!(cosseno(-3.0 * FRAC_PI_2), Ok(0.0));
+    }
+
+    #[test]
+    fn test_tangente_e_assintotas() {
+        assert_aprox(tangente(FRAC_PI_4), 1.0);
+        assert_aprox(tangente(-FRAC_PI_4), -1.0);
+        assert_eq!(tangente(PI), Ok(0.0));
+        for angulo in [FRAC_PI_2, -FRAC_PI_2, 3.0 * FRAC_PI_2, 90.0f64.to_radians(), 270.0f64.to_radians()] {
+            assert_eq!(tangente(angulo), Err(CalcError::Assintota { operacao: "tangente" }));
+        }
+        // Perto da assíntota, mas não sobre ela, ainda é um número
+        assert!(tangente(FRAC_PI_2 - 1e-6).unwrap() > 1e5);
+    }
+
+    #[test]
+    fn test_trigonometricas_inversas() {
+        assert_aprox(arco_seno(1.0), FRAC_PI_2);
+        assert_aprox(arco_cosseno(-1.0), PI);
+        assert_aprox(arco_tangente(1.0), FRAC_PI_4);
+        assert_aprox(arco_tangente2(1.0, -1.0), 3.0 * FRAC_PI_4);
+        assert_aprox(arco_tangente2(-1.0, 0.0), -FRAC_PI_2);
+        assert_eq!(arco_seno(1.5), Err(CalcError::Dominio { operacao: "arco seno", valor: 1.5 }));
+        assert!(matches!(arco_cosseno(-2.0), Err(CalcError::Dominio { .. })));
+        assert!(matches!(arco_tangente2(0.0, 0.0), Err(CalcError::Dominio { .. })));
+    }
+
+    #[test]
+    fn test_hiperbolicas() {
+        assert_aprox(seno_hiperbolico(1.0), 1.1752011936438014);
+        assert_aprox(cosseno_hiperbolico(0.0), 1.0);
+        assert_aprox(tangente_hiperbolica(0.5), 0.46211715726000974);
+        assert_aprox(arco_seno_hiperbolico(seno_hiperbolico(2.0).unwrap()), 2.0);
+        assert_aprox(arco_cosseno_hiperbolico(1.0), 0.0);
+        assert_aprox(arco_tangente_hiperbolica(0.5), 0.5493061443340549);
+        assert_eq!(cosseno_hiperbolico(1000.0), Err(CalcError::Overflow));
+        assert!(matches!(arco_cosseno_hiperbolico(0.5), Err(CalcError::Dominio { .. })));
+        assert!(matches!(arco_tangente_hiperbolica(1.0), Err(CalcError::Assintota { .. })));
+        assert!(matches!(arco_tangente_hiperbolica(-3.0), Err(CalcError::Dominio { .. })));
+    }
+
+    #[test]
+    fn test_modulo() {
+        assert_aprox(modulo(7.0, 4.0), 3.0);
+        assert_eq!(modulo(7.0, 0.0), Err(CalcError::DivisaoPorZero));
+    }
+
+    #[test]
+    fn test_logaritmo() {
+        assert_aprox(logaritmo(1.0), 0.0);
+        assert_eq!(logaritmo(0.0), Err(CalcError::Dominio { operacao: "logaritmo", valor: 0.0 }));
+    }
+
+    #[test]
+    fn test_overflow_e_nao_finito() {
+        assert_eq!(multiplicar(f64::MAX, 2.0), Err(CalcError::Overflow));
+        assert_eq!(potencia(10.0, 400.0), Err(CalcError::Overflow));
+        assert_eq!(somar(f64::INFINITY, 1.0), Err(CalcError::NaoFinito));
+        assert_eq!(somar(f64::NAN, 1.0), Err(CalcError::NaoFinito));
+    }
+
+    #[test]
+    fn test_fatorial() {
+        assert_aprox(fatorial(5.0), 120.0);
+        assert_aprox(fatorial(0.0), 1.0);
+        assert_eq!(fatorial(171.0), Err(CalcError::Overflow));
+        assert!(matches!(fatorial(-1.0), Err(CalcError::Dominio { .. })));
+        assert!(matches!(fatorial(2.5), Err(CalcError::Dominio { .. })));
+    }
+
+    #[test]
+    fn test_fatorial_inteiro_grande() {
+        let resultado = fatorial(BigInt::from(25)).unwrap();
+        assert_eq!(resultado.to_string(), "15511210043330985984000000");
+    }
+
+    #[test]
+    fn test_operacoes_em_modo_racional() {
+        let decimo = BigRational::new(BigInt::from(1), BigInt::from(10));
+        let soma = somar(decimo.clone(), decimo.clone() * BigInt::from(2)).unwrap();
+        assert_eq!(soma.to_string(), "3/10");
+        assert_eq!(dividir(decimo.clone(), BigRational::from_integer(BigInt::from(0))), Err(CalcError::DivisaoPorZero));
+        assert!(matches!(seno(decimo), Err(CalcError::NaoExato { .. })));
+    }
+
+    #[test]
+    fn test_potencia_fora_do_dominio() {
+        assert_eq!(potencia(0.0, -1.0), Err(CalcError::DivisaoPorZero));
+        assert!(matches!(potencia(-8.0, 0.5), Err(CalcError::Dominio { .. })));
+    }
+
+    #[test]
+    fn test_conjunto_complexo_em_modo_real() {
+        assert_eq!(valor_absoluto(BigInt::from(-7)), Ok(BigInt::from(7)));
+        assert_eq!(argumento(-2.0), Ok(PI));
+        assert_eq!(argumento(2.0), Ok(0.0));
+        assert!(matches!(argumento(0.0), Err(CalcError::Dominio { .. })));
+        assert_eq!(conjugado(1.5), Ok(1.5));
+        assert_eq!(parte_imaginaria(1.5), Ok(0.0));
     }
 }
@@ -0,0 +1,269 @@
+use num_complex::Complex64;
+
+use crate::angulo::UnidadeAngulo;
+use crate::erro::{CalcError, Resultado};
+use crate::numero::{verificar_complexo, Complexo, Numero};
+use crate::operacoes;
+
+// Versões complexas das funções de `operacoes`, com os mesmos nomes, para o
+// registro instanciar com `unaria!`/`binaria!`. Argumentos reais dentro do
+// domínio real passam pela versão real: sin(180°) continua exatamente 0 e as
+// assíntotas continuam sendo reconhecidas.
+
+fn real_ou_complexa(
+    z: Complexo,
+    dominio_real: fn(f64) -> bool,
+    real: fn(f64) -> Resultado<f64>,
+    complexa: fn(Complex64) -> Complex64,
+) -> Resultado<Complexo> {
+    if z.eh_real() && dominio_real(z.re()) {
+        return real(z.re()).map(|x| Complexo::new(x, 0.0));
+    }
+    verificar_complexo(complexa(z.0), &[z]).map(Complexo::limpar)
+}
+
+fn qualquer(_: f64) -> bool {
+    true
+}
+
+// Funções sem extensão útil aos complexos: fatorial, resto, atan2
+fn so_real(operacao: &'static str, z: Complexo, real: fn(f64) -> Resultado<f64>) -> Resultado<Complexo> {
+    if !z.eh_real() {
+        return Err(CalcError::SoReal { operacao });
+    }
+    real(z.re()).map(|x| Complexo::new(x, 0.0))
+}
+
+pub fn somar(a: Complexo, b: Complexo) -> Resultado<Complexo> {
+    operacoes::somar(a, b)
+}
+
+pub fn subtrair(a: Complexo, b: Complexo) -> Resultado<Complexo> {
+    operacoes::subtrair(a, b)
+}
+
+pub fn multiplicar(a: Complexo, b: Complexo) -> Resultado<Complexo> {
+    operacoes::multiplicar(a, b)
+}
+
+pub fn dividir(a: Complexo, b: Complexo) -> Resultado<Complexo> {
+    operacoes::dividir(a, b)
+}
+
+// Base negativa com expoente fracionário deixa de ser erro: (-8)^(1/3) é o
+// valor principal 1 + 1.732i
+pub fn potencia(base: Complexo, expoente: Complexo) -> Resultado<Complexo> {
+    if base == Complexo::zero() {
+        return match expoente {
+            e if e == Complexo::zero() => Ok(Complexo::um()),
+            e if e.eh_real() && e.re() > 0.0 => Ok(Complexo::zero()),
+            e if e.eh_real() => Err(CalcError::DivisaoPorZero),
+            _ => Err(CalcError::Dominio { operacao: "potência de zero com expoente complexo", valor: 0.0 }),
+        };
+    }
+    base.potencia(&expoente)
+}
+
+pub fn raiz_quadrada(numero: Complexo) -> Resultado<Complexo> {
+    numero.raiz_quadrada()
+}
+
+pub fn fatorial(numero: Complexo) -> Resultado<Complexo> {
+    so_real("fatorial", numero, operacoes::fatorial)
+}
+
+pub fn modulo(a: Complexo, b: Complexo) -> Resultado<Complexo> {
+    if !a.eh_real() || !b.eh_real() {
+        return Err(CalcError::SoReal { operacao: "módulo" });
+    }
+    operacoes::modulo(a.re(), b.re()).map(|x| Complexo::new(x, 0.0))
+}
+
+// Valor principal: ln(-1) = iπ
+pub fn logaritmo(numero: Complexo) -> Resultado<Complexo> {
+    if numero == Complexo::zero() {
+        return Err(CalcError::Dominio { operacao: "logaritmo", valor: 0.0 });
+    }
+    real_ou_complexa(numero, |x| x > 0.0, operacoes::logaritmo, Complex64::ln)
+}
+
+pub fn seno(angulo: Complexo) -> Resultado<Complexo> {
+    real_ou_complexa(angulo, qualquer, operacoes::seno, Complex64::sin)
+}
+
+pub fn cosseno(angulo: Complexo) -> Resultado<Complexo> {
+    real_ou_complexa(angulo, qualquer, operacoes::cosseno, Complex64::cos)
+}
+
+pub fn tangente(angulo: Complexo) -> Resultado<Complexo> {
+    real_ou_complexa(angulo, qualquer, operacoes::tangente, Complex64::tan)
+}
+
+// Fora de [-1, 1] o arco seno e o arco cosseno têm valor complexo: asin(2)
+pub fn arco_seno(numero: Complexo) -> Resultado<Complexo> {
+    real_ou_complexa(numero, |x| x.abs() <= 1.0, operacoes::arco_seno, Complex64::asin)
+}
+
+pub fn arco_cosseno(numero: Complexo) -> Resultado<Complexo> {
+    real_ou_complexa(numero, |x| x.abs() <= 1.0, operacoes::arco_cosseno, Complex64::acos)
+}
+
+pub fn arco_tangente(numero: Complexo) -> Resultado<Complexo> {
+    if numero == Complexo::I || numero == Complexo::new(0.0, -1.0) {
+        return Err(CalcError::Assintota { operacao: "arco tangente" });
+    }
+    real_ou_complexa(numero, qualquer, operacoes::arco_tangente, Complex64::atan)
+}
+
+pub fn arco_tangente2(y: Complexo, x: Complexo) -> Resultado<Complexo> {
+    if !y.eh_real() || !x.eh_real() {
+        return Err(CalcError::SoReal { operacao: "atan2" });
+    }
+    operacoes::arco_tangente2(y.re(), x.re()).map(|angulo| Complexo::new(angulo, 0.0))
+}
+
+pub fn seno_hiperbolico(numero: Complexo) -> Resultado<Complexo> {
+    real_ou_complexa(numero, qualquer, operacoes::seno_hiperbolico, Complex64::sinh)
+}
+
+pub fn cosseno_hiperbolico(numero: Complexo) -> Resultado<Complexo> {
+    real_ou_complexa(numero, qualquer, operacoes::cosseno_hiperbolico, Complex64::cosh)
+}
+
+pub fn tangente_hiperbolica(numero: Complexo) -> Resultado<Complexo> {
+    real_ou_complexa(numero, qualquer, operacoes::tangente_hiperbolica, Complex64::tanh)
+}
+
+pub fn arco_seno_hiperbolico(numero: Complexo) -> Resultado<Complexo> {
+    real_ou_complexa(numero, qualquer, operacoes::arco_seno_hiperbolico, Complex64::asinh)
+}
+
+pub fn arco_cosseno_hiperbolico(numero: Complexo) -> Resultado<Complexo> {
+    real_ou_complexa(numero, |x| x >= 1.0, operacoes::arco_cosseno_hiperbolico, Complex64::acosh)
+}
+
+// ±1 continuam sendo assíntotas (tratadas pela versão real)
+pub fn arco_tangente_hiperbolica(numero: Complexo) -> Resultado<Complexo> {
+    real_ou_complexa(numero, |x| x.abs() <= 1.0, operacoes::arco_tangente_hiperbolica, Complex64::atanh)
+}
+
+pub fn valor_absoluto(numero: Complexo) -> Resultado<Complexo> {
+    verificar_complexo(Complex64::new(numero.0.norm(), 0.0), &[numero])
+}
+
+pub fn argumento(numero: Complexo) -> Resultado<Complexo> {
+    if numero == Complexo::zero() {
+        return Err(CalcError::Dominio { operacao: "argumento", valor: 0.0 });
+    }
+    Ok(Complexo::new(numero.0.arg(), 0.0))
+}
+
+pub fn conjugado(numero: Complexo) -> Resultado<Complexo> {
+    Ok(Complexo(numero.0.conj()))
+}
+
+pub fn parte_real(numero: Complexo) -> Resultado<Complexo> {
+    Ok(Complexo::new(numero.re(), 0.0))
+}
+
+pub fn parte_imaginaria(numero: Complexo) -> Resultado<Complexo> {
+    Ok(Complexo::new(numero.im(), 0.0))
+}
+
+// r∠θ em qualquer modo: o módulo e o ângulo (na unidade da sessão) precisam
+// ser reais, e fora do modo complexo o resultado também
+pub fn polar<N: Numero>(modulo: N, angulo: N, unidade: UnidadeAngulo) -> Resultado<N> {
+    let (modulo, angulo) = (modulo.para_complexo(), angulo.para_complexo());
+    if !modulo.eh_real() || !angulo.eh_real() {
+        return Err(CalcError::SoReal { operacao: "forma polar" });
+    }
+    let z = Complexo::de_polar(modulo.re(), unidade.para_radianos(angulo.re()))?;
+    N::de_complexo(z).ok_or(CalcError::Imaginario)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn c(re: f64, im: f64) -> Complexo {
+        Complexo::new(re, im)
+    }
+
+    #[track_caller]
+    fn assert_aprox(resultado: Resultado<Complexo>, esperado: Complexo) {
+        match resultado {
+            Ok(z) => assert!(
+                (z.0 - esperado.0).norm() <= 1e-12 * esperado.0.norm().max(1.0),
+                "{} não é aproximadamente {}",
+                z,
+                esperado
+            ),
+            Err(erro) => panic!("esperado {}, mas deu erro: {}", esperado, erro),
+        }
+    }
+
+    #[test]
+    fn test_raiz_e_logaritmo_de_negativos() {
+        assert_eq!(raiz_quadrada(c(-4.0, 0.0)), Ok(c(0.0, 2.0)));
+        assert_eq!(raiz_quadrada(c(-1.0, 0.0)), Ok(Complexo::I));
+        assert_eq!(logaritmo(c(-1.0, 0.0)), Ok(c(0.0, PI)));
+        assert_aprox(raiz_quadrada(c(3.0, 4.0)), c(2.0, 1.0));
+        assert!(matches!(logaritmo(Complexo::zero()), Err(CalcError::Dominio { .. })));
+    }
+
+    #[test]
+    fn test_potencia() {
+        assert_aprox(potencia(c(-8.0, 0.0), c(1.0 / 3.0, 0.0)), c(1.0, 3f64.sqrt()));
+        assert_eq!(potencia(Complexo::I, c(2.0, 0.0)), Ok(c(-1.0, 0.0)));
+        assert_eq!(potencia(Complexo::zero(), c(-1.0, 0.0)), Err(CalcError::DivisaoPorZero));
+        assert_eq!(potencia(Complexo::zero(), Complexo::zero()), Ok(Complexo::um()));
+        // i^i é real: e^(-π/2)
+        assert_aprox(potencia(Complexo::I, Complexo::I), c((-PI / 2.0).exp(), 0.0));
+    }
+
+    #[test]
+    fn test_trigonometricas() {
+        assert_eq!(seno(c(PI, 0.0)), Ok(Complexo::zero()));
+        assert_aprox(seno(c(0.0, 1.0)), c(0.0, 1f64.sinh()));
+        assert_aprox(cosseno(c(0.0, 1.0)), c(1f64.cosh(), 0.0));
+        assert_eq!(tangente(c(PI / 2.0, 0.0)), Err(CalcError::Assintota { operacao: "tangente" }));
+        assert_aprox(tangente(c(1.0, 1.0)), c(0.2717525853195118, 1.0839233273386946));
+    }
+
+    #[test]
+    fn test_inversas_fora_do_dominio_real() {
+        assert_aprox(arco_seno(c(2.0, 0.0)), c(PI / 2.0, -1.3169578969248166));
+        assert_aprox(arco_cosseno_hiperbolico(c(0.0, 0.0)), c(0.0, PI / 2.0));
+        assert_eq!(arco_tangente(Complexo::I), Err(CalcError::Assintota { operacao: "arco tangente" }));
+        assert_eq!(
+            arco_tangente_hiperbolica(c(1.0, 0.0)),
+            Err(CalcError::Assintota { operacao: "arco tangente hiperbólica" })
+        );
+    }
+
+    #[test]
+    fn test_conjunto_complexo() {
+        let z = c(3.0, -4.0);
+        assert_eq!(valor_absoluto(z), Ok(c(5.0, 0.0)));
+        assert_eq!(argumento(Complexo::I), Ok(c(PI / 2.0, 0.0)));
+        assert_eq!(conjugado(z), Ok(c(3.0, 4.0)));
+        assert_eq!(parte_real(z), Ok(c(3.0, 0.0)));
+        assert_eq!(parte_imaginaria(z), Ok(c(-4.0, 0.0)));
+    }
+
+    #[test]
+    fn test_so_reais() {
+        assert_eq!(fatorial(c(5.0, 0.0)), Ok(c(120.0, 0.0)));
+        assert_eq!(fatorial(Complexo::I), Err(CalcError::SoReal { operacao: "fatorial" }));
+        assert_eq!(modulo(c(7.0, 0.0), c(1.0, 1.0)), Err(CalcError::SoReal { operacao: "módulo" }));
+    }
+
+    #[test]
+    fn test_forma_polar() {
+        assert_eq!(polar(c(2.0, 0.0), c(90.0, 0.0), UnidadeAngulo::Graus), Ok(c(0.0, 2.0)));
+        assert_eq!(polar(2.0, 180.0, UnidadeAngulo::Graus), Ok(-2.0));
+        assert_eq!(polar(2.0, 45.0, UnidadeAngulo::Graus), Err(CalcError::Imaginario));
+        assert_eq!(polar(Complexo::I, c(1.0, 0.0), UnidadeAngulo::Radianos), Err(CalcError::SoReal { operacao: "forma polar" }));
+    }
+}
@@ -0,0 +1,470 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::erro::{CalcError, Resultado};
+
+// Modo programador: inteiros de largura fixa guardados como padrão de bits
+// em um u64 (só os `bits()` menos significativos são usados). Se o padrão é
+// lido com ou sem sinal depende do `FormatoPalavra` da sessão.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Base {
+    Binaria,
+    Octal,
+    #[default]
+    Decimal,
+    Hexadecimal,
+}
+
+impl Base {
+    pub const TODAS: [Base; 4] = [Base::Binaria, Base::Octal, Base::Decimal, Base::Hexadecimal];
+
+    pub fn raiz(&self) -> u32 {
+        match self {
+            Base::Binaria => 2,
+            Base::Octal => 8,
+            Base::Decimal => 10,
+            Base::Hexadecimal => 16,
+        }
+    }
+
+    pub fn prefixo(&self) -> &'static str {
+        match self {
+            Base::Binaria => "0b",
+            Base::Octal => "0o",
+            Base::Decimal => "",
+            Base::Hexadecimal => "0x",
+        }
+    }
+}
+
+impl fmt::Display for Base {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nome = match self {
+            Base::Binaria => "bin",
+            Base::Octal => "oct",
+            Base::Decimal => "dec",
+            Base::Hexadecimal => "hex",
+        };
+        write!(f, "{}", nome)
+    }
+}
+
+impl FromStr for Base {
+    type Err = String;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        match texto.trim().to_lowercase().as_str() {
+            "bin" | "2" => Ok(Base::Binaria),
+            "oct" | "8" => Ok(Base::Octal),
+            "dec" | "10" => Ok(Base::Decimal),
+            "hex" | "16" => Ok(Base::Hexadecimal),
+            _ => Err(format!("Base desconhecida: {}", texto)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TamanhoPalavra {
+    B8,
+    B16,
+    B32,
+    #[default]
+    B64,
+}
+
+impl TamanhoPalavra {
+    pub const TODOS: [TamanhoPalavra; 4] = [TamanhoPalavra::B8, TamanhoPalavra::B16, TamanhoPalavra::B32, TamanhoPalavra::B64];
+
+    pub fn bits(&self) -> u32 {
+        match self {
+            TamanhoPalavra::B8 => 8,
+            TamanhoPalavra::B16 => 16,
+            TamanhoPalavra::B32 => 32,
+            TamanhoPalavra::B64 => 64,
+        }
+    }
+}
+
+// O que fazer quando o resultado não cabe na palavra
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Aritmetica {
+    // Descarta os bits excedentes, como `wrapping_add`
+    Circular,
+    // Reporta overflow, como `checked_add`
+    #[default]
+    Verificada,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormatoPalavra {
+    pub tamanho: TamanhoPalavra,
+    pub com_sinal: bool,
+    pub aritmetica: Aritmetica,
+    // Base usada para ler números sem prefixo e para exibir resultados
+    pub base: Base,
+}
+
+impl Default for FormatoPalavra {
+    fn default() -> Self {
+        FormatoPalavra {
+            tamanho: TamanhoPalavra::default(),
+            com_sinal: true,
+            aritmetica: Aritmetica::default(),
+            base: Base::default(),
+        }
+    }
+}
+
+impl fmt::Display for FormatoPalavra {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tipo = if self.com_sinal { "i" } else { "u" };
+        let aritmetica = match self.aritmetica {
+            Aritmetica::Circular => "circular",
+            Aritmetica::Verificada => "verificada",
+        };
+        write!(f, "{}{}, base {}, aritmética {}", tipo, self.tamanho.bits(), self.base, aritmetica)
+    }
+}
+
+impl FormatoPalavra {
+    fn bits(&self) -> u32 {
+        self.tamanho.bits()
+    }
+
+    fn mascara(&self) -> u64 {
+        u64::MAX >> (64 - self.bits())
+    }
+
+    fn minimo(&self) -> i128 {
+        if self.com_sinal {
+            -(1i128 << (self.bits() - 1))
+        } else {
+            0
+        }
+    }
+
+    fn maximo(&self) -> i128 {
+        if self.com_sinal {
+            (1i128 << (self.bits() - 1)) - 1
+        } else {
+            self.mascara() as i128
+        }
+    }
+
+    // Valor numérico representado pelo padrão de bits
+    pub fn interpretar(&self, bits: u64) -> i128 {
+        let bits = bits & self.mascara();
+        let bit_de_sinal = 1u64 << (self.bits() - 1);
+        if self.com_sinal && bits & bit_de_sinal != 0 {
+            bits as i128 - (1i128 << self.bits())
+        } else {
+            bits as i128
+        }
+    }
+
+    // Complemento de dois truncado para a largura da palavra
+    fn truncar(&self, valor: i128) -> u64 {
+        valor as u64 & self.mascara()
+    }
+
+    // `exato` é o resultado matemático (None se nem i128 comporta);
+    // `circular` é o mesmo cálculo com estouro silencioso
+    fn ajustar(&self, exato: Option<i128>, circular: i128) -> Resultado<u64> {
+        match exato {
+            Some(valor) if (self.minimo()..=self.maximo()).contains(&valor) => Ok(self.truncar(valor)),
+            _ => match self.aritmetica {
+                Aritmetica::Circular => Ok(self.truncar(circular)),
+                Aritmetica::Verificada => Err(CalcError::Overflow),
+            },
+        }
+    }
+
+    // Aceita "255", "-1", "0xFF", "0b1010_0101", "0o17" e, sem prefixo, dígitos
+    // da base atual. Literais com prefixo são padrões de bits: 0xFF em i8 é -1.
+    pub fn ler(&self, texto: &str) -> Option<u64> {
+        let texto = texto.trim().replace('_', "");
+        let (negativo, texto) = match texto.strip_prefix('-') {
+            Some(resto) => (true, resto.to_string()),
+            None => (false, texto),
+        };
+        let minusculo = texto.to_lowercase();
+        let (base, digitos) = Base::TODAS
+            .into_iter()
+            .filter(|base| *base != Base::Decimal)
+            .find_map(|base| minusculo.strip_prefix(base.prefixo()).map(|resto| (base, resto)))
+            .unwrap_or((self.base, minusculo.as_str()));
+
+        let magnitude = u128::from_str_radix(digitos, base.raiz()).ok()?;
+        if !negativo && base != Base::Decimal {
+            return (magnitude <= self.mascara() as u128).then_some(magnitude as u64);
+        }
+        let valor = i128::try_from(magnitude).ok()?;
+        let valor = if negativo { -valor } else { valor };
+        self.ajustar(Some(valor), valor).ok()
+    }
+
+    pub fn exibir(&self, bits: u64, base: Base) -> String {
+        let bits = bits & self.mascara();
+        let digitos = match base {
+            Base::Decimal => return self.interpretar(bits).to_string(),
+            Base::Binaria => agrupar(&format!("{:b}", bits), 4),
+            Base::Octal => format!("{:o}", bits),
+            Base::Hexadecimal => format!("{:X}", bits),
+        };
+        format!("{}{}", base.prefixo(), digitos)
+    }
+
+    // Resultado nas quatro bases, com a base atual primeiro
+    pub fn exibir_todas(&self, bits: u64) -> String {
+        let mut bases = vec![self.base];
+        bases.extend(Base::TODAS.into_iter().filter(|base| *base != self.base));
+        bases
+            .into_iter()
+            .map(|base| format!("{} {}", base, self.exibir(bits, base)))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+// Separa os dígitos binários em grupos a partir da direita: 1_0000_0001
+fn agrupar(digitos: &str, tamanho: usize) -> String {
+    let primeiro = match digitos.len() % tamanho {
+        0 => tamanho,
+        resto => resto,
+    };
+    let mut grupos = vec![&digitos[..primeiro.min(digitos.len())]];
+    let mut inicio = primeiro;
+    while inicio < digitos.len() {
+        grupos.push(&digitos[inicio..inicio + tamanho]);
+        inicio += tamanho;
+    }
+    grupos.join("_")
+}
+
+pub fn somar(formato: &FormatoPalavra, a: u64, b: u64) -> Resultado<u64> {
+    let (a, b) = (formato.interpretar(a), formato.interpretar(b));
+    formato.ajustar(a.checked_add(b), a.wrapping_add(b))
+}
+
+pub fn subtrair(formato: &FormatoPalavra, a: u64, b: u64) -> Resultado<u64> {
+    let (a, b) = (formato.interpretar(a), formato.interpretar(b));
+    formato.ajustar(a.checked_sub(b), a.wrapping_sub(b))
+}
+
+pub fn multiplicar(formato: &FormatoPalavra, a: u64, b: u64) -> Resultado<u64> {
+    let (a, b) = (formato.interpretar(a), formato.interpretar(b));
+    formato.ajustar(a.checked_mul(b), a.wrapping_mul(b))
+}
+
+// Divisão inteira truncada em direção a zero, como em Rust
+pub fn dividir(formato: &FormatoPalavra, a: u64, b: u64) -> Resultado<u64> {
+    let (a, b) = (formato.interpretar(a), formato.interpretar(b));
+    if b == 0 {
+        return Err(CalcError::DivisaoPorZero);
+    }
+    // Só MIN / -1 estoura, e o resultado circular é o próprio MIN
+    formato.ajustar(Some(a / b), a / b)
+}
+
+pub fn resto(formato: &FormatoPalavra, a: u64, b: u64) -> Resultado<u64> {
+    let (a, b) = (formato.interpretar(a), formato.interpretar(b));
+    if b == 0 {
+        return Err(CalcError::DivisaoPorZero);
+    }
+    formato.ajustar(Some(a % b), a % b)
+}
+
+pub fn negar(formato: &FormatoPalavra, a: u64) -> Resultado<u64> {
+    let a = formato.interpretar(a);
+    formato.ajustar(Some(-a), -a)
+}
+
+pub fn e_bit_a_bit(formato: &FormatoPalavra, a: u64, b: u64) -> Resultado<u64> {
+    Ok(a & b & formato.mascara())
+}
+
+pub fn ou_bit_a_bit(formato: &FormatoPalavra, a: u64, b: u64) -> Resultado<u64> {
+    Ok((a | b) & formato.mascara())
+}
+
+pub fn ou_exclusivo(formato: &FormatoPalavra, a: u64, b: u64) -> Resultado<u64> {
+    Ok((a ^ b) & formato.mascara())
+}
+
+pub fn nao_bit_a_bit(formato: &FormatoPalavra, a: u64) -> Resultado<u64> {
+    Ok(!a & formato.mascara())
+}
+
+// Quantidade de posições de um deslocamento. Fora de 0..bits a aritmética
+// verificada recusa; a circular usa o resto, como `wrapping_shl`.
+fn posicoes(formato: &FormatoPalavra, quantidade: u64) -> Resultado<u32> {
+    let quantidade = formato.interpretar(quantidade);
+    let bits = formato.bits() as i128;
+    if (0..bits).contains(&quantidade) {
+        return Ok(quantidade as u32);
+    }
+    match formato.aritmetica {
+        Aritmetica::Circular => Ok(quantidade.rem_euclid(bits) as u32),
+        Aritmetica::Verificada => Err(CalcError::Dominio { operacao: "deslocamento", valor: quantidade as f64 }),
+    }
+}
+
+pub fn deslocar_esquerda(formato: &FormatoPalavra, a: u64, quantidade: u64) -> Resultado<u64> {
+    let n = posicoes(formato, quantidade)?;
+    Ok((a << n) & formato.mascara())
+}
+
+// Com sinal o deslocamento é aritmético (replica o bit de sinal); sem sinal, lógico
+pub fn deslocar_direita(formato: &FormatoPalavra, a: u64, quantidade: u64) -> Resultado<u64> {
+    let n = posicoes(formato, quantidade)?;
+    Ok(formato.truncar(formato.interpretar(a) >> n))
+}
+
+pub fn rotacionar_esquerda(formato: &FormatoPalavra, a: u64, quantidade: u64) -> Resultado<u64> {
+    let bits = formato.bits();
+    let n = formato.interpretar(quantidade).rem_euclid(bits as i128) as u32;
+    let a = a & formato.mascara();
+    if n == 0 {
+        return Ok(a);
+    }
+    Ok(((a << n) | (a >> (bits - n))) & formato.mascara())
+}
+
+pub fn rotacionar_direita(formato: &FormatoPalavra, a: u64, quantidade: u64) -> Resultado<u64> {
+    let bits = formato.bits() as i128;
+    let n = formato.interpretar(quantidade).rem_euclid(bits);
+    rotacionar_esquerda(formato, a, formato.truncar(bits - n))
+}
+
+// Não altera o valor: serve para ver um número em todas as bases
+pub fn converter(formato: &FormatoPalavra, a: u64) -> Resultado<u64> {
+    Ok(a & formato.mascara())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn formato(tamanho: TamanhoPalavra, com_sinal: bool, aritmetica: Aritmetica) -> FormatoPalavra {
+        FormatoPalavra { tamanho, com_sinal, aritmetica, base: Base::Decimal }
+    }
+
+    const I8: FormatoPalavra = FormatoPalavra {
+        tamanho: TamanhoPalavra::B8,
+        com_sinal: true,
+        aritmetica: Aritmetica::Verificada,
+        base: Base::Decimal,
+    };
+
+    #[test]
+    fn test_ler_em_varias_bases() {
+        let u16 = formato(TamanhoPalavra::B16, false, Aritmetica::Verificada);
+        assert_eq!(u16.ler("255"), Some(255));
+        assert_eq!(u16.ler("0xff"), Some(255));
+        assert_eq!(u16.ler("0b1111_1111"), Some(255));
+        assert_eq!(u16.ler("0o377"), Some(255));
+        assert_eq!(u16.ler("0x1_0000"), None);
+        assert_eq!(u16.ler("-1"), None);
+        assert_eq!(u16.ler("12z"), None);
+
+        let hex = FormatoPalavra { base: Base::Hexadecimal, ..u16 };
+        assert_eq!(hex.ler("ff"), Some(255));
+        assert_eq!(hex.ler("10"), Some(16));
+    }
+
+    #[test]
+    fn test_ler_com_sinal() {
+        assert_eq!(I8.ler("-128"), Some(0x80));
+        assert_eq!(I8.ler("0xFF"), Some(0xFF));
+        assert_eq!(I8.interpretar(0xFF), -1);
+        assert_eq!(I8.ler("128"), None);
+        let circular = FormatoPalavra { aritmetica: Aritmetica::Circular, ..I8 };
+        assert_eq!(circular.ler("128"), Some(0x80));
+    }
+
+    #[test]
+    fn test_exibir() {
+        assert_eq!(I8.exibir(0xFF, Base::Decimal), "-1");
+        assert_eq!(I8.exibir(0xFF, Base::Hexadecimal), "0xFF");
+        assert_eq!(I8.exibir(0x85, Base::Binaria), "0b1000_0101");
+        assert_eq!(I8.exibir(0x05, Base::Binaria), "0b101");
+        assert_eq!(I8.exibir(8, Base::Octal), "0o10");
+        assert_eq!(I8.exibir_todas(10), "dec 10 | bin 0b1010 | oct 0o12 | hex 0xA");
+    }
+
+    #[test]
+    fn test_overflow_verificado_e_circular() {
+        assert_eq!(somar(&I8, 127, 1), Err(CalcError::Overflow));
+        assert_eq!(multiplicar(&I8, I8.ler("-128").unwrap(), I8.ler("-1").unwrap()), Err(CalcError::Overflow));
+        assert_eq!(dividir(&I8, I8.ler("-128").unwrap(), I8.ler("-1").unwrap()), Err(CalcError::Overflow));
+
+        let i8_circular = FormatoPalavra { aritmetica: Aritmetica::Circular, ..I8 };
+        assert_eq!(somar(&i8_circular, 127, 1), Ok(0x80));
+        assert_eq!(dividir(&i8_circular, 0x80, 0xFF), Ok(0x80));
+
+        let u8 = formato(TamanhoPalavra::B8, false, Aritmetica::Verificada);
+        assert_eq!(subtrair(&u8, 0, 1), Err(CalcError::Overflow));
+        let u8_circular = FormatoPalavra { aritmetica: Aritmetica::Circular, ..u8 };
+        assert_eq!(subtrair(&u8_circular, 0, 1), Ok(0xFF));
+        assert_eq!(multiplicar(&u8_circular, 16, 17), Ok(16));
+    }
+
+    #[test]
+    fn test_u64_nos_limites() {
+        let u64 = formato(TamanhoPalavra::B64, false, Aritmetica::Verificada);
+        assert_eq!(u64.ler("18446744073709551615"), Some(u64::MAX));
+        assert_eq!(somar(&u64, u64::MAX, 1), Err(CalcError::Overflow));
+        assert_eq!(multiplicar(&u64, u64::MAX, u64::MAX), Err(CalcError::Overflow));
+
+        let circular = FormatoPalavra { aritmetica: Aritmetica::Circular, ..u64 };
+        assert_eq!(multiplicar(&circular, u64::MAX, u64::MAX), Ok(1));
+        assert_eq!(
+            multiplicar(&FormatoPalavra::default(), i64::MAX as u64, 2),
+            Err(CalcError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_divisao_e_resto() {
+        assert_eq!(dividir(&I8, I8.ler("-7").unwrap(), 2), I8.ler("-3").ok_or(CalcError::Overflow));
+        assert_eq!(resto(&I8, I8.ler("-7").unwrap(), 2), I8.ler("-1").ok_or(CalcError::Overflow));
+        assert_eq!(dividir(&I8, 1, 0), Err(CalcError::DivisaoPorZero));
+        assert_eq!(negar(&I8, 0x80), Err(CalcError::Overflow));
+    }
+
+    #[test]
+    fn test_operacoes_bit_a_bit() {
+        let u8 = formato(TamanhoPalavra::B8, false, Aritmetica::Verificada);
+        assert_eq!(e_bit_a_bit(&u8, 0b1100, 0b1010), Ok(0b1000));
+        assert_eq!(ou_bit_a_bit(&u8, 0b1100, 0b1010), Ok(0b1110));
+        assert_eq!(ou_exclusivo(&u8, 0b1100, 0b1010), Ok(0b0110));
+        assert_eq!(nao_bit_a_bit(&u8, 0x0F), Ok(0xF0));
+    }
+
+    #[test]
+    fn test_deslocamentos() {
+        let u8 = formato(TamanhoPalavra::B8, false, Aritmetica::Verificada);
+        assert_eq!(deslocar_esquerda(&u8, 0x81, 1), Ok(0x02));
+        assert_eq!(deslocar_direita(&u8, 0x80, 7), Ok(0x01));
+        assert_eq!(deslocar_direita(&I8, 0x80, 7), Ok(0xFF));
+        assert!(matches!(deslocar_esquerda(&u8, 1, 8), Err(CalcError::Dominio { .. })));
+
+        let circular = FormatoPalavra { aritmetica: Aritmetica::Circular, ..u8 };
+        assert_eq!(deslocar_esquerda(&circular, 1, 9), Ok(0x02));
+    }
+
+    #[test]
+    fn test_rotacoes() {
+        let u8 = formato(TamanhoPalavra::B8, false, Aritmetica::Verificada);
+        assert_eq!(rotacionar_esquerda(&u8, 0x81, 1), Ok(0x03));
+        assert_eq!(rotacionar_direita(&u8, 0x81, 1), Ok(0xC0));
+        assert_eq!(rotacionar_esquerda(&u8, 0x81, 8), Ok(0x81));
+        let u32 = formato(TamanhoPalavra::B32, false, Aritmetica::Verificada);
+        assert_eq!(rotacionar_direita(&u32, 1, 1), Ok(0x8000_0000));
+        let u64 = formato(TamanhoPalavra::B64, false, Aritmetica::Verificada);
+        assert_eq!(rotacionar_esquerda(&u64, 1 << 63, 1), Ok(1));
+    }
+}
@@ -0,0 +1,515 @@
+use crate::angulo::UnidadeAngulo;
+use crate::erro::{CalcError, Resultado};
+use crate::numero::{Complexo, FuncaoBinaria, FuncaoUnaria, Modo, Numero};
+use crate::programador::{self, FormatoPalavra};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Funcao {
+    Unaria(FuncaoUnaria),
+    Binaria(FuncaoBinaria),
+}
+
+// Instancia uma função genérica de `operacoes` para todos os modos numéricos
+macro_rules! unaria {
+    ($funcao:ident) => {
+        Funcao::Unaria(FuncaoUnaria {
+            real: crate::operacoes::$funcao::<f64>,
+            inteiro: crate::operacoes::$funcao::<num_bigint::BigInt>,
+            racional: crate::operacoes::$funcao::<num_rational::BigRational>,
+            decimal: crate::operacoes::$funcao::<crate::numero::Decimal>,
+            complexo: crate::operacoes_complexas::$funcao,
+        })
+    };
+}
+
+macro_rules! binaria {
+    ($funcao:ident) => {
+        Funcao::Binaria(FuncaoBinaria {
+            real: crate::operacoes::$funcao::<f64>,
+            inteiro: crate::operacoes::$funcao::<num_bigint::BigInt>,
+            racional: crate::operacoes::$funcao::<num_rational::BigRational>,
+            decimal: crate::operacoes::$funcao::<crate::numero::Decimal>,
+            complexo: crate::operacoes_complexas::$funcao,
+        })
+    };
+}
+
+// Se a função recebe ou devolve um ângulo. Essas funções são calculadas em
+// f64 e radianos, e o registro converte para a unidade da sessão.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Angulo {
+    Nenhum,
+    Entrada,
+    Saida,
+}
+
+#[derive(Debug)]
+pub struct Operacao {
+    // Nome exibido no menu
+    pub nome: &'static str,
+    pub simbolo: &'static str,
+    // Nomes aceitos como função dentro de expressões, ex: sqrt(2)
+    pub identificadores: &'static [&'static str],
+    pub funcao: Funcao,
+    pub angulo: Angulo,
+}
+
+impl Operacao {
+    pub fn aridade(&self) -> usize {
+        match self.funcao {
+            Funcao::Unaria(_) => 1,
+            Funcao::Binaria(_) => 2,
+        }
+    }
+
+    // Aplica a operação a `aridade()` argumentos já no tipo do modo atual
+    pub fn aplicar<N: Numero>(&self, argumentos: Vec<N>, unidade: UnidadeAngulo) -> Resultado<N> {
+        if self.angulo == Angulo::Nenhum {
+            let mut argumentos = argumentos.into_iter();
+            let mut proximo = || argumentos.next().expect("aridade verificada por quem chama");
+            return match &self.funcao {
+                Funcao::Unaria(f) => N::unaria(f)(proximo()),
+                Funcao::Binaria(f) => {
+                    let a = proximo();
+                    N::binaria(f)(a, proximo())
+                }
+            };
+        }
+
+        if N::MODO == Modo::Complexo {
+            return self.aplicar_complexo(argumentos, unidade);
+        }
+
+        let mut valores: Vec<f64> = argumentos.iter().map(N::para_f64).collect();
+        if self.angulo == Angulo::Entrada {
+            valores = valores.into_iter().map(|x| unidade.para_radianos(x)).collect();
+        }
+        let resultado = match &self.funcao {
+            Funcao::Unaria(f) => (f.real)(valores[0])?,
+            Funcao::Binaria(f) => (f.real)(valores[0], valores[1])?,
+        };
+        let resultado = if self.angulo == Angulo::Saida {
+            unidade.de_radianos(resultado)
+        } else {
+            resultado
+        };
+        N::de_f64(resultado).ok_or(CalcError::NaoExato { operacao: self.nome })
+    }
+
+    // No modo complexo os ângulos também são complexos: a conversão de
+    // unidade escala as duas partes
+    fn aplicar_complexo<N: Numero>(&self, argumentos: Vec<N>, unidade: UnidadeAngulo) -> Resultado<N> {
+        let mut valores: Vec<Complexo> = argumentos.iter().map(N::para_complexo).collect();
+        if self.angulo == Angulo::Entrada {
+            valores = valores.into_iter().map(|z| z.mapear_partes(|x| unidade.para_radianos(x))).collect();
+        }
+        let resultado = match &self.funcao {
+            Funcao::Unaria(f) => (f.complexo)(valores[0])?,
+            Funcao::Binaria(f) => (f.complexo)(valores[0], valores[1])?,
+        };
+        let resultado = if self.angulo == Angulo::Saida {
+            resultado.mapear_partes(|x| unidade.de_radianos(x))
+        } else {
+            resultado
+        };
+        N::de_complexo(resultado).ok_or(CalcError::NaoExato { operacao: self.nome })
+    }
+}
+
+// Para adicionar uma operação à calculadora basta incluir uma entrada aqui:
+// o menu, os símbolos e as funções de expressão são gerados a partir dela.
+pub const OPERACOES: &[Operacao] = &[
+    Operacao {
+        nome: "Soma",
+        simbolo: "+",
+        identificadores: &[],
+        funcao: binaria!(somar),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Subtração",
+        simbolo: "-",
+        identificadores: &[],
+        funcao: binaria!(subtrair),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Multiplicação",
+        simbolo: "×",
+        identificadores: &[],
+        funcao: binaria!(multiplicar),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Divisão",
+        simbolo: "÷",
+        identificadores: &[],
+        funcao: binaria!(dividir),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Potenciação",
+        simbolo: "^",
+        identificadores: &["pow"],
+        funcao: binaria!(potencia),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Raiz Quadrada",
+        simbolo: "√",
+        identificadores: &["sqrt", "raiz"],
+        funcao: unaria!(raiz_quadrada),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Fatorial",
+        simbolo: "!",
+        identificadores: &["fact", "fatorial"],
+        funcao: unaria!(fatorial),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Módulo",
+        simbolo: "mod",
+        identificadores: &["mod"],
+        funcao: binaria!(modulo),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Logaritmo Natural",
+        simbolo: "ln",
+        identificadores: &["ln", "log"],
+        funcao: unaria!(logaritmo),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Seno",
+        simbolo: "sen",
+        identificadores: &["sin", "sen"],
+        funcao: unaria!(seno),
+        angulo: Angulo::Entrada,
+    },
+    Operacao {
+        nome: "Cosseno",
+        simbolo: "cos",
+        identificadores: &["cos"],
+        funcao: unaria!(cosseno),
+        angulo: Angulo::Entrada,
+    },
+    Operacao {
+        nome: "Tangente",
+        simbolo: "tan",
+        identificadores: &["tan", "tg"],
+        funcao: unaria!(tangente),
+        angulo: Angulo::Entrada,
+    },
+    Operacao {
+        nome: "Arco Seno",
+        simbolo: "asen",
+        identificadores: &["asin", "asen", "arcsen"],
+        funcao: unaria!(arco_seno),
+        angulo: Angulo::Saida,
+    },
+    Operacao {
+        nome: "Arco Cosseno",
+        simbolo: "acos",
+        identificadores: &["acos", "arccos"],
+        funcao: unaria!(arco_cosseno),
+        angulo: Angulo::Saida,
+    },
+    Operacao {
+        nome: "Arco Tangente",
+        simbolo: "atg",
+        identificadores: &["atan", "atg", "arctg"],
+        funcao: unaria!(arco_tangente),
+        angulo: Angulo::Saida,
+    },
+    Operacao {
+        nome: "Arco Tangente de y/x",
+        simbolo: "atan2",
+        identificadores: &["atan2"],
+        funcao: binaria!(arco_tangente2),
+        angulo: Angulo::Saida,
+    },
+    Operacao {
+        nome: "Seno Hiperbólico",
+        simbolo: "senh",
+        identificadores: &["sinh", "senh"],
+        funcao: unaria!(seno_hiperbolico),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Cosseno Hiperbólico",
+        simbolo: "cosh",
+        identificadores: &["cosh"],
+        funcao: unaria!(cosseno_hiperbolico),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Tangente Hiperbólica",
+        simbolo: "tgh",
+        identificadores: &["tanh", "tgh"],
+        funcao: unaria!(tangente_hiperbolica),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Arco Seno Hiperbólico",
+        simbolo: "asenh",
+        identificadores: &["asinh", "asenh"],
+        funcao: unaria!(arco_seno_hiperbolico),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Arco Cosseno Hiperbólico",
+        simbolo: "acosh",
+        identificadores: &["acosh"],
+        funcao: unaria!(arco_cosseno_hiperbolico),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Arco Tangente Hiperbólica",
+        simbolo: "atgh",
+        identificadores: &["atanh", "atgh"],
+        funcao: unaria!(arco_tangente_hiperbolica),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Valor Absoluto",
+        simbolo: "abs",
+        identificadores: &["abs"],
+        funcao: unaria!(valor_absoluto),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Argumento",
+        simbolo: "arg",
+        identificadores: &["arg"],
+        funcao: unaria!(argumento),
+        angulo: Angulo::Saida,
+    },
+    Operacao {
+        nome: "Conjugado",
+        simbolo: "conj",
+        identificadores: &["conj"],
+        funcao: unaria!(conjugado),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Parte Real",
+        simbolo: "re",
+        identificadores: &["re"],
+        funcao: unaria!(parte_real),
+        angulo: Angulo::Nenhum,
+    },
+    Operacao {
+        nome: "Parte Imaginária",
+        simbolo: "im",
+        identificadores: &["im"],
+        funcao: unaria!(parte_imaginaria),
+        angulo: Angulo::Nenhum,
+    },
+];
+
+// Operações do modo programador, sobre palavras de largura fixa. O submenu
+// do modo é gerado a partir desta tabela, como o menu principal.
+#[derive(Debug, Clone, Copy)]
+pub enum FuncaoBits {
+    Unaria(fn(&FormatoPalavra, u64) -> Resultado<u64>),
+    Binaria(fn(&FormatoPalavra, u64, u64) -> Resultado<u64>),
+}
+
+#[derive(Debug)]
+pub struct OperacaoBits {
+    pub nome: &'static str,
+    pub simbolo: &'static str,
+    pub funcao: FuncaoBits,
+}
+
+impl OperacaoBits {
+    pub fn aridade(&self) -> usize {
+        match self.funcao {
+            FuncaoBits::Unaria(_) => 1,
+            FuncaoBits::Binaria(_) => 2,
+        }
+    }
+
+    pub fn aplicar(&self, formato: &FormatoPalavra, argumentos: &[u64]) -> Resultado<u64> {
+        match self.funcao {
+            FuncaoBits::Unaria(f) => f(formato, argumentos[0]),
+            FuncaoBits::Binaria(f) => f(formato, argumentos[0], argumentos[1]),
+        }
+    }
+}
+
+pub const OPERACOES_PROGRAMADOR: &[OperacaoBits] = &[
+    OperacaoBits {
+        nome: "Soma",
+        simbolo: "+",
+        funcao: FuncaoBits::Binaria(programador::somar),
+    },
+    OperacaoBits {
+        nome: "Subtração",
+        simbolo: "-",
+        funcao: FuncaoBits::Binaria(programador::subtrair),
+    },
+    OperacaoBits {
+        nome: "Multiplicação",
+        simbolo: "×",
+        funcao: FuncaoBits::Binaria(programador::multiplicar),
+    },
+    OperacaoBits {
+        nome: "Divisão Inteira",
+        simbolo: "÷",
+        funcao: FuncaoBits::Binaria(programador::dividir),
+    },
+    OperacaoBits {
+        nome: "Resto",
+        simbolo: "mod",
+        funcao: FuncaoBits::Binaria(programador::resto),
+    },
+    OperacaoBits {
+        nome: "Negação",
+        simbolo: "neg",
+        funcao: FuncaoBits::Unaria(programador::negar),
+    },
+    OperacaoBits {
+        nome: "AND",
+        simbolo: "&",
+        funcao: FuncaoBits::Binaria(programador::e_bit_a_bit),
+    },
+    OperacaoBits {
+        nome: "OR",
+        simbolo: "|",
+        funcao: FuncaoBits::Binaria(programador::ou_bit_a_bit),
+    },
+    OperacaoBits {
+        nome: "XOR",
+        simbolo: "^",
+        funcao: FuncaoBits::Binaria(programador::ou_exclusivo),
+    },
+    OperacaoBits {
+        nome: "NOT",
+        simbolo: "~",
+        funcao: FuncaoBits::Unaria(programador::nao_bit_a_bit),
+    },
+    OperacaoBits {
+        nome: "Deslocamento à Esquerda",
+        simbolo: "<<",
+        funcao: FuncaoBits::Binaria(programador::deslocar_esquerda),
+    },
+    OperacaoBits {
+        nome: "Deslocamento à Direita",
+        simbolo: ">>",
+        funcao: FuncaoBits::Binaria(programador::deslocar_direita),
+    },
+    OperacaoBits {
+        nome: "Rotação à Esquerda",
+        simbolo: "rol",
+        funcao: FuncaoBits::Binaria(programador::rotacionar_esquerda),
+    },
+    OperacaoBits {
+        nome: "Rotação à Direita",
+        simbolo: "ror",
+        funcao: FuncaoBits::Binaria(programador::rotacionar_direita),
+    },
+    OperacaoBits {
+        nome: "Conversão de Base",
+        simbolo: "=",
+        funcao: FuncaoBits::Unaria(programador::converter),
+    },
+];
+
+pub fn buscar_por_nome(nome: &str) -> Option<&'static Operacao> {
+    OPERACOES.iter().find(|op| op.nome.to_lowercase() == nome.to_lowercase())
+}
+
+pub fn buscar_por_identificador(identificador: &str) -> Option<&'static Operacao> {
+    OPERACOES.iter().find(|op| op.identificadores.contains(&identificador))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nomes_e_identificadores_unicos() {
+        for (i, op) in OPERACOES.iter().enumerate() {
+            for outra in &OPERACOES[i + 1..] {
+                assert_ne!(op.nome, outra.nome);
+                for identificador in op.identificadores {
+                    assert!(!outra.identificadores.contains(identificador));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_buscar_por_nome_ignora_maiusculas() {
+        let op = buscar_por_nome("raiz quadrada").unwrap();
+        assert_eq!(op.simbolo, "√");
+        assert_eq!(op.aridade(), 1);
+        assert!(buscar_por_nome("inexistente").is_none());
+    }
+
+    #[test]
+    fn test_buscar_por_identificador() {
+        assert_eq!(buscar_por_identificador("tg").unwrap().nome, "Tangente");
+        assert_eq!(buscar_por_identificador("pow").unwrap().aridade(), 2);
+        assert!(buscar_por_identificador("foo").is_none());
+    }
+
+    #[test]
+    fn test_operacoes_programador() {
+        let formato = FormatoPalavra::default();
+        for (i, op) in OPERACOES_PROGRAMADOR.iter().enumerate() {
+            assert!(OPERACOES_PROGRAMADOR[i + 1..].iter().all(|outra| outra.nome != op.nome));
+        }
+        let xor = OPERACOES_PROGRAMADOR.iter().find(|op| op.simbolo == "^").unwrap();
+        assert_eq!(xor.aridade(), 2);
+        assert_eq!(xor.aplicar(&formato, &[0b1100, 0b1010]), Ok(0b0110));
+    }
+
+    #[test]
+    fn test_aplicar_converte_unidade_de_angulo() {
+        let seno = buscar_por_identificador("sin").unwrap();
+        let asin = buscar_por_identificador("asin").unwrap();
+        assert_eq!(seno.aplicar(vec![90.0], UnidadeAngulo::Graus), Ok(1.0));
+        assert_eq!(seno.aplicar(vec![100.0], UnidadeAngulo::Grados), Ok(1.0));
+        assert!((seno.aplicar(vec![1.0], UnidadeAngulo::Radianos).unwrap() - 1f64.sin()).abs() < 1e-15);
+        assert_eq!(asin.aplicar(vec![1.0], UnidadeAngulo::Graus), Ok(90.0));
+        assert_eq!(asin.aplicar(vec![1.0], UnidadeAngulo::Grados), Ok(100.0));
+        assert_eq!(
+            buscar_por_identificador("tan").unwrap().aplicar(vec![270.0], UnidadeAngulo::Graus),
+            Err(CalcError::Assintota { operacao: "tangente" })
+        );
+    }
+
+    #[test]
+    fn test_aplicar_em_modo_inteiro() {
+        use num_bigint::BigInt;
+
+        let seno = buscar_por_identificador("sin").unwrap();
+        assert_eq!(seno.aplicar(vec![BigInt::from(90)], UnidadeAngulo::Graus), Ok(BigInt::from(1)));
+        assert_eq!(
+            seno.aplicar(vec![BigInt::from(30)], UnidadeAngulo::Graus),
+            Err(CalcError::NaoExato { operacao: "Seno" })
+        );
+        let fatorial = buscar_por_identificador("fact").unwrap();
+        assert_eq!(fatorial.aplicar(vec![BigInt::from(5)], UnidadeAngulo::Graus), Ok(BigInt::from(120)));
+    }
+
+    #[test]
+    fn test_aplicar_em_modo_complexo() {
+        let seno = buscar_por_identificador("sin").unwrap();
+        let arg = buscar_por_identificador("arg").unwrap();
+        let sqrt = buscar_por_identificador("sqrt").unwrap();
+        assert_eq!(seno.aplicar(vec![Complexo::new(90.0, 0.0)], UnidadeAngulo::Graus), Ok(Complexo::new(1.0, 0.0)));
+        assert_eq!(arg.aplicar(vec![Complexo::I], UnidadeAngulo::Graus), Ok(Complexo::new(90.0, 0.0)));
+        assert_eq!(arg.aplicar(vec![-1.0], UnidadeAngulo::Graus), Ok(180.0));
+        assert_eq!(sqrt.aplicar(vec![Complexo::new(-4.0, 0.0)], UnidadeAngulo::Graus), Ok(Complexo::new(0.0, 2.0)));
+        // Em graus, sin(i) é sin(π/180 · i)
+        let resultado = seno.aplicar(vec![Complexo::I], UnidadeAngulo::Graus).unwrap();
+        assert_eq!(resultado.re(), 0.0);
+        assert!((resultado.im() - (std::f64::consts::PI / 180.0).sinh()).abs() < 1e-15);
+    }
+}
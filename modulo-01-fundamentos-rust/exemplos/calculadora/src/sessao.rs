@@ -0,0 +1,342 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::angulo::UnidadeAngulo;
+use crate::erro::Resultado;
+use crate::expressao::{
+    self, buscar_constante, Comando, Contexto, ErroAvaliacao, ErroExpressao, PALAVRAS_CONVERSAO, UNIDADE_IMAGINARIA,
+};
+use crate::numero::{no_modo, FormaComplexa, Modo, Numero, Valor};
+use crate::programador::FormatoPalavra;
+use crate::registro;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntradaHistorico {
+    pub expressao: String,
+    pub resultado: Valor,
+    // Unidade do resultado de uma conversão, ex: "mi"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unidade: Option<String>,
+}
+
+impl EntradaHistorico {
+    pub fn resultado_com_unidade(&self) -> String {
+        match &self.unidade {
+            Some(unidade) => format!("{} {}", self.resultado, unidade),
+            None => self.resultado.to_string(),
+        }
+    }
+}
+
+// Estado que sobrevive entre uma operação e outra: modo numérico, unidade
+// de ângulo, `ans`, variáveis, registrador de memória (M+, M−, MR, MC) e
+// histórico numerado a partir de 1. Os valores guardam o modo em que foram
+// calculados e são convertidos quando usados em outro modo.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Sessao {
+    pub modo: Modo,
+    // Sessões salvas antes da unidade de ângulo existir abrem em graus
+    #[serde(default)]
+    pub angulo: UnidadeAngulo,
+    // Retangular (a + bi) ou polar (r∠θ) ao exibir resultados complexos
+    #[serde(default)]
+    pub forma: FormaComplexa,
+    // Largura, sinal, base e aritmética do modo programador
+    #[serde(default)]
+    pub programador: FormatoPalavra,
+    pub ans: Option<Valor>,
+    pub variaveis: BTreeMap<String, Valor>,
+    pub memoria: Option<Valor>,
+    pub historico: Vec<EntradaHistorico>,
+}
+
+impl Contexto for Sessao {
+    fn variavel(&self, nome: &str) -> Option<Valor> {
+        if nome == "ans" {
+            return self.ans.clone();
+        }
+        self.variaveis.get(nome).cloned()
+    }
+
+    fn historico(&self, indice: usize) -> Option<Valor> {
+        indice
+            .checked_sub(1)
+            .and_then(|i| self.historico.get(i))
+            .map(|entrada| entrada.resultado.clone())
+    }
+
+    fn unidade_angulo(&self) -> UnidadeAngulo {
+        self.angulo
+    }
+}
+
+impl Sessao {
+    pub fn nova() -> Self {
+        Self::default()
+    }
+
+    // Avalia uma linha (expressão ou atribuição) no modo atual e registra o resultado
+    pub fn executar(&mut self, linha: &str) -> Result<Valor, ErroExpressao> {
+        no_modo!(self.modo, N => self.executar_em::<N>(linha))
+    }
+
+    fn executar_em<N: Numero>(&mut self, linha: &str) -> Result<Valor, ErroExpressao> {
+        let comando = expressao::analisar_comando(linha)?;
+
+        let mut unidade = None;
+        let resultado = match comando {
+            Comando::Expressao(expr) => expressao::avaliar::<N>(&expr, self)?.para_valor(),
+            Comando::Conversao { expr, destino, unidade: texto } => {
+                unidade = Some(texto);
+                Valor::Real(expressao::converter(&expr, &destino, self)?)
+            }
+            Comando::Atribuicao { variavel, expr } => {
+                if Self::nome_reservado(&variavel) {
+                    return Err(ErroAvaliacao::NomeReservado(variavel).into());
+                }
+                let valor = expressao::avaliar::<N>(&expr, self)?.para_valor();
+                self.variaveis.insert(variavel, valor.clone());
+                valor
+            }
+        };
+
+        self.ans = Some(resultado.clone());
+        self.historico.push(EntradaHistorico {
+            expressao: linha.trim().to_string(),
+            resultado: resultado.clone(),
+            unidade,
+        });
+        Ok(resultado)
+    }
+
+    // Valor como deve aparecer para o usuário: complexos na forma escolhida
+    pub fn formatar(&self, valor: &Valor) -> String {
+        match (self.forma, valor) {
+            (FormaComplexa::Polar, Valor::Complexo(z)) => z.polar(self.angulo),
+            _ => valor.to_string(),
+        }
+    }
+
+    pub fn registrar(&mut self, expressao: &str, resultado: Valor) {
+        self.ans = Some(resultado.clone());
+        self.historico.push(EntradaHistorico {
+            expressao: expressao.to_string(),
+            resultado,
+            unidade: None,
+        });
+    }
+
+    fn nome_reservado(nome: &str) -> bool {
+        nome == "ans"
+            || nome == UNIDADE_IMAGINARIA
+            || PALAVRAS_CONVERSAO.contains(&nome)
+            || buscar_constante(nome).is_some()
+            || registro::buscar_por_identificador(nome).is_some()
+    }
+
+    // Valor atual (ou zero) convertido para o modo em uso
+    fn no_modo_atual<N: Numero>(valor: &Option<Valor>) -> Resultado<N> {
+        valor.as_ref().map_or(Ok(N::zero()), Valor::converter)
+    }
+
+    fn acumular_memoria<N: Numero>(&mut self, sinal: fn(&N, &N) -> Resultado<N>) -> Resultado<()> {
+        let memoria: N = Self::no_modo_atual(&self.memoria)?;
+        let ans: N = Self::no_modo_atual(&self.ans)?;
+        self.memoria = Some(sinal(&memoria, &ans)?.para_valor());
+        Ok(())
+    }
+
+    pub fn memoria_somar(&mut self) -> Resultado<()> {
+        no_modo!(self.modo, N => self.acumular_memoria::<N>(N::somar))
+    }
+
+    pub fn memoria_subtrair(&mut self) -> Resultado<()> {
+        no_modo!(self.modo, N => self.acumular_memoria::<N>(N::subtrair))
+    }
+
+    // MR: traz o valor da memória para `ans`
+    pub fn memoria_recuperar(&mut self) -> Resultado<Valor> {
+        let valor = no_modo!(self.modo, N => Self::no_modo_atual::<N>(&self.memoria)?.para_valor());
+        self.ans = Some(valor.clone());
+        Ok(valor)
+    }
+
+    pub fn memoria_limpar(&mut self) {
+        self.memoria = None;
+    }
+
+    pub fn salvar(&self, caminho: impl AsRef<Path>) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(caminho, json)
+    }
+
+    pub fn carregar(caminho: impl AsRef<Path>) -> io::Result<Sessao> {
+        let json = fs::read_to_string(caminho)?;
+        serde_json::from_str(&json).map_err(|erro| io::Error::new(io::ErrorKind::InvalidData, erro))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::erro::CalcError;
+
+    #[test]
+    fn test_ans_guarda_ultimo_resultado() {
+        let mut sessao = Sessao::nova();
+        assert_eq!(sessao.executar("2 + 3"), Ok(Valor::Real(5.0)));
+        assert_eq!(sessao.executar("ans * 2"), Ok(Valor::Real(10.0)));
+        assert_eq!(sessao.ans, Some(Valor::Real(10.0)));
+    }
+
+    #[test]
+    fn test_variaveis() {
+        let mut sessao = Sessao::nova();
+        assert_eq!(sessao.executar("x = 3.5"), Ok(Valor::Real(3.5)));
+        assert_eq!(sessao.executar("y = x * 2"), Ok(Valor::Real(7.0)));
+        assert_eq!(sessao.executar("x + y"), Ok(Valor::Real(10.5)));
+    }
+
+    #[test]
+    fn test_nomes_reservados() {
+        let mut sessao = Sessao::nova();
+        for nome in ["pi", "ans", "sqrt", "i"] {
+            assert_eq!(
+                sessao.executar(&format!("{} = 1", nome)),
+                Err(ErroAvaliacao::NomeReservado(nome.to_string()).into())
+            );
+        }
+    }
+
+    #[test]
+    fn test_historico_numerado() {
+        let mut sessao = Sessao::nova();
+        sessao.executar("10").unwrap();
+        sessao.executar("20").unwrap();
+        assert_eq!(sessao.executar("$1 + $2"), Ok(Valor::Real(30.0)));
+        assert_eq!(sessao.historico.len(), 3);
+        assert_eq!(sessao.historico[2].expressao, "$1 + $2");
+        assert_eq!(sessao.executar("$0"), Err(ErroAvaliacao::HistoricoInexistente(0).into()));
+        assert_eq!(sessao.executar("$9"), Err(ErroAvaliacao::HistoricoInexistente(9).into()));
+    }
+
+    #[test]
+    fn test_erro_nao_entra_no_historico() {
+        let mut sessao = Sessao::nova();
+        sessao.executar("4").unwrap();
+        assert!(sessao.executar("1 / 0").is_err());
+        assert_eq!(sessao.ans, Some(Valor::Real(4.0)));
+        assert_eq!(sessao.historico.len(), 1);
+    }
+
+    #[test]
+    fn test_memoria() {
+        let mut sessao = Sessao::nova();
+        sessao.executar("5").unwrap();
+        sessao.memoria_somar().unwrap();
+        sessao.memoria_somar().unwrap();
+        sessao.executar("3").unwrap();
+        sessao.memoria_subtrair().unwrap();
+        assert_eq!(sessao.memoria_recuperar(), Ok(Valor::Real(7.0)));
+        assert_eq!(sessao.ans, Some(Valor::Real(7.0)));
+        sessao.memoria_limpar();
+        assert_eq!(sessao.memoria, None);
+    }
+
+    #[test]
+    fn test_trocar_de_modo() {
+        let mut sessao = Sessao::nova();
+        sessao.executar("x = 0.1 + 0.2").unwrap();
+
+        sessao.modo = Modo::Racional;
+        assert_eq!(sessao.executar("1/10 + 2/10").unwrap().to_string(), "3/10");
+        assert_eq!(sessao.executar("ans * 10").unwrap().to_string(), "3");
+
+        sessao.modo = Modo::Inteiro;
+        assert_eq!(sessao.executar("2 ^ 80").unwrap().to_string(), "1208925819614629174706176");
+
+        sessao.modo = Modo::Decimal;
+        assert_eq!(sessao.executar("x").unwrap().to_string(), "0.30000000000000004");
+        assert_eq!(sessao.executar("0.1 + 0.2").unwrap().to_string(), "0.3");
+    }
+
+    #[test]
+    fn test_unidade_de_angulo() {
+        let mut sessao = Sessao::nova();
+        assert_eq!(sessao.executar("sin(90)"), Ok(Valor::Real(1.0)));
+
+        sessao.angulo = UnidadeAngulo::Radianos;
+        assert_eq!(sessao.executar("cos(pi)"), Ok(Valor::Real(-1.0)));
+        assert_eq!(sessao.executar("atan2(1, 0)"), Ok(Valor::Real(std::f64::consts::FRAC_PI_2)));
+
+        sessao.angulo = UnidadeAngulo::Grados;
+        assert_eq!(sessao.executar("acos(0)"), Ok(Valor::Real(100.0)));
+
+        // Inteiros continuam exatos quando o ângulo cai em um múltiplo de 90°
+        sessao.angulo = UnidadeAngulo::Graus;
+        sessao.modo = Modo::Inteiro;
+        assert_eq!(sessao.executar("sin(270) * 5").unwrap().to_string(), "-5");
+    }
+
+    #[test]
+    fn test_conversao_de_unidades() {
+        let mut sessao = Sessao::nova();
+        sessao.executar("d = 42.195").unwrap();
+        assert_eq!(sessao.executar("d km to m"), Ok(Valor::Real(42195.0)));
+        assert_eq!(sessao.historico.last().unwrap().resultado_com_unidade(), "42195 m");
+        assert_eq!(sessao.executar("ans / 1000"), Ok(Valor::Real(42.195)));
+        assert_eq!(sessao.historico.last().unwrap().unidade, None);
+        assert!(sessao.executar("5 km + 3 kg to m").is_err());
+        assert_eq!(sessao.executar("to = 1"), Err(ErroAvaliacao::NomeReservado("to".to_string()).into()));
+    }
+
+    #[test]
+    fn test_carregar_sessao_sem_unidade_de_angulo() {
+        let json = r#"{"modo":"Real","ans":null,"variaveis":{},"memoria":null,"historico":[]}"#;
+        let sessao: Sessao = serde_json::from_str(json).unwrap();
+        assert_eq!(sessao.angulo, UnidadeAngulo::Graus);
+        assert_eq!(sessao.programador, FormatoPalavra::default());
+    }
+
+    #[test]
+    fn test_salvar_e_carregar() {
+        let mut sessao = Sessao::nova();
+        sessao.executar("x = 2").unwrap();
+        sessao.executar("x ^ 10").unwrap();
+        sessao.modo = Modo::Racional;
+        sessao.executar("1 / 3").unwrap();
+        sessao.memoria_somar().unwrap();
+        sessao.angulo = UnidadeAngulo::Radianos;
+        sessao.programador.base = crate::programador::Base::Hexadecimal;
+
+        let caminho = std::env::temp_dir().join(format!("calculadora-sessao-{}.json", std::process::id()));
+        sessao.salvar(&caminho).unwrap();
+        let carregada = Sessao::carregar(&caminho).unwrap();
+        fs::remove_file(&caminho).unwrap();
+
+        assert_eq!(carregada, sessao);
+    }
+
+    #[test]
+    fn test_modo_complexo() {
+        let mut sessao = Sessao::nova();
+        sessao.executar("x = -4").unwrap();
+        assert!(sessao.executar("sqrt(x)").is_err());
+
+        sessao.modo = Modo::Complexo;
+        assert_eq!(sessao.executar("sqrt(x)").unwrap().to_string(), "2i");
+        assert_eq!(sessao.executar("ans * (1 + i)").unwrap().to_string(), "-2 + 2i");
+        sessao.forma = FormaComplexa::Polar;
+        let ultimo = sessao.historico.last().unwrap().resultado.clone();
+        assert_eq!(sessao.formatar(&ultimo), format!("{}∠135", 8f64.sqrt()));
+
+        // De volta ao modo real, valores com parte imaginária não convertem
+        sessao.modo = Modo::Real;
+        assert_eq!(sessao.executar("ans"), Err(ErroAvaliacao::Calculo(CalcError::Imaginario).into()));
+        assert_eq!(sessao.executar("$1 * 1"), Ok(Valor::Real(-4.0)));
+    }
+}
@@ -0,0 +1,239 @@
+use std::cell::RefCell;
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process;
+
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::FileHistory;
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
+use rustyline::{Config, Context, Editor, Helper};
+
+use crate::registro;
+
+// Histórico de expressões, na pasta pessoal do usuário
+const ARQUIVO_HISTORICO: &str = ".calculadora_historico";
+const TAMANHO_HISTORICO: usize = 1000;
+
+// Oferecidos no Tab junto com as funções do registro e as variáveis da sessão
+const COMANDOS: &[&str] = &[
+    "hist", "vars", "modo", "forma", "angulo", "solve", "integral", "plot", "salvar", "carregar",
+];
+const CONSTANTES: &[&str] = &["pi", "e", "ans"];
+
+type EditorCalculadora = Editor<Ajudante, FileHistory>;
+
+thread_local! {
+    // Só existe com um terminal de verdade; com a entrada redirecionada
+    // (pipes, testes) as linhas são lidas direto da stdin
+    static EDITOR: RefCell<Option<EditorCalculadora>> = RefCell::new(abrir_editor());
+}
+
+#[derive(Default)]
+struct Ajudante {
+    // Variáveis da sessão no momento da leitura
+    variaveis: Vec<String>,
+    // Só expressões continuam na linha seguinte; opções de menu não
+    multilinha: bool,
+}
+
+impl Completer for Ajudante {
+    type Candidate = String;
+
+    fn complete(&self, linha: &str, posicao: usize, _: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+        let inicio = inicio_da_palavra(linha, posicao);
+        Ok((inicio, completar(&linha[inicio..posicao], &self.variaveis)))
+    }
+}
+
+impl Hinter for Ajudante {
+    type Hint = String;
+}
+
+impl Highlighter for Ajudante {}
+
+impl Validator for Ajudante {
+    fn validate(&self, contexto: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        if self.multilinha && linha_incompleta(contexto.input()) {
+            Ok(ValidationResult::Incomplete)
+        } else {
+            Ok(ValidationResult::Valid(None))
+        }
+    }
+}
+
+impl Helper for Ajudante {}
+
+fn abrir_editor() -> Option<EditorCalculadora> {
+    if !io::stdin().is_terminal() {
+        return None;
+    }
+    let configuracao = Config::builder().max_history_size(TAMANHO_HISTORICO).ok()?.build();
+    let mut editor = Editor::with_config(configuracao).ok()?;
+    editor.set_helper(Some(Ajudante::default()));
+    if let Some(arquivo) = arquivo_historico() {
+        // Na primeira execução o arquivo ainda não existe
+        let _ = editor.load_history(&arquivo);
+    }
+    Some(editor)
+}
+
+fn arquivo_historico() -> Option<PathBuf> {
+    env::var_os("HOME").map(|casa| PathBuf::from(casa).join(ARQUIVO_HISTORICO))
+}
+
+// Lê uma opção de menu ou um valor. Ctrl-D, Ctrl-C ou o fim da entrada
+// encerram a calculadora.
+pub fn ler_linha(prompt: &str) -> String {
+    ler(prompt, false, Vec::new())
+}
+
+// Lê uma expressão, que continua nas linhas seguintes enquanto houver
+// parênteses abertos ou `\` no fim, e a guarda no histórico
+pub fn ler_expressao(prompt: &str, variaveis: Vec<String>) -> String {
+    let texto = ler(prompt, true, variaveis);
+    if !texto.is_empty() {
+        EDITOR.with(|editor| {
+            if let Some(editor) = editor.borrow_mut().as_mut() {
+                let _ = editor.add_history_entry(texto.as_str());
+            }
+        });
+    }
+    texto
+}
+
+fn ler(prompt: &str, multilinha: bool, variaveis: Vec<String>) -> String {
+    let lida = EDITOR.with(|editor| match editor.borrow_mut().as_mut() {
+        Some(editor) => {
+            if let Some(ajudante) = editor.helper_mut() {
+                ajudante.multilinha = multilinha;
+                ajudante.variaveis = variaveis;
+            }
+            match editor.readline(prompt) {
+                Ok(linha) => Some(linha),
+                Err(ReadlineError::Eof | ReadlineError::Interrupted) => None,
+                Err(erro) => {
+                    println!("Erro ao ler entrada: {}", erro);
+                    None
+                }
+            }
+        }
+        None => ler_direto(prompt, multilinha),
+    });
+    match lida {
+        Some(texto) => juntar_linhas(&texto),
+        None => encerrar(),
+    }
+}
+
+// Sem terminal: mesma continuação de linhas, sem edição nem histórico
+fn ler_direto(prompt: &str, multilinha: bool) -> Option<String> {
+    print!("{}", prompt);
+    let _ = io::stdout().flush();
+    let mut texto = String::new();
+    loop {
+        let mut linha = String::new();
+        let lidos = io::stdin().read_line(&mut linha).ok()?;
+        if lidos == 0 {
+            // Uma última linha sem \n ainda vale
+            return (!texto.is_empty()).then_some(texto);
+        }
+        texto.push_str(&linha);
+        let completa = texto.trim_end_matches(['\n', '\r']);
+        if !multilinha || !linha_incompleta(completa) {
+            return Some(completa.to_string());
+        }
+    }
+}
+
+pub fn salvar_historico() {
+    EDITOR.with(|editor| {
+        if let (Some(editor), Some(arquivo)) = (editor.borrow_mut().as_mut(), arquivo_historico()) {
+            if let Err(erro) = editor.save_history(&arquivo) {
+                println!("Erro ao salvar histórico em {}: {}", arquivo.display(), erro);
+            }
+        }
+    });
+}
+
+fn encerrar() -> ! {
+    salvar_historico();
+    println!();
+    println!("Obrigado por usar a calculadora!");
+    process::exit(0)
+}
+
+// Parênteses ou colchetes abertos, ou `\` no fim, pedem mais uma linha
+fn linha_incompleta(texto: &str) -> bool {
+    let abertos = texto.chars().filter(|c| matches!(c, '(' | '[')).count();
+    let fechados = texto.chars().filter(|c| matches!(c, ')' | ']')).count();
+    texto.trim_end().ends_with('\\') || abertos > fechados
+}
+
+fn juntar_linhas(texto: &str) -> String {
+    texto
+        .lines()
+        .map(|linha| linha.trim().trim_end_matches('\\').trim_end())
+        .filter(|linha| !linha.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Início, em bytes, do nome sob o cursor
+fn inicio_da_palavra(linha: &str, posicao: usize) -> usize {
+    linha[..posicao]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_alphanumeric() || *c == '_')
+        .last()
+        .map_or(posicao, |(i, _)| i)
+}
+
+fn completar(prefixo: &str, variaveis: &[String]) -> Vec<String> {
+    if prefixo.is_empty() {
+        return Vec::new();
+    }
+    let funcoes = registro::OPERACOES.iter().flat_map(|operacao| operacao.identificadores.iter().copied());
+    let mut nomes: Vec<String> = funcoes
+        .chain(COMANDOS.iter().copied())
+        .chain(CONSTANTES.iter().copied())
+        .chain(variaveis.iter().map(String::as_str))
+        .filter(|nome| nome.starts_with(prefixo))
+        .map(str::to_string)
+        .collect();
+    nomes.sort();
+    nomes.dedup();
+    nomes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completar() {
+        let variaveis = vec!["taxa".to_string(), "x".to_string()];
+        assert_eq!(completar("ta", &variaveis), vec!["tan", "tanh", "taxa"]);
+        assert_eq!(completar("sq", &variaveis), vec!["sqrt"]);
+        assert_eq!(completar("pl", &variaveis), vec!["plot"]);
+        assert!(completar("", &variaveis).is_empty());
+
+        assert_eq!(inicio_da_palavra("2 * sin(co", 10), 8);
+        assert_eq!(inicio_da_palavra("2 * ", 4), 4);
+        assert_eq!(inicio_da_palavra("√ ângulo", "√ ângulo".len()), "√ ".len());
+    }
+
+    #[test]
+    fn test_multilinha() {
+        assert!(linha_incompleta("2 * (3 +"));
+        assert!(linha_incompleta("[1, 2;"));
+        assert!(linha_incompleta("1 + \\"));
+        assert!(!linha_incompleta("2 * (3 + 4)"));
+
+        assert_eq!(juntar_linhas("2 * (3 +\n  4)"), "2 * (3 + 4)");
+        assert_eq!(juntar_linhas("1 + \\\n2"), "1 + 2");
+    }
+}
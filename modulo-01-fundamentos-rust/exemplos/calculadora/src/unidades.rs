@@ -0,0 +1,357 @@
+use std::fmt;
+
+// Análise dimensional: cada grandeza guarda seu valor em unidades base do SI
+// (m, kg, s, K, bit) e o expoente de cada dimensão. Somar metros com
+// quilogramas é erro; multiplicar metro por metro dá área.
+
+const BASES: [&str; 5] = ["m", "kg", "s", "K", "bit"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dimensao([i8; 5]);
+
+impl Dimensao {
+    pub const ADIMENSIONAL: Dimensao = Dimensao([0, 0, 0, 0, 0]);
+    pub const COMPRIMENTO: Dimensao = Dimensao([1, 0, 0, 0, 0]);
+    pub const MASSA: Dimensao = Dimensao([0, 1, 0, 0, 0]);
+    pub const TEMPO: Dimensao = Dimensao([0, 0, 1, 0, 0]);
+    pub const TEMPERATURA: Dimensao = Dimensao([0, 0, 0, 1, 0]);
+    pub const INFORMACAO: Dimensao = Dimensao([0, 0, 0, 0, 1]);
+    pub const AREA: Dimensao = Dimensao([2, 0, 0, 0, 0]);
+    pub const VOLUME: Dimensao = Dimensao([3, 0, 0, 0, 0]);
+    pub const VELOCIDADE: Dimensao = Dimensao([1, 0, -1, 0, 0]);
+    pub const FREQUENCIA: Dimensao = Dimensao([0, 0, -1, 0, 0]);
+    pub const FORCA: Dimensao = Dimensao([1, 1, -2, 0, 0]);
+    pub const ENERGIA: Dimensao = Dimensao([2, 1, -2, 0, 0]);
+    pub const POTENCIA: Dimensao = Dimensao([2, 1, -3, 0, 0]);
+    pub const PRESSAO: Dimensao = Dimensao([-1, 1, -2, 0, 0]);
+
+    const NOMEADAS: [(Dimensao, &'static str); 14] = [
+        (Dimensao::ADIMENSIONAL, "adimensional"),
+        (Dimensao::COMPRIMENTO, "comprimento"),
+        (Dimensao::MASSA, "massa"),
+        (Dimensao::TEMPO, "tempo"),
+        (Dimensao::TEMPERATURA, "temperatura"),
+        (Dimensao::INFORMACAO, "informação"),
+        (Dimensao::AREA, "área"),
+        (Dimensao::VOLUME, "volume"),
+        (Dimensao::VELOCIDADE, "velocidade"),
+        (Dimensao::FREQUENCIA, "frequência"),
+        (Dimensao::FORCA, "força"),
+        (Dimensao::ENERGIA, "energia"),
+        (Dimensao::POTENCIA, "potência"),
+        (Dimensao::PRESSAO, "pressão"),
+    ];
+
+    pub fn eh_adimensional(&self) -> bool {
+        *self == Dimensao::ADIMENSIONAL
+    }
+
+    // None se algum expoente sair de i8, como em potencia
+    fn combinar(&self, outra: &Dimensao, sinal: i8) -> Option<Dimensao> {
+        let mut expoentes = self.0;
+        for (expoente, outro) in expoentes.iter_mut().zip(outra.0) {
+            *expoente = expoente.checked_add(sinal.checked_mul(outro)?)?;
+        }
+        Some(Dimensao(expoentes))
+    }
+
+    pub fn multiplicar(&self, outra: &Dimensao) -> Option<Dimensao> {
+        self.combinar(outra, 1)
+    }
+
+    pub fn dividir(&self, outra: &Dimensao) -> Option<Dimensao> {
+        self.combinar(outra, -1)
+    }
+
+    pub fn potencia(&self, expoente: i8) -> Option<Dimensao> {
+        let mut expoentes = self.0;
+        for e in expoentes.iter_mut() {
+            *e = e.checked_mul(expoente)?;
+        }
+        Some(Dimensao(expoentes))
+    }
+}
+
+impl fmt::Display for Dimensao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some((_, nome)) = Dimensao::NOMEADAS.iter().find(|(dimensao, _)| dimensao == self) {
+            return write!(f, "{}", nome);
+        }
+        // Sem nome conhecido: escreve em unidades base, ex: m^2·kg·s^-2
+        let partes: Vec<String> = BASES
+            .iter()
+            .zip(self.0)
+            .filter(|(_, expoente)| *expoente != 0)
+            .map(|(base, expoente)| match expoente {
+                1 => base.to_string(),
+                _ => format!("{}^{}", base, expoente),
+            })
+            .collect();
+        write!(f, "{}", partes.join("·"))
+    }
+}
+
+#[derive(Debug)]
+pub struct Unidade {
+    pub simbolo: &'static str,
+    // Outras grafias aceitas, ex: "metro", "metros"
+    pub nomes: &'static [&'static str],
+    pub dimensao: Dimensao,
+    // valor_si = valor * fator + deslocamento
+    pub fator: f64,
+    // Diferente de zero só nas escalas de temperatura com zero deslocado
+    pub deslocamento: f64,
+}
+
+impl Unidade {
+    const fn nova(simbolo: &'static str, nomes: &'static [&'static str], dimensao: Dimensao, fator: f64) -> Unidade {
+        Unidade { simbolo, nomes, dimensao, fator, deslocamento: 0.0 }
+    }
+
+    // °C e °F não podem entrar em contas: 10 °C + 10 °C não é 20 °C em kelvin
+    pub fn tem_deslocamento(&self) -> bool {
+        self.deslocamento != 0.0
+    }
+
+    pub fn para_si(&self, valor: f64) -> f64 {
+        valor * self.fator + self.deslocamento
+    }
+
+    pub fn de_si(&self, valor: f64) -> f64 {
+        (valor - self.deslocamento) / self.fator
+    }
+}
+
+pub const UNIDADES: &[Unidade] = &[
+    // Comprimento
+    Unidade::nova("m", &["metro", "metros"], Dimensao::COMPRIMENTO, 1.0),
+    Unidade::nova("km", &["quilometro", "quilometros"], Dimensao::COMPRIMENTO, 1e3),
+    Unidade::nova("cm", &["centimetro", "centimetros"], Dimensao::COMPRIMENTO, 1e-2),
+    Unidade::nova("mm", &["milimetro", "milimetros"], Dimensao::COMPRIMENTO, 1e-3),
+    Unidade::nova("µm", &["um", "micrometro"], Dimensao::COMPRIMENTO, 1e-6),
+    Unidade::nova("nm", &["nanometro"], Dimensao::COMPRIMENTO, 1e-9),
+    Unidade::nova("in", &["pol", "polegada", "polegadas"], Dimensao::COMPRIMENTO, 0.0254),
+    Unidade::nova("ft", &["pé", "pés"], Dimensao::COMPRIMENTO, 0.3048),
+    Unidade::nova("yd", &["jarda", "jardas"], Dimensao::COMPRIMENTO, 0.9144),
+    Unidade::nova("mi", &["milha", "milhas"], Dimensao::COMPRIMENTO, 1609.344),
+    Unidade::nova("nmi", &["milha_nautica"], Dimensao::COMPRIMENTO, 1852.0),
+    // Massa
+    Unidade::nova("kg", &["quilo", "quilograma", "quilogramas"], Dimensao::MASSA, 1.0),
+    Unidade::nova("g", &["grama", "gramas"], Dimensao::MASSA, 1e-3),
+    Unidade::nova("mg", &["miligrama", "miligramas"], Dimensao::MASSA, 1e-6),
+    Unidade::nova("t", &["tonelada", "toneladas"], Dimensao::MASSA, 1e3),
+    Unidade::nova("lb", &["libra", "libras"], Dimensao::MASSA, 0.45359237),
+    Unidade::nova("oz", &["onca", "oncas"], Dimensao::MASSA, 0.028349523125),
+    // Tempo
+    Unidade::nova("s", &["seg", "segundo", "segundos"], Dimensao::TEMPO, 1.0),
+    Unidade::nova("ms", &["milissegundo", "milissegundos"], Dimensao::TEMPO, 1e-3),
+    Unidade::nova("min", &["minuto", "minutos"], Dimensao::TEMPO, 60.0),
+    Unidade::nova("h", &["hora", "horas"], Dimensao::TEMPO, 3600.0),
+    Unidade::nova("d", &["dia", "dias"], Dimensao::TEMPO, 86_400.0),
+    Unidade::nova("semana", &["semanas"], Dimensao::TEMPO, 604_800.0),
+    Unidade::nova("ano", &["anos"], Dimensao::TEMPO, 31_557_600.0),
+    // Temperatura
+    Unidade::nova("K", &["kelvin"], Dimensao::TEMPERATURA, 1.0),
+    Unidade {
+        simbolo: "°C",
+        nomes: &["degC", "celsius"],
+        dimensao: Dimensao::TEMPERATURA,
+        fator: 1.0,
+        deslocamento: 273.15,
+    },
+    Unidade {
+        simbolo: "°F",
+        nomes: &["degF", "fahrenheit"],
+        dimensao: Dimensao::TEMPERATURA,
+        fator: 5.0 / 9.0,
+        deslocamento: 459.67 * 5.0 / 9.0,
+    },
+    // Informação
+    Unidade::nova("bit", &["bits"], Dimensao::INFORMACAO, 1.0),
+    Unidade::nova("B", &["byte", "bytes"], Dimensao::INFORMACAO, 8.0),
+    Unidade::nova("kB", &[], Dimensao::INFORMACAO, 8e3),
+    Unidade::nova("MB", &[], Dimensao::INFORMACAO, 8e6),
+    Unidade::nova("GB", &[], Dimensao::INFORMACAO, 8e9),
+    Unidade::nova("TB", &[], Dimensao::INFORMACAO, 8e12),
+    Unidade::nova("KiB", &[], Dimensao::INFORMACAO, 8.0 * 1024.0),
+    Unidade::nova("MiB", &[], Dimensao::INFORMACAO, 8.0 * 1024.0 * 1024.0),
+    Unidade::nova("GiB", &[], Dimensao::INFORMACAO, 8.0 * 1024.0 * 1024.0 * 1024.0),
+    Unidade::nova("TiB", &[], Dimensao::INFORMACAO, 8.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0),
+    // Área e volume
+    Unidade::nova("ha", &["hectare", "hectares"], Dimensao::AREA, 1e4),
+    Unidade::nova("acre", &["acres"], Dimensao::AREA, 4046.8564224),
+    Unidade::nova("L", &["l", "litro", "litros"], Dimensao::VOLUME, 1e-3),
+    Unidade::nova("mL", &["ml", "mililitro", "mililitros"], Dimensao::VOLUME, 1e-6),
+    Unidade::nova("gal", &["galao", "galoes"], Dimensao::VOLUME, 3.785411784e-3),
+    // Velocidade (km/h, m/s etc. saem da combinação de unidades)
+    Unidade::nova("mph", &[], Dimensao::VELOCIDADE, 0.44704),
+    Unidade::nova("kn", &["nó", "nós"], Dimensao::VELOCIDADE, 1852.0 / 3600.0),
+    // Grandezas derivadas
+    Unidade::nova("Hz", &["hertz"], Dimensao::FREQUENCIA, 1.0),
+    Unidade::nova("N", &["newton", "newtons"], Dimensao::FORCA, 1.0),
+    Unidade::nova("J", &["joule", "joules"], Dimensao::ENERGIA, 1.0),
+    Unidade::nova("kJ", &[], Dimensao::ENERGIA, 1e3),
+    Unidade::nova("cal", &["caloria", "calorias"], Dimensao::ENERGIA, 4.184),
+    Unidade::nova("kcal", &[], Dimensao::ENERGIA, 4184.0),
+    Unidade::nova("kWh", &[], Dimensao::ENERGIA, 3.6e6),
+    Unidade::nova("W", &["watt", "watts"], Dimensao::POTENCIA, 1.0),
+    Unidade::nova("kW", &[], Dimensao::POTENCIA, 1e3),
+    Unidade::nova("cv", &[], Dimensao::POTENCIA, 735.49875),
+    Unidade::nova("Pa", &["pascal"], Dimensao::PRESSAO, 1.0),
+    Unidade::nova("bar", &[], Dimensao::PRESSAO, 1e5),
+    Unidade::nova("atm", &[], Dimensao::PRESSAO, 101_325.0),
+    Unidade::nova("psi", &[], Dimensao::PRESSAO, 6894.757293168361),
+];
+
+// Diferencia maiúsculas: "mm" é milímetro e "Mm" não existe
+pub fn buscar_unidade(nome: &str) -> Option<&'static Unidade> {
+    UNIDADES.iter().find(|unidade| unidade.simbolo == nome || unidade.nomes.contains(&nome))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Grandeza {
+    // Sempre em unidades base do SI
+    pub valor: f64,
+    pub dimensao: Dimensao,
+}
+
+impl Grandeza {
+    pub fn numero(valor: f64) -> Grandeza {
+        Grandeza { valor, dimensao: Dimensao::ADIMENSIONAL }
+    }
+
+    // Uma unidade de `unidade`, ex: 1 km = 1000 m
+    pub fn unitaria(unidade: &Unidade) -> Result<Grandeza, ErroUnidade> {
+        if unidade.tem_deslocamento() {
+            return Err(ErroUnidade::TemperaturaEmOperacao(unidade.simbolo));
+        }
+        Ok(Grandeza { valor: unidade.fator, dimensao: unidade.dimensao })
+    }
+
+    // Soma, subtração e resto só entre grandezas da mesma dimensão
+    pub fn mesma_dimensao(&self, outra: &Grandeza) -> Result<Dimensao, ErroUnidade> {
+        if self.dimensao != outra.dimensao {
+            return Err(ErroUnidade::Incompativel { esquerda: self.dimensao, direita: outra.dimensao });
+        }
+        Ok(self.dimensao)
+    }
+
+    pub fn exigir_adimensional(&self) -> Result<f64, ErroUnidade> {
+        self.mesma_dimensao(&Grandeza::numero(0.0))?;
+        Ok(self.valor)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErroUnidade {
+    Desconhecida(String),
+    Incompativel {
+        esquerda: Dimensao,
+        direita: Dimensao,
+    },
+    // m ^ 0.5 não tem significado dimensional, e m^200 não cabe em i8
+    ExpoenteInvalido,
+    TemperaturaEmOperacao(&'static str),
+    // Expressão com unidades sem indicar a unidade do resultado
+    SemConversao,
+}
+
+impl fmt::Display for ErroUnidade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroUnidade::Desconhecida(nome) => write!(f, "Unidade desconhecida: {}", nome),
+            ErroUnidade::Incompativel { esquerda, direita } => {
+                write!(f, "Dimensões incompatíveis: {} e {}", esquerda, direita)
+            }
+            ErroUnidade::ExpoenteInvalido => {
+                write!(f, "Grandezas com unidade só aceitam expoentes inteiros entre -128 e 127")
+            }
+            ErroUnidade::TemperaturaEmOperacao(simbolo) => write!(
+                f,
+                "{} só pode ser convertida diretamente (ex: 20 {} to K); use K para fazer contas",
+                simbolo, simbolo
+            ),
+            ErroUnidade::SemConversao => {
+                write!(f, "Indique a unidade do resultado com 'to', ex: 5 km to m")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErroUnidade {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u(nome: &str) -> &'static Unidade {
+        buscar_unidade(nome).unwrap()
+    }
+
+    fn converter(valor: f64, de: &Unidade, para: &Unidade) -> f64 {
+        assert_eq!(de.dimensao, para.dimensao);
+        para.de_si(de.para_si(valor))
+    }
+
+    fn aproximado(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-9 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn test_simbolos_unicos() {
+        for (i, unidade) in UNIDADES.iter().enumerate() {
+            for outra in &UNIDADES[i + 1..] {
+                assert_ne!(unidade.simbolo, outra.simbolo);
+                for nome in unidade.nomes.iter().chain([&unidade.simbolo]) {
+                    assert!(!outra.nomes.contains(nome) && outra.simbolo != *nome, "{} repetido", nome);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_converter_comprimento_e_massa() {
+        assert!(aproximado(converter(5.0, u("km"), u("mi")), 3.106855961186669));
+        assert!(aproximado(converter(1.0, u("ft"), u("in")), 12.0));
+        assert!(aproximado(converter(1.0, u("libra"), u("g")), 453.59237));
+    }
+
+    #[test]
+    fn test_converter_temperatura() {
+        assert!(aproximado(converter(100.0, u("°F"), u("°C")), 37.77777777777778));
+        assert!(aproximado(converter(-40.0, u("°C"), u("°F")), -40.0));
+        assert!(aproximado(converter(0.0, u("K"), u("°C")), -273.15));
+    }
+
+    #[test]
+    fn test_converter_informacao() {
+        assert!(aproximado(converter(1.0, u("GiB"), u("MB")), 1073.741824));
+        assert!(aproximado(converter(1.0, u("B"), u("bit")), 8.0));
+    }
+
+    #[test]
+    fn test_dimensoes_incompativeis() {
+        let km = Grandeza::unitaria(u("km")).unwrap();
+        let kg = Grandeza::unitaria(u("kg")).unwrap();
+        let erro = km.mesma_dimensao(&kg).unwrap_err();
+        assert_eq!(erro, ErroUnidade::Incompativel { esquerda: Dimensao::COMPRIMENTO, direita: Dimensao::MASSA });
+        assert_eq!(erro.to_string(), "Dimensões incompatíveis: comprimento e massa");
+    }
+
+    #[test]
+    fn test_exibir_dimensao() {
+        assert_eq!(Dimensao::COMPRIMENTO.dividir(&Dimensao::TEMPO).unwrap().to_string(), "velocidade");
+        assert_eq!(Dimensao::COMPRIMENTO.potencia(2).unwrap().to_string(), "área");
+        assert_eq!(Dimensao::ENERGIA.multiplicar(&Dimensao::TEMPO).unwrap().to_string(), "m^2·kg·s^-1");
+        let muito = Dimensao::COMPRIMENTO.potencia(100).unwrap();
+        assert_eq!(muito.multiplicar(&muito), None);
+        assert_eq!(muito.potencia(-1).unwrap().dividir(&muito), None);
+        assert_eq!(Dimensao::AREA.potencia(100), None);
+    }
+
+    #[test]
+    fn test_temperatura_com_deslocamento_nao_entra_em_contas() {
+        assert!(Grandeza::unitaria(u("K")).is_ok());
+        assert_eq!(Grandeza::unitaria(u("°C")), Err(ErroUnidade::TemperaturaEmOperacao("°C")));
+    }
+}
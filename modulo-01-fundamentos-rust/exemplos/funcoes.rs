@@ -1,3 +1,11 @@
+mod matematica;
+mod memoizacao;
+
+use std::thread;
+use std::time::Instant;
+
+use memoizacao::{memoizar, CacheIlimitado, CacheLru, Memo, MemoCompartilhado};
+
 fn main() {
     println!("=== Exemplo: Funções ===");
     
@@ -19,8 +27,40 @@ fn main() {
     let fibonacci_10 = fibonacci(10);
     println!("Fibonacci(10) = {}", fibonacci_10);
     
+    // Versões seguras do módulo matematica: erro em vez de estouro
+    match matematica::fatorial(25) {
+        Ok(valor) => println!("25! = {}", valor),
+        Err(erro) => println!("Erro: {}", erro),
+    }
+    println!("25! (saturado) = {}", matematica::fatorial_saturado(25));
+    println!("25! (exato) = {}", matematica::fatorial_grande(25));
+    match matematica::fibonacci(90) {
+        Ok(valor) => println!("Fibonacci(90) = {}", valor),
+        Err(erro) => println!("Erro: {}", erro),
+    }
+    match matematica::binomial(52, 5) {
+        Ok(valor) => println!("C(52, 5) = {}", valor),
+        Err(erro) => println!("Erro: {}", erro),
+    }
+    match matematica::permutacoes(10, 3) {
+        Ok(valor) => println!("P(10, 3) = {}", valor),
+        Err(erro) => println!("Erro: {}", erro),
+    }
+    println!("mdc(48, 18) = {}", matematica::mdc(48, 18));
+    match matematica::mmc(4, 6) {
+        Ok(valor) => println!("mmc(4, 6) = {}", valor),
+        Err(erro) => println!("Erro: {}", erro),
+    }
+    println!("97 é primo? {}", matematica::eh_primo(97));
+    match matematica::fatorar(360) {
+        Ok(fatores) => println!("360 = {:?}", fatores),
+        Err(erro) => println!("Erro: {}", erro),
+    }
+    
+    comparar_memoizacao();
+    
     // Função com múltiplos parâmetros
-    let media = calcular_media(10, 20, 30);
+    let media = calcular_media(10.0, 20.0, 30.0);
     println!("Média de 10, 20, 30: {}", media);
     
     // Função que retorna boolean
@@ -28,13 +68,13 @@ fn main() {
     println!("8 é par? {}", eh_par);
     
     // Função com parâmetros opcionais (usando Option)
-    let resultado_opcional = dividir_seguro(10, 2);
+    let resultado_opcional = dividir_seguro(10.0, 2.0);
     match resultado_opcional {
         Some(valor) => println!("10 / 2 = {}", valor),
         None => println!("Divisão por zero!"),
     }
     
-    let resultado_invalido = dividir_seguro(10, 0);
+    let resultado_invalido = dividir_seguro(10.0, 0.0);
     match resultado_invalido {
         Some(valor) => println!("Resultado: {}", valor),
         None => println!("Divisão por zero!"),
@@ -61,17 +101,19 @@ fn operacoes(a: i32, b: i32) -> (i32, i32) {
     (a + b, a * b)
 }
 
-// Função recursiva - Fatorial
-fn fatorial(n: u32) -> u32 {
+// Função recursiva - Fatorial (estoura u64 a partir de 21!; veja
+// matematica::fatorial para a versão verificada)
+fn fatorial(n: u32) -> u64 {
     if n <= 1 {
         1
     } else {
-        n * fatorial(n - 1)
+        n as u64 * fatorial(n - 1)
     }
 }
 
-// Função recursiva - Fibonacci
-fn fibonacci(n: u32) -> u32 {
+// Função recursiva - Fibonacci (tempo exponencial; matematica::fibonacci
+// é O(log n))
+fn fibonacci(n: u32) -> u64 {
     match n {
         0 => 0,
         1 => 1,
@@ -79,6 +121,77 @@ fn fibonacci(n: u32) -> u32 {
     }
 }
 
+// As mesmas recursões em "recursão aberta": quem chamar para os
+// subproblemas vem de fora, e o memo passa uma versão com cache
+fn fibonacci_memo(fib: &mut dyn FnMut(u32) -> u64, n: u32) -> u64 {
+    match n {
+        0 => 0,
+        1 => 1,
+        _ => fib(n - 1) + fib(n - 2),
+    }
+}
+
+fn fatorial_memo(fat: &mut dyn FnMut(u32) -> u64, n: u32) -> u64 {
+    if n <= 1 {
+        1
+    } else {
+        n as u64 * fat(n - 1)
+    }
+}
+
+// Compara o tempo das versões ingênuas com as memoizadas
+fn comparar_memoizacao() {
+    let n = 32;
+    let inicio = Instant::now();
+    let ingenuo = fibonacci(n);
+    println!("Fibonacci({}) sem memo = {} em {:?}", n, ingenuo, inicio.elapsed());
+
+    let mut memo = Memo::nova(fibonacci_memo, CacheIlimitado::new());
+    let inicio = Instant::now();
+    let memoizado = memo.chamar(n);
+    println!("Fibonacci({}) com memo = {} em {:?} ({})", n, memoizado, inicio.elapsed(), memo.estatisticas());
+
+    // A recursão só volta aos dois valores anteriores: 3 entradas bastam
+    let mut memo = Memo::nova(fibonacci_memo, CacheLru::new(3));
+    let inicio = Instant::now();
+    let memoizado = memo.chamar(n);
+    println!(
+        "Fibonacci({}) com LRU de 3 = {} em {:?} ({}; {} no cache)",
+        n,
+        memoizado,
+        inicio.elapsed(),
+        memo.estatisticas(),
+        memo.tamanho()
+    );
+
+    // O fatorial não repete subproblemas dentro de uma chamada; o memo só
+    // ajuda quando vários fatoriais são pedidos
+    let inicio = Instant::now();
+    let soma: u64 = (0..=20).map(fatorial).sum();
+    println!("0! + ... + 20! sem memo = {} em {:?}", soma, inicio.elapsed());
+
+    let memo = MemoCompartilhado::nova(fatorial_memo, CacheIlimitado::new());
+    let inicio = Instant::now();
+    let soma: u64 = thread::scope(|escopo| {
+        let memo = &memo;
+        let threads: Vec<_> = (0..=20).map(|n| escopo.spawn(move || memo.chamar(n))).collect();
+        threads.into_iter().map(|thread| thread.join().unwrap()).sum()
+    });
+    println!(
+        "0! + ... + 20! com memo em threads = {} em {:?} ({}; {} no cache)",
+        soma,
+        inicio.elapsed(),
+        memo.estatisticas(),
+        memo.tamanho()
+    );
+
+    // Função comum, não recursiva: só as chamadas repetidas acertam o cache
+    let mut primo = memoizar(matematica::eh_primo, CacheLru::new(8));
+    let numeros = [97, 1_000_000_007, 97, 561, 1_000_000_007];
+    let primos = numeros.iter().filter(|&&n| primo.chamar(n)).count();
+    println!("{} primos em {:?} ({})", primos, numeros, primo.estatisticas());
+}
+
 // Função com múltiplos parâmetros
 fn calcular_media(a: f64, b: f64, c: f64) -> f64 {
     (a + b + c) / 3.0
@@ -0,0 +1,374 @@
+// Matemática inteira segura: em vez de estourar em silêncio (13! em u32) ou
+// entrar em pânico, as funções devolvem ErroMatematico
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErroMatematico {
+    // O resultado não cabe em u64; guarda a chamada, ex: "fatorial(21)"
+    Estouro(String),
+    // Zero não tem fatoração em primos
+    FatorarZero,
+    // Pollard rho esgotou as constantes sem achar um divisor de n
+    FatoracaoFalhou(u64),
+}
+
+impl fmt::Display for ErroMatematico {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroMatematico::Estouro(chamada) => write!(f, "{} não cabe em u64", chamada),
+            ErroMatematico::FatorarZero => write!(f, "0 não pode ser fatorado em primos"),
+            ErroMatematico::FatoracaoFalhou(n) => write!(f, "nenhum divisor de {} encontrado", n),
+        }
+    }
+}
+
+impl std::error::Error for ErroMatematico {}
+
+// ---------- Fatorial ----------
+
+// Iterativo e verificado: 20! é o maior que cabe em u64, então o laço para
+// no 21º fator mesmo para n enorme
+pub fn fatorial(n: u32) -> Result<u64, ErroMatematico> {
+    (2..=n as u64).try_fold(1u64, |produto, fator| {
+        produto
+            .checked_mul(fator)
+            .ok_or_else(|| ErroMatematico::Estouro(format!("fatorial({})", n)))
+    })
+}
+
+// Para no maior valor de u64 em vez de dar erro
+pub fn fatorial_saturado(n: u32) -> u64 {
+    fatorial(n).unwrap_or(u64::MAX)
+}
+
+// Exato para qualquer n, com um número natural de tamanho arbitrário
+pub fn fatorial_grande(n: u32) -> Natural {
+    let mut resultado = Natural::from(1);
+    for fator in 2..=n {
+        resultado.multiplicar(fator);
+    }
+    resultado
+}
+
+// Número natural sem limite de tamanho, em "dígitos" de base 10^9 (o menos
+// significativo primeiro), o que deixa a impressão em decimal trivial
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Natural {
+    digitos: Vec<u32>,
+}
+
+const BASE: u64 = 1_000_000_000;
+
+impl Natural {
+    pub fn multiplicar(&mut self, fator: u32) {
+        if fator == 0 {
+            self.digitos = vec![0];
+            return;
+        }
+        let mut vai_um = 0;
+        for digito in &mut self.digitos {
+            let produto = *digito as u64 * fator as u64 + vai_um;
+            *digito = (produto % BASE) as u32;
+            vai_um = produto / BASE;
+        }
+        while vai_um > 0 {
+            self.digitos.push((vai_um % BASE) as u32);
+            vai_um /= BASE;
+        }
+    }
+}
+
+impl From<u64> for Natural {
+    fn from(mut n: u64) -> Natural {
+        let mut digitos = vec![(n % BASE) as u32];
+        n /= BASE;
+        while n > 0 {
+            digitos.push((n % BASE) as u32);
+            n /= BASE;
+        }
+        Natural { digitos }
+    }
+}
+
+impl fmt::Display for Natural {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut digitos = self.digitos.iter().rev();
+        if let Some(primeiro) = digitos.next() {
+            write!(f, "{}", primeiro)?;
+        }
+        for digito in digitos {
+            write!(f, "{:09}", digito)?;
+        }
+        Ok(())
+    }
+}
+
+// ---------- Fibonacci ----------
+
+// "Dobro rápido": F(2k) = F(k)·(2·F(k+1) − F(k)) e F(2k+1) = F(k)² + F(k+1)²,
+// O(log n) multiplicações em vez das O(φ^n) chamadas da recursão ingênua.
+// F(93) é o maior que cabe em u64.
+pub fn fibonacci(n: u32) -> Result<u64, ErroMatematico> {
+    let estouro = || ErroMatematico::Estouro(format!("fibonacci({})", n));
+    let (f, _) = fibonacci_par(n).ok_or_else(estouro)?;
+    u64::try_from(f).map_err(|_| estouro())
+}
+
+// (F(n), F(n + 1)) em u128, que tem folga para o F(n + 1) de F(93)
+fn fibonacci_par(n: u32) -> Option<(u128, u128)> {
+    if n == 0 {
+        return Some((0, 1));
+    }
+    let (a, b) = fibonacci_par(n / 2)?;
+    let dobro = a.checked_mul(b.checked_mul(2)?.checked_sub(a)?)?;
+    let dobro_mais_um = a.checked_mul(a)?.checked_add(b.checked_mul(b)?)?;
+    if n.is_multiple_of(2) {
+        Some((dobro, dobro_mais_um))
+    } else {
+        Some((dobro_mais_um, dobro.checked_add(dobro_mais_um)?))
+    }
+}
+
+// ---------- Combinatória ----------
+
+// C(n, k) pela fórmula multiplicativa: cada resultado parcial C(n − k + i, i)
+// é inteiro, então a divisão é exata e nada passa de u64 sem necessidade
+pub fn binomial(n: u64, k: u64) -> Result<u64, ErroMatematico> {
+    if k > n {
+        return Ok(0);
+    }
+    // C(n, k) = C(n, n − k): basta o menor dos dois
+    let menor = k.min(n - k);
+    let mut resultado: u64 = 1;
+    for i in 1..=menor {
+        let proximo = resultado as u128 * (n - menor + i) as u128 / i as u128;
+        resultado = u64::try_from(proximo).map_err(|_| ErroMatematico::Estouro(format!("binomial({}, {})", n, k)))?;
+    }
+    Ok(resultado)
+}
+
+// Arranjos: n! / (n − k)!, sequências de k elementos distintos entre n
+pub fn permutacoes(n: u64, k: u64) -> Result<u64, ErroMatematico> {
+    if k > n {
+        return Ok(0);
+    }
+    (n - k + 1..=n).try_fold(1u64, |produto, fator| {
+        produto
+            .checked_mul(fator)
+            .ok_or_else(|| ErroMatematico::Estouro(format!("permutacoes({}, {})", n, k)))
+    })
+}
+
+// ---------- Divisibilidade ----------
+
+pub fn mdc(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// mmc(0, n) = 0 por convenção
+pub fn mmc(a: u64, b: u64) -> Result<u64, ErroMatematico> {
+    if a == 0 || b == 0 {
+        return Ok(0);
+    }
+    (a / mdc(a, b))
+        .checked_mul(b)
+        .ok_or_else(|| ErroMatematico::Estouro(format!("mmc({}, {})", a, b)))
+}
+
+// ---------- Primos ----------
+
+// Com essas 12 bases o teste de Miller–Rabin é determinístico para todo u64
+const BASES_MILLER_RABIN: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+fn multiplicar_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+fn potencia_mod(mut base: u64, mut expoente: u64, m: u64) -> u64 {
+    let mut resultado = 1;
+    base %= m;
+    while expoente > 0 {
+        if expoente & 1 == 1 {
+            resultado = multiplicar_mod(resultado, base, m);
+        }
+        base = multiplicar_mod(base, base, m);
+        expoente >>= 1;
+    }
+    resultado
+}
+
+pub fn eh_primo(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in BASES_MILLER_RABIN {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    // n − 1 = d · 2^s com d ímpar
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'bases: for a in BASES_MILLER_RABIN {
+        let mut x = potencia_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = multiplicar_mod(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+        }
+        // `a` testemunha que n é composto
+        return false;
+    }
+    true
+}
+
+// Pares (primo, expoente) em ordem crescente: 360 = 2^3 · 3^2 · 5
+pub fn fatorar(n: u64) -> Result<Vec<(u64, u32)>, ErroMatematico> {
+    if n == 0 {
+        return Err(ErroMatematico::FatorarZero);
+    }
+    let mut primos = Vec::new();
+    separar_fatores(n, &mut primos)?;
+    primos.sort_unstable();
+
+    let mut fatores: Vec<(u64, u32)> = Vec::new();
+    for p in primos {
+        match fatores.last_mut() {
+            Some((ultimo, expoente)) if *ultimo == p => *expoente += 1,
+            _ => fatores.push((p, 1)),
+        }
+    }
+    Ok(fatores)
+}
+
+// Divisão por primos pequenos e, para o que sobrar, Pollard rho: tentar
+// todos os divisores até √n seria lento demais perto de 2^64
+fn separar_fatores(mut n: u64, primos: &mut Vec<u64>) -> Result<(), ErroMatematico> {
+    for p in BASES_MILLER_RABIN {
+        while n.is_multiple_of(p) {
+            primos.push(p);
+            n /= p;
+        }
+    }
+    if n == 1 {
+        return Ok(());
+    }
+    if eh_primo(n) {
+        primos.push(n);
+        return Ok(());
+    }
+    let divisor = pollard_rho(n)?;
+    separar_fatores(divisor, primos)?;
+    separar_fatores(n / divisor, primos)
+}
+
+// Na prática uma ou duas constantes bastam; o limite só evita um laço
+// infinito se algo der errado
+const TENTATIVAS_POLLARD: u64 = 64;
+
+// Um divisor não trivial de n, que deve ser composto e sem fatores pequenos.
+// Com x ↦ x² + c mod n, as sequências x e y (duas vezes mais rápida) acabam
+// coincidindo módulo um fator p de n antes de coincidirem módulo n.
+fn pollard_rho(n: u64) -> Result<u64, ErroMatematico> {
+    for c in 1..=TENTATIVAS_POLLARD {
+        let f = |x: u64| ((multiplicar_mod(x, x, n) as u128 + c as u128) % n as u128) as u64;
+        let (mut x, mut y, mut divisor) = (2, 2, 1);
+        while divisor == 1 {
+            x = f(x);
+            y = f(f(y));
+            divisor = mdc(x.abs_diff(y), n);
+        }
+        // divisor == n: o ciclo fechou sem achar fator, tenta outra constante
+        if divisor != n {
+            return Ok(divisor);
+        }
+    }
+    Err(ErroMatematico::FatoracaoFalhou(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fatorial() {
+        assert_eq!(fatorial(0), Ok(1));
+        assert_eq!(fatorial(13), Ok(6_227_020_800));
+        assert_eq!(fatorial(20), Ok(2_432_902_008_176_640_000));
+        assert_eq!(fatorial(21), Err(ErroMatematico::Estouro("fatorial(21)".to_string())));
+        // O erro cita o n pedido, não o fator em que estourou
+        assert_eq!(fatorial(10_000_000), Err(ErroMatematico::Estouro("fatorial(10000000)".to_string())));
+        assert_eq!(fatorial(u32::MAX), Err(ErroMatematico::Estouro(format!("fatorial({})", u32::MAX))));
+        assert_eq!(fatorial_saturado(25), u64::MAX);
+        assert_eq!(fatorial_saturado(10_000_000), u64::MAX);
+        assert_eq!(fatorial_grande(25).to_string(), "15511210043330985984000000");
+        assert_eq!(fatorial_grande(20).to_string(), fatorial(20).unwrap().to_string());
+    }
+
+    #[test]
+    fn test_fibonacci() {
+        let esperados = [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55];
+        for (n, esperado) in esperados.iter().enumerate() {
+            assert_eq!(fibonacci(n as u32), Ok(*esperado));
+        }
+        assert_eq!(fibonacci(93), Ok(12_200_160_415_121_876_738));
+        assert!(fibonacci(94).is_err());
+        assert!(fibonacci(u32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_combinatoria() {
+        assert_eq!(binomial(5, 2), Ok(10));
+        assert_eq!(binomial(3, 5), Ok(0));
+        assert_eq!(binomial(67, 33), Ok(14_226_520_737_620_288_370));
+        assert!(binomial(70, 35).is_err());
+        assert_eq!(binomial(100, 60), Err(ErroMatematico::Estouro("binomial(100, 60)".to_string())));
+        assert_eq!(permutacoes(5, 2), Ok(20));
+        assert_eq!(permutacoes(20, 20), fatorial(20));
+        assert!(permutacoes(21, 21).is_err());
+
+        assert_eq!(mdc(48, 18), 6);
+        assert_eq!(mdc(0, 7), 7);
+        assert_eq!(mdc(7, 0), 7);
+        assert_eq!(mdc(0, 0), 0);
+        assert_eq!(mdc(u64::MAX, u64::MAX - 1), 1);
+        assert_eq!(mmc(4, 6), Ok(12));
+        assert_eq!(mmc(0, 6), Ok(0));
+        assert_eq!(mmc(6, 0), Ok(0));
+        assert_eq!(mmc(0, 0), Ok(0));
+        assert_eq!(mmc(u64::MAX, u64::MAX), Ok(u64::MAX));
+        assert!(mmc(u64::MAX, u64::MAX - 1).is_err());
+    }
+
+    #[test]
+    fn test_primos() {
+        let primos: Vec<u64> = (0..30).filter(|&n| eh_primo(n)).collect();
+        assert_eq!(primos, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        // Pseudoprimos fortes para a base 2 e números de Carmichael
+        assert!(!eh_primo(2047));
+        assert!(!eh_primo(561));
+        assert!(!eh_primo(3_215_031_751));
+        // Em torno de u64::MAX: 2^64 − 59 é o maior primo de u64
+        assert!(eh_primo(18_446_744_073_709_551_557));
+        assert!(!eh_primo(18_446_744_073_709_551_559));
+        assert!(!eh_primo(u64::MAX));
+        assert!(!eh_primo(u64::MAX - 1));
+        assert_eq!(fatorar(18_446_744_073_709_551_557), Ok(vec![(18_446_744_073_709_551_557, 1)]));
+
+        assert_eq!(fatorar(360), Ok(vec![(2, 3), (3, 2), (5, 1)]));
+        assert_eq!(fatorar(1), Ok(vec![]));
+        assert_eq!(fatorar(0), Err(ErroMatematico::FatorarZero));
+        // Produto de dois primos perto de 2^32
+        assert_eq!(fatorar(4_294_967_291 * 4_294_967_279), Ok(vec![(4_294_967_279, 1), (4_294_967_291, 1)]));
+        assert_eq!(fatorar(u64::MAX), Ok(vec![(3, 1), (5, 1), (17, 1), (257, 1), (641, 1), (65_537, 1), (6_700_417, 1)]));
+    }
+}
@@ -0,0 +1,306 @@
+// Memoização genérica: guarda o resultado de cada chamada para não
+// recalcular os mesmos subproblemas.
+//
+// As funções são escritas em "recursão aberta": em vez de chamarem a si
+// mesmas, recebem quem chamar para os subproblemas, e o memo passa uma
+// versão que consulta o cache primeiro:
+//
+//     fn fibonacci(fib: &mut dyn FnMut(u32) -> u64, n: u32) -> u64 {
+//         if n < 2 { n as u64 } else { fib(n - 1) + fib(n - 2) }
+//     }
+//     let mut memo = Memo::nova(fibonacci, CacheIlimitado::new());
+//     memo.chamar(90);
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+// O que a função em recursão aberta recebe para resolver os subproblemas
+pub type Recursao<'a, K, V> = &'a mut dyn FnMut(K) -> V;
+
+// Onde os resultados ficam guardados
+pub trait Cache<K, V> {
+    fn buscar(&mut self, chave: &K) -> Option<V>;
+    fn guardar(&mut self, chave: K, valor: V);
+    fn tamanho(&self) -> usize;
+}
+
+// Guarda tudo para sempre
+pub struct CacheIlimitado<K, V> {
+    valores: HashMap<K, V>,
+}
+
+impl<K, V> CacheIlimitado<K, V> {
+    pub fn new() -> Self {
+        CacheIlimitado { valores: HashMap::new() }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Cache<K, V> for CacheIlimitado<K, V> {
+    fn buscar(&mut self, chave: &K) -> Option<V> {
+        self.valores.get(chave).cloned()
+    }
+
+    fn guardar(&mut self, chave: K, valor: V) {
+        self.valores.insert(chave, valor);
+    }
+
+    fn tamanho(&self) -> usize {
+        self.valores.len()
+    }
+}
+
+// Guarda no máximo `capacidade` resultados; quando enche, descarta o usado
+// há mais tempo (least recently used)
+pub struct CacheLru<K, V> {
+    capacidade: usize,
+    // Valor e momento do último uso
+    valores: HashMap<K, (V, u64)>,
+    // Momento do uso → chave; o primeiro é o usado há mais tempo
+    usos: BTreeMap<u64, K>,
+    relogio: u64,
+}
+
+impl<K, V> CacheLru<K, V> {
+    pub fn new(capacidade: usize) -> Self {
+        CacheLru { capacidade, valores: HashMap::new(), usos: BTreeMap::new(), relogio: 0 }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Cache<K, V> for CacheLru<K, V> {
+    fn buscar(&mut self, chave: &K) -> Option<V> {
+        let (valor, uso) = self.valores.get_mut(chave)?;
+        self.usos.remove(uso);
+        self.relogio += 1;
+        *uso = self.relogio;
+        self.usos.insert(self.relogio, chave.clone());
+        Some(valor.clone())
+    }
+
+    fn guardar(&mut self, chave: K, valor: V) {
+        if self.capacidade == 0 {
+            return;
+        }
+        if let Some((_, uso)) = self.valores.remove(&chave) {
+            self.usos.remove(&uso);
+        }
+        if self.valores.len() >= self.capacidade {
+            if let Some((_, antiga)) = self.usos.pop_first() {
+                self.valores.remove(&antiga);
+            }
+        }
+        self.relogio += 1;
+        self.usos.insert(self.relogio, chave.clone());
+        self.valores.insert(chave, (valor, self.relogio));
+    }
+
+    fn tamanho(&self) -> usize {
+        self.valores.len()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Estatisticas {
+    pub acertos: u64,
+    pub falhas: u64,
+}
+
+impl Estatisticas {
+    pub fn taxa_de_acerto(&self) -> f64 {
+        match self.acertos + self.falhas {
+            0 => 0.0,
+            total => self.acertos as f64 / total as f64,
+        }
+    }
+}
+
+impl fmt::Display for Estatisticas {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} acertos, {} falhas ({:.1}% de acerto)",
+            self.acertos,
+            self.falhas,
+            self.taxa_de_acerto() * 100.0
+        )
+    }
+}
+
+// Memo de uso em uma thread: `chamar` precisa de &mut para mexer no cache
+pub struct Memo<K, V, C, F> {
+    funcao: F,
+    cache: C,
+    estatisticas: Estatisticas,
+    _tipos: PhantomData<fn(K) -> V>,
+}
+
+impl<K, V, C, F> Memo<K, V, C, F>
+where
+    K: Clone,
+    V: Clone,
+    C: Cache<K, V>,
+    F: Fn(Recursao<'_, K, V>, K) -> V,
+{
+    pub fn nova(funcao: F, cache: C) -> Self {
+        Memo { funcao, cache, estatisticas: Estatisticas::default(), _tipos: PhantomData }
+    }
+
+    pub fn chamar(&mut self, chave: K) -> V {
+        avaliar(&self.funcao, &mut self.cache, &mut self.estatisticas, chave)
+    }
+
+    pub fn estatisticas(&self) -> Estatisticas {
+        self.estatisticas
+    }
+
+    pub fn tamanho(&self) -> usize {
+        self.cache.tamanho()
+    }
+}
+
+// Função comum, não recursiva: só as chamadas de fora passam pelo cache
+pub fn memoizar<K, V, C>(f: impl Fn(K) -> V, cache: C) -> Memo<K, V, C, impl Fn(Recursao<'_, K, V>, K) -> V>
+where
+    K: Clone,
+    V: Clone,
+    C: Cache<K, V>,
+{
+    Memo::nova(move |_: Recursao<'_, K, V>, chave| f(chave), cache)
+}
+
+fn avaliar<K, V, C, F>(funcao: &F, cache: &mut C, estatisticas: &mut Estatisticas, chave: K) -> V
+where
+    K: Clone,
+    V: Clone,
+    C: Cache<K, V>,
+    F: Fn(Recursao<'_, K, V>, K) -> V,
+{
+    if let Some(valor) = cache.buscar(&chave) {
+        estatisticas.acertos += 1;
+        return valor;
+    }
+    estatisticas.falhas += 1;
+    let valor = funcao(&mut |subproblema| avaliar(funcao, cache, estatisticas, subproblema), chave.clone());
+    cache.guardar(chave, valor.clone());
+    valor
+}
+
+// Memo que pode ser compartilhado entre threads (por referência ou Arc).
+// O cálculo acontece fora do lock, já que as chamadas recursivas voltam a
+// `chamar`; duas threads podem então calcular a mesma chave ao mesmo tempo,
+// o que só custa trabalho repetido.
+pub struct MemoCompartilhado<K, V, C, F> {
+    funcao: F,
+    cache: Mutex<C>,
+    acertos: AtomicU64,
+    falhas: AtomicU64,
+    _tipos: PhantomData<fn(K) -> V>,
+}
+
+impl<K, V, C, F> MemoCompartilhado<K, V, C, F>
+where
+    K: Clone,
+    V: Clone,
+    C: Cache<K, V>,
+    F: Fn(Recursao<'_, K, V>, K) -> V,
+{
+    pub fn nova(funcao: F, cache: C) -> Self {
+        MemoCompartilhado {
+            funcao,
+            cache: Mutex::new(cache),
+            acertos: AtomicU64::new(0),
+            falhas: AtomicU64::new(0),
+            _tipos: PhantomData,
+        }
+    }
+
+    pub fn chamar(&self, chave: K) -> V {
+        // Um pânico em outra thread não estraga o cache: no pior caso falta uma entrada
+        let guardado = self.cache.lock().unwrap_or_else(PoisonError::into_inner).buscar(&chave);
+        if let Some(valor) = guardado {
+            self.acertos.fetch_add(1, Ordering::Relaxed);
+            return valor;
+        }
+        self.falhas.fetch_add(1, Ordering::Relaxed);
+        let valor = (self.funcao)(&mut |subproblema| self.chamar(subproblema), chave.clone());
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner).guardar(chave, valor.clone());
+        valor
+    }
+
+    pub fn estatisticas(&self) -> Estatisticas {
+        Estatisticas { acertos: self.acertos.load(Ordering::Relaxed), falhas: self.falhas.load(Ordering::Relaxed) }
+    }
+
+    pub fn tamanho(&self) -> usize {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner).tamanho()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::thread;
+
+    fn fibonacci(fib: &mut dyn FnMut(u32) -> u64, n: u32) -> u64 {
+        if n < 2 {
+            n as u64
+        } else {
+            fib(n - 1) + fib(n - 2)
+        }
+    }
+
+    #[test]
+    fn test_memo_recursivo() {
+        let mut memo = Memo::nova(fibonacci, CacheIlimitado::new());
+        assert_eq!(memo.chamar(90), 2_880_067_194_370_816_120);
+        // Cada n de 0 a 90 é calculado uma única vez
+        assert_eq!(memo.estatisticas(), Estatisticas { acertos: 88, falhas: 91 });
+        assert_eq!(memo.chamar(90), 2_880_067_194_370_816_120);
+        assert_eq!(memo.estatisticas().acertos, 89);
+
+        // Capacidade 3 basta: a recursão só volta aos dois valores anteriores
+        let mut memo = Memo::nova(fibonacci, CacheLru::new(3));
+        assert_eq!(memo.chamar(90), 2_880_067_194_370_816_120);
+        assert_eq!(memo.estatisticas().falhas, 91);
+        assert_eq!(memo.tamanho(), 3);
+    }
+
+    #[test]
+    fn test_memoizar_e_lru() {
+        let chamadas = Cell::new(0);
+        let mut quadrado = memoizar(
+            |x: i32| {
+                chamadas.set(chamadas.get() + 1);
+                x * x
+            },
+            CacheLru::new(2),
+        );
+        assert_eq!(quadrado.chamar(2), 4);
+        assert_eq!(quadrado.chamar(3), 9);
+        assert_eq!(quadrado.chamar(2), 4);
+        // 3 é o usado há mais tempo e sai para 4 entrar
+        assert_eq!(quadrado.chamar(4), 16);
+        assert_eq!(quadrado.chamar(2), 4);
+        assert_eq!(quadrado.chamar(3), 9);
+        assert_eq!(chamadas.get(), 4);
+        assert_eq!(quadrado.estatisticas(), Estatisticas { acertos: 2, falhas: 4 });
+    }
+
+    #[test]
+    fn test_memo_compartilhado() {
+        let memo = MemoCompartilhado::nova(fibonacci, CacheIlimitado::new());
+        thread::scope(|escopo| {
+            for n in [50, 60, 70, 80] {
+                let memo = &memo;
+                escopo.spawn(move || memo.chamar(n));
+            }
+        });
+        assert_eq!(memo.chamar(80), 23_416_728_348_467_685);
+        assert_eq!(memo.tamanho(), 81);
+        assert!(memo.estatisticas().acertos > 0);
+    }
+}
@@ -0,0 +1,321 @@
+// Algoritmos de busca de substrings. As ocorrências saem como slices do
+// texto original, junto com a posição em bytes, como em str::match_indices.
+//
+// A busca é feita nos bytes UTF-8: como nenhum caractere começa no meio de
+// outro, toda ocorrência de um padrão válido cai em fronteira de caractere e
+// pode ser fatiada com segurança. Padrões vazios não têm ocorrências.
+
+use std::collections::VecDeque;
+use std::iter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modo {
+    // "aa" em "aaaa": posições 0, 1 e 2
+    Sobrepostas,
+    // "aa" em "aaaa": posições 0 e 2
+    SemSobreposicao,
+}
+
+pub trait Busca {
+    fn ocorrencias<'t>(&self, texto: &'t str, modo: Modo) -> impl Iterator<Item = (usize, &'t str)>;
+
+    fn primeira(&self, texto: &str) -> Option<usize> {
+        self.ocorrencias(texto, Modo::SemSobreposicao).next().map(|(posicao, _)| posicao)
+    }
+
+    fn todas<'t>(&self, texto: &'t str) -> Vec<(usize, &'t str)> {
+        self.ocorrencias(texto, Modo::SemSobreposicao).collect()
+    }
+}
+
+// Algoritmo ingênuo: compara o padrão em cada posição, O(n·m). É o de
+// slices::encontrar_palavra com duas guardas a mais, padrão vazio e padrão
+// maior que o texto (o original entrava em pânico nesse caso). Fica como
+// referência para testes e benchmark.
+pub fn ingenua(texto: &str, padrao: &str) -> Option<usize> {
+    let bytes_texto = texto.as_bytes();
+    let bytes_padrao = padrao.as_bytes();
+    // O vazio casaria em toda posição; o maior que o texto fatiaria além do fim
+    if bytes_padrao.is_empty() || bytes_padrao.len() > bytes_texto.len() {
+        return None;
+    }
+
+    for i in 0..=bytes_texto.len() - bytes_padrao.len() {
+        if &bytes_texto[i..i + bytes_padrao.len()] == bytes_padrao {
+            return Some(i);
+        }
+    }
+
+    None
+}
+
+// ---------- Knuth–Morris–Pratt ----------
+
+// O(n + m): nunca volta no texto. Ao falhar depois de casar k bytes, usa a
+// tabela de falha para saber quanto do padrão já está casado.
+pub struct Kmp<'p> {
+    padrao: &'p [u8],
+    // falha[k] = tamanho do maior prefixo próprio de padrao[..=k] que também é sufixo
+    falha: Vec<usize>,
+}
+
+impl<'p> Kmp<'p> {
+    pub fn new(padrao: &'p str) -> Self {
+        let padrao = padrao.as_bytes();
+        let mut falha = vec![0; padrao.len()];
+        let mut k = 0;
+        for i in 1..padrao.len() {
+            while k > 0 && padrao[i] != padrao[k] {
+                k = falha[k - 1];
+            }
+            if padrao[i] == padrao[k] {
+                k += 1;
+            }
+            falha[i] = k;
+        }
+        Kmp { padrao, falha }
+    }
+}
+
+impl Busca for Kmp<'_> {
+    fn ocorrencias<'t>(&self, texto: &'t str, modo: Modo) -> impl Iterator<Item = (usize, &'t str)> {
+        let bytes = texto.as_bytes();
+        let m = self.padrao.len();
+        // Posição no texto e quantos bytes do padrão já casaram
+        let (mut i, mut k) = (0, 0);
+        iter::from_fn(move || {
+            if m == 0 {
+                return None;
+            }
+            while i < bytes.len() {
+                let byte = bytes[i];
+                i += 1;
+                while k > 0 && byte != self.padrao[k] {
+                    k = self.falha[k - 1];
+                }
+                if byte == self.padrao[k] {
+                    k += 1;
+                }
+                if k == m {
+                    k = match modo {
+                        Modo::Sobrepostas => self.falha[m - 1],
+                        Modo::SemSobreposicao => 0,
+                    };
+                    return Some((i - m, &texto[i - m..i]));
+                }
+            }
+            None
+        })
+    }
+}
+
+// ---------- Boyer–Moore–Horspool ----------
+
+// Compara a janela e, se não casar, pula conforme o último byte dela: até m
+// bytes de uma vez. Sublinear na prática para padrões longos; O(n·m) no pior caso.
+pub struct Horspool<'p> {
+    padrao: &'p [u8],
+    salto: [usize; 256],
+}
+
+impl<'p> Horspool<'p> {
+    pub fn new(padrao: &'p str) -> Self {
+        let padrao = padrao.as_bytes();
+        let m = padrao.len();
+        let mut salto = [m.max(1); 256];
+        // O último byte não entra: alinhar com ele mesmo daria salto 0
+        for (i, &byte) in padrao.iter().enumerate().take(m.saturating_sub(1)) {
+            salto[byte as usize] = m - 1 - i;
+        }
+        Horspool { padrao, salto }
+    }
+}
+
+impl Busca for Horspool<'_> {
+    fn ocorrencias<'t>(&self, texto: &'t str, modo: Modo) -> impl Iterator<Item = (usize, &'t str)> {
+        let bytes = texto.as_bytes();
+        let m = self.padrao.len();
+        let mut i = 0;
+        iter::from_fn(move || {
+            if m == 0 {
+                return None;
+            }
+            while i + m <= bytes.len() {
+                let inicio = i;
+                let casou = &bytes[i..i + m] == self.padrao;
+                // O salto da tabela também vale depois de um acerto: nunca
+                // passa por cima de outra ocorrência
+                i += match modo {
+                    Modo::SemSobreposicao if casou => m,
+                    _ => self.salto[bytes[i + m - 1] as usize],
+                };
+                if casou {
+                    return Some((inicio, &texto[inicio..inicio + m]));
+                }
+            }
+            None
+        })
+    }
+}
+
+// ---------- Aho–Corasick ----------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ocorrencia<'t> {
+    pub inicio: usize,
+    // Índice na lista de padrões passada a AhoCorasick::new
+    pub padrao: usize,
+    pub trecho: &'t str,
+}
+
+// Vários padrões em uma só passada, O(n + m + ocorrências): uma trie dos
+// padrões vira um autômato, em que cada estado sabe para onde ir com
+// qualquer byte, inclusive quando o caminho na trie acaba.
+pub struct AhoCorasick<'p> {
+    padroes: Vec<&'p str>,
+    transicoes: Vec<[usize; 256]>,
+    // Padrões que terminam em cada estado, do mais longo para o mais curto
+    saidas: Vec<Vec<usize>>,
+}
+
+const SEM_TRANSICAO: usize = usize::MAX;
+
+impl<'p> AhoCorasick<'p> {
+    pub fn new(padroes: &[&'p str]) -> Self {
+        let mut transicoes = vec![[SEM_TRANSICAO; 256]];
+        let mut saidas = vec![Vec::new()];
+        for (indice, padrao) in padroes.iter().enumerate().filter(|(_, padrao)| !padrao.is_empty()) {
+            let mut estado = 0;
+            for &byte in padrao.as_bytes() {
+                if transicoes[estado][byte as usize] == SEM_TRANSICAO {
+                    transicoes.push([SEM_TRANSICAO; 256]);
+                    saidas.push(Vec::new());
+                    transicoes[estado][byte as usize] = transicoes.len() - 1;
+                }
+                estado = transicoes[estado][byte as usize];
+            }
+            saidas[estado].push(indice);
+        }
+
+        // Em largura: a falha de um estado (maior sufixo próprio que também
+        // está na trie) é mais raso que ele e, portanto, já está pronto
+        let mut falha = vec![0; transicoes.len()];
+        let mut fila = VecDeque::new();
+        for proximo in transicoes[0].iter_mut() {
+            match *proximo {
+                SEM_TRANSICAO => *proximo = 0,
+                estado => fila.push_back(estado),
+            }
+        }
+        while let Some(estado) = fila.pop_front() {
+            let herdadas = saidas[falha[estado]].clone();
+            saidas[estado].extend(herdadas);
+            let atalhos = transicoes[falha[estado]];
+            for (proximo, atalho) in transicoes[estado].iter_mut().zip(atalhos) {
+                match *proximo {
+                    SEM_TRANSICAO => *proximo = atalho,
+                    filho => {
+                        falha[filho] = atalho;
+                        fila.push_back(filho);
+                    }
+                }
+            }
+        }
+
+        AhoCorasick { padroes: padroes.to_vec(), transicoes, saidas }
+    }
+
+    // Sem sobreposição, entrega a ocorrência que termina primeiro (a mais
+    // longa, se várias terminam no mesmo byte) e recomeça depois dela
+    pub fn ocorrencias<'a, 't>(&'a self, texto: &'t str, modo: Modo) -> impl Iterator<Item = Ocorrencia<'t>> + 'a
+    where
+        't: 'a,
+    {
+        let bytes = texto.as_bytes();
+        // Posição, estado do autômato e quantas saídas dele já foram entregues
+        let (mut i, mut estado, mut entregues) = (0, 0, 0);
+        iter::from_fn(move || loop {
+            if let Some(&padrao) = self.saidas[estado].get(entregues) {
+                let inicio = i - self.padroes[padrao].len();
+                match modo {
+                    Modo::Sobrepostas => entregues += 1,
+                    Modo::SemSobreposicao => (estado, entregues) = (0, 0),
+                }
+                return Some(Ocorrencia { inicio, padrao, trecho: &texto[inicio..i] });
+            }
+            let &byte = bytes.get(i)?;
+            estado = self.transicoes[estado][byte as usize];
+            entregues = 0;
+            i += 1;
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXTOS: [&str; 4] = ["programação em Rust", "abracadabra", "aaaaaa", "ação, reação e inação"];
+    const PADROES: [&str; 7] = ["Rust", "abra", "aa", "ação", "x", "ç", ""];
+
+    // Referência: testa cada posição, avançando 1 ou m bytes depois de um acerto
+    fn esperado(texto: &str, padrao: &str, modo: Modo) -> Vec<usize> {
+        let mut posicoes = Vec::new();
+        let mut i = 0;
+        while let Some(posicao) = ingenua(&texto[i..], padrao) {
+            posicoes.push(i + posicao);
+            i += posicao + if modo == Modo::Sobrepostas { texto[i + posicao..].chars().next().unwrap().len_utf8() } else { padrao.len() };
+        }
+        posicoes
+    }
+
+    fn posicoes<'t>(ocorrencias: impl Iterator<Item = (usize, &'t str)>) -> Vec<usize> {
+        ocorrencias.map(|(posicao, _)| posicao).collect()
+    }
+
+    #[test]
+    fn test_kmp_e_horspool() {
+        for texto in TEXTOS {
+            for padrao in PADROES {
+                for modo in [Modo::Sobrepostas, Modo::SemSobreposicao] {
+                    let esperado = esperado(texto, padrao, modo);
+                    assert_eq!(posicoes(Kmp::new(padrao).ocorrencias(texto, modo)), esperado, "KMP {:?} em {:?}", padrao, texto);
+                    assert_eq!(posicoes(Horspool::new(padrao).ocorrencias(texto, modo)), esperado, "BMH {:?} em {:?}", padrao, texto);
+                }
+            }
+        }
+        assert_eq!(Horspool::new("ação").todas("reação e inação"), vec![(2, "ação"), (13, "ação")]);
+        assert_eq!(Kmp::new("Rust").primeira("programação em Rust"), Some(17));
+        assert_eq!(Kmp::new("").primeira("abc"), None);
+        assert_eq!(ingenua("abc", ""), None);
+        assert_eq!(ingenua("", ""), None);
+    }
+
+    #[test]
+    fn test_aho_corasick() {
+        let automato = AhoCorasick::new(&["he", "she", "his", "hers"]);
+        let sobrepostas: Vec<(usize, &str)> = automato
+            .ocorrencias("ushers", Modo::Sobrepostas)
+            .map(|ocorrencia| (ocorrencia.inicio, ocorrencia.trecho))
+            .collect();
+        assert_eq!(sobrepostas, vec![(1, "she"), (2, "he"), (2, "hers")]);
+        let sem_sobreposicao: Vec<&str> =
+            automato.ocorrencias("ushers his", Modo::SemSobreposicao).map(|ocorrencia| ocorrencia.trecho).collect();
+        assert_eq!(sem_sobreposicao, vec!["she", "his"]);
+
+        // Mesmo resultado que uma busca por padrão, somando todos
+        let padroes = ["ação", "re", "in", ""];
+        let automato = AhoCorasick::new(&padroes);
+        let texto = TEXTOS[3];
+        let mut encontradas: Vec<(usize, usize)> =
+            automato.ocorrencias(texto, Modo::Sobrepostas).map(|ocorrencia| (ocorrencia.inicio, ocorrencia.padrao)).collect();
+        encontradas.sort();
+        let mut esperadas: Vec<(usize, usize)> = padroes
+            .iter()
+            .enumerate()
+            .flat_map(|(indice, padrao)| esperado(texto, padrao, Modo::Sobrepostas).into_iter().map(move |posicao| (posicao, indice)))
+            .collect();
+        esperadas.sort();
+        assert_eq!(encontradas, esperadas);
+    }
+}
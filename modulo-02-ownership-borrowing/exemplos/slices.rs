@@ -1,3 +1,8 @@
+mod busca;
+mod texto;
+
+use busca::{AhoCorasick, Busca, Horspool, Kmp, Modo};
+
 fn main() {
     println!("=== Exemplo: Slices ===");
     
@@ -66,6 +71,10 @@ fn main() {
     println!("Texto: {}", texto);
     println!("Palavras: {:?}", palavras);
     
+    // Espaços que não são o byte b' ' também separam palavras
+    let texto_unicode = "Ação\tprogramação\u{00A0}café\nd'água";
+    println!("Palavras de {:?}: {:?}", texto_unicode, extrair_palavras(texto_unicode));
+    
     // Demonstração com caracteres
     println!("\n--- Slices de Caracteres ---");
     let s_chars = "🦀🦀🦀";
@@ -84,18 +93,46 @@ fn main() {
         }
         None => println!("Palavra não encontrada"),
     }
-}
-
-fn primeira_palavra(s: &str) -> &str {
-    let bytes = s.as_bytes();
     
-    for (i, &item) in bytes.iter().enumerate() {
-        if item == b' ' {
-            return &s[0..i];
-        }
+    println!("Busca ingênua (byte a byte): {:?}", busca::ingenua(texto_busca, "Rust"));
+    
+    // Todas as ocorrências, como slices do texto
+    let texto_repetido = "banana";
+    let sobrepostas: Vec<(usize, &str)> = Kmp::new("ana").ocorrencias(texto_repetido, Modo::Sobrepostas).collect();
+    println!("'ana' em '{}' (sobrepostas): {:?}", texto_repetido, sobrepostas);
+    println!("'ana' em '{}' (sem sobreposição): {:?}", texto_repetido, Kmp::new("ana").todas(texto_repetido));
+    
+    // Vários padrões de uma vez
+    let padroes = ["Rust", "ção", "em"];
+    let automato = AhoCorasick::new(&padroes);
+    for ocorrencia in automato.ocorrencias(texto_busca, Modo::Sobrepostas) {
+        println!("'{}' na posição {}", padroes[ocorrencia.padrao], ocorrencia.inicio);
     }
     
-    &s[..]
+    demonstrar_texto_unicode();
+}
+
+// Os utilitários do módulo texto devolvem slices do texto original
+fn demonstrar_texto_unicode() {
+    println!("\n--- Texto Unicode ---");
+    let texto = "Programação em Rust: a AÇÃO é segura, e a ação é rápida! 👨‍👩‍👧🇧🇷";
+    
+    println!("Busca por 'acao': {:?}", texto::buscar(texto, "acao"));
+    println!("Primeiros 14 grafemas: {}", texto::truncar(texto, 14));
+    println!(
+        "{} bytes, {} chars, {} grafemas",
+        texto.len(),
+        texto.chars().count(),
+        texto::grafemas(texto).count()
+    );
+    for (palavra, vezes) in texto::frequencia_palavras(texto).iter().take(3) {
+        println!("'{}' aparece {} vez(es)", palavra, vezes);
+    }
+}
+
+// Sem nenhuma palavra, devolve o slice vazio do fim de `s`
+fn primeira_palavra(s: &str) -> &str {
+    texto::primeira_palavra(s).unwrap_or(&s[s.len()..])
 }
 
 fn somar_slice(slice: &[i32]) -> i32 {
@@ -106,38 +143,14 @@ fn somar_slice(slice: &[i32]) -> i32 {
     soma
 }
 
+// Cada palavra é um slice de `s`: nada é copiado
 fn extrair_palavras(s: &str) -> Vec<&str> {
-    let mut palavras = Vec::new();
-    let mut inicio = 0;
-    
-    for (i, &byte) in s.as_bytes().iter().enumerate() {
-        if byte == b' ' {
-            if inicio != i {
-                palavras.push(&s[inicio..i]);
-            }
-            inicio = i + 1;
-        }
-    }
-    
-    // Adiciona a última palavra se não terminar com espaço
-    if inicio < s.len() {
-        palavras.push(&s[inicio..]);
-    }
-    
-    palavras
+    texto::palavras(s).collect()
 }
 
+// Boyer–Moore–Horspool em vez de comparar em cada posição (veja busca::ingenua)
 fn encontrar_palavra(texto: &str, palavra: &str) -> Option<usize> {
-    let bytes_texto = texto.as_bytes();
-    let bytes_palavra = palavra.as_bytes();
-    
-    for i in 0..=bytes_texto.len().saturating_sub(bytes_palavra.len()) {
-        if &bytes_texto[i..i + bytes_palavra.len()] == bytes_palavra {
-            return Some(i);
-        }
-    }
-    
-    None
+    Horspool::new(palavra).primeira(texto)
 }
 
 fn demonstrar_ownership_slices() {
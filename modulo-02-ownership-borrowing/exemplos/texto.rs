@@ -0,0 +1,275 @@
+// Utilitários de texto que entendem Unicode e, como os slices, não copiam
+// nada: tudo que sai daqui é um &str apontando para dentro do texto original.
+//
+// As fronteiras de palavra e de grafema seguem um subconjunto das regras do
+// Unicode (UAX #29) que cobre texto em português, números e emojis comuns,
+// sem depender de tabelas externas.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+// ---------- Palavras ----------
+
+// Sequências de letras e dígitos. Apóstrofo e ponto entre letras ("d'água",
+// "U.S.A") e vírgula ou ponto entre dígitos ("3,14", "1.000") não separam.
+// Qualquer espaço Unicode separa: tabulação, quebra de linha, espaço rígido.
+pub fn palavras(texto: &str) -> Palavras<'_> {
+    Palavras { texto, posicao: 0 }
+}
+
+pub struct Palavras<'a> {
+    texto: &'a str,
+    posicao: usize,
+}
+
+impl<'a> Iterator for Palavras<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let resto = &self.texto[self.posicao..];
+        let (deslocamento, _) = resto.char_indices().find(|&(_, c)| eh_de_palavra(c) && !eh_marca(c))?;
+        let inicio = self.posicao + deslocamento;
+
+        let mut fim = inicio;
+        // Último caractere base (sem contar acentos combinantes) da palavra
+        let mut anterior = None;
+        let mut caracteres = self.texto[inicio..].char_indices().peekable();
+        while let Some((i, c)) = caracteres.next() {
+            if eh_de_palavra(c) {
+                fim = inicio + i + c.len_utf8();
+                if !eh_marca(c) {
+                    anterior = Some(c);
+                }
+                continue;
+            }
+            let proximo = caracteres.peek().map(|&(_, c)| c);
+            match (anterior, proximo) {
+                (Some(antes), Some(depois)) if une(antes, c, depois) => continue,
+                _ => break,
+            }
+        }
+
+        self.posicao = fim;
+        Some(&self.texto[inicio..fim])
+    }
+}
+
+pub fn primeira_palavra(texto: &str) -> Option<&str> {
+    palavras(texto).next()
+}
+
+fn eh_de_palavra(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || eh_marca(c)
+}
+
+// `separador` fica dentro da palavra quando está entre duas letras ou entre dois dígitos
+fn une(antes: char, separador: char, depois: char) -> bool {
+    if antes.is_alphabetic() && depois.is_alphabetic() {
+        matches!(separador, '\'' | '’' | '.' | '·')
+    } else if antes.is_numeric() && depois.is_numeric() {
+        matches!(separador, '\'' | '’' | '.' | ',')
+    } else {
+        false
+    }
+}
+
+// Acentos combinantes (ex: "e" + U+0301 = "é" em texto decomposto)
+fn eh_marca(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{0483}'..='\u{0489}'
+            | '\u{0591}'..='\u{05BD}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+// ---------- Grafemas ----------
+
+const ZWJ: char = '\u{200D}';
+
+// O que o leitor vê como um caractere: letra + acentos combinantes, emoji +
+// modificadores (tom de pele, seletor de variação), sequências unidas por
+// ZWJ (👨‍👩‍👧), pares de bandeira (🇧🇷) e \r\n
+pub fn grafemas(texto: &str) -> Grafemas<'_> {
+    Grafemas { resto: texto }
+}
+
+pub struct Grafemas<'a> {
+    resto: &'a str,
+}
+
+impl<'a> Iterator for Grafemas<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut caracteres = self.resto.char_indices();
+        let (_, primeiro) = caracteres.next()?;
+        let mut fim = primeiro.len_utf8();
+        let mut depois_de_zwj = false;
+        let mut indicadores = usize::from(eh_indicador_regional(primeiro));
+
+        for (i, c) in caracteres {
+            let estende = match c {
+                '\n' => primeiro == '\r' && i == 1,
+                _ if primeiro == '\r' || primeiro == '\n' => false,
+                _ if eh_indicador_regional(c) => indicadores == 1,
+                _ => depois_de_zwj || eh_marca(c) || eh_modificador(c),
+            };
+            if !estende {
+                break;
+            }
+            depois_de_zwj = c == ZWJ;
+            indicadores += usize::from(eh_indicador_regional(c));
+            fim = i + c.len_utf8();
+        }
+
+        let (grafema, resto) = self.resto.split_at(fim);
+        self.resto = resto;
+        Some(grafema)
+    }
+}
+
+fn eh_modificador(c: char) -> bool {
+    matches!(
+        c,
+        ZWJ | '\u{FE00}'..='\u{FE0F}' | '\u{1F3FB}'..='\u{1F3FF}' | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+fn eh_indicador_regional(c: char) -> bool {
+    matches!(c, '\u{1F1E6}'..='\u{1F1FF}')
+}
+
+// Os primeiros `maximo` grafemas, sem nunca cortar um acento ou emoji ao meio
+pub fn truncar(texto: &str, maximo: usize) -> &str {
+    let bytes = grafemas(texto).take(maximo).map(str::len).sum();
+    &texto[..bytes]
+}
+
+// ---------- Busca ----------
+
+// Todas as ocorrências de `padrao`, ignorando maiúsculas e acentos
+// ("acao" encontra "Ação"), como trechos do texto original
+pub fn buscar<'a>(texto: &'a str, padrao: &str) -> Vec<&'a str> {
+    let padrao: Vec<char> = padrao.chars().flat_map(dobrar).collect();
+    if padrao.is_empty() {
+        return Vec::new();
+    }
+    // Texto dobrado, com o intervalo de bytes de onde veio cada caractere
+    let dobrado: Vec<(char, usize, usize)> = texto
+        .char_indices()
+        .flat_map(|(i, c)| dobrar(c).map(move |d| (d, i, i + c.len_utf8())))
+        .collect();
+
+    let mut encontrados = Vec::new();
+    let mut i = 0;
+    while i + padrao.len() <= dobrado.len() {
+        let trecho = &dobrado[i..i + padrao.len()];
+        if trecho.iter().map(|&(c, _, _)| c).eq(padrao.iter().copied()) {
+            let inicio = trecho[0].1;
+            let mut fim = trecho[trecho.len() - 1].2;
+            // Acentos combinantes logo após o último caractere fazem parte dele
+            fim += texto[fim..].chars().take_while(|&c| eh_marca(c)).map(char::len_utf8).sum::<usize>();
+            encontrados.push(&texto[inicio..fim]);
+            i += padrao.len();
+        } else {
+            i += 1;
+        }
+    }
+    encontrados
+}
+
+// Minúscula sem acento; acentos combinantes somem
+fn dobrar(c: char) -> impl Iterator<Item = char> {
+    c.to_lowercase().filter(|&c| !eh_marca(c)).map(|c| match c {
+        'á' | 'à' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'í' | 'ì' | 'î' | 'ï' => 'i',
+        'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+        'ú' | 'ù' | 'û' | 'ü' => 'u',
+        'ç' => 'c',
+        'ñ' => 'n',
+        'ý' | 'ÿ' => 'y',
+        c => c,
+    })
+}
+
+// ---------- Frequência ----------
+
+// Quantas vezes cada palavra aparece, da mais frequente para a menos (empates
+// na ordem do texto). Maiúsculas não importam, mas acentos sim: "e" e "é"
+// são palavras diferentes. Cada palavra é representada pela primeira
+// ocorrência no texto.
+pub fn frequencia_palavras(texto: &str) -> Vec<(&str, usize)> {
+    let mut posicoes: HashMap<String, usize> = HashMap::new();
+    let mut contagem: Vec<(&str, usize)> = Vec::new();
+    for palavra in palavras(texto) {
+        let chave = palavra.to_lowercase();
+        match posicoes.get(&chave) {
+            Some(&i) => contagem[i].1 += 1,
+            None => {
+                posicoes.insert(chave, contagem.len());
+                contagem.push((palavra, 1));
+            }
+        }
+    }
+    // sort_by_key é estável: os empates continuam na ordem de aparição
+    contagem.sort_by_key(|&(_, vezes)| Reverse(vezes));
+    contagem
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palavras() {
+        let texto = "Rust\té uma\u{00A0}linguagem\nincrível, não?";
+        let lista: Vec<&str> = palavras(texto).collect();
+        assert_eq!(lista, vec!["Rust", "é", "uma", "linguagem", "incrível", "não"]);
+        assert_eq!(primeira_palavra("  \t olá mundo"), Some("olá"));
+        assert_eq!(primeira_palavra(" \n "), None);
+
+        let lista: Vec<&str> = palavras("copo d'água custa 3,50 ou 1.000,00; fim.").collect();
+        assert_eq!(lista, vec!["copo", "d'água", "custa", "3,50", "ou", "1.000,00", "fim"]);
+
+        // "é" decomposto (e + acento agudo combinante) continua uma palavra só
+        let decomposto = "cafe\u{0301} quente";
+        assert_eq!(palavras(decomposto).collect::<Vec<_>>(), vec!["cafe\u{0301}", "quente"]);
+    }
+
+    #[test]
+    fn test_grafemas_e_truncar() {
+        let texto = "e\u{0301}🇧🇷👨‍👩‍👧👍🏽\r\nx";
+        let lista: Vec<&str> = grafemas(texto).collect();
+        assert_eq!(lista, vec!["e\u{0301}", "🇧🇷", "👨‍👩‍👧", "👍🏽", "\r\n", "x"]);
+
+        assert_eq!(truncar("ação", 2), "aç");
+        assert_eq!(truncar("cafe\u{0301}!", 4), "cafe\u{0301}");
+        assert_eq!(truncar("🦀🦀🦀", 2), "🦀🦀");
+        assert_eq!(truncar("abc", 10), "abc");
+    }
+
+    #[test]
+    fn test_buscar() {
+        let texto = "AÇÃO, ação e acao; Ac\u{0327}a\u{0303}o";
+        assert_eq!(buscar(texto, "acao"), vec!["AÇÃO", "ação", "acao", "Ac\u{0327}a\u{0303}o"]);
+        assert_eq!(buscar("Programação em RUST", "rust"), vec!["RUST"]);
+        assert!(buscar("abc", "").is_empty());
+        assert!(buscar("abc", "abcd").is_empty());
+    }
+
+    #[test]
+    fn test_frequencia() {
+        let texto = "O rato roeu a roupa. o Rato fugiu, e é isso: o rato!";
+        let contagem = frequencia_palavras(texto);
+        assert_eq!(contagem[0], ("O", 3));
+        assert_eq!(contagem[1], ("rato", 3));
+        assert!(contagem.contains(&("e", 1)));
+        assert!(contagem.contains(&("é", 1)));
+    }
+}
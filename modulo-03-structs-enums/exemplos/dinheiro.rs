@@ -0,0 +1,716 @@
+// Dinheiro em volta dos enums de moedas: valores em centavos inteiros (nada
+// de f64, que não representa 0,10 exatamente), conjuntos de denominações de
+// cada país, troco com o mínimo de moedas e um caixa com estoque limitado.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter,
+}
+
+impl Coin {
+    pub const TODAS: [Coin; 4] = [Coin::Penny, Coin::Nickel, Coin::Dime, Coin::Quarter];
+
+    pub fn centavos(&self) -> u32 {
+        match self {
+            Coin::Penny => 1,
+            Coin::Nickel => 5,
+            Coin::Dime => 10,
+            Coin::Quarter => 25,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum UsState {
+    Alabama,
+    Alaska,
+    California,
+    NewYork,
+    Texas,
+}
+
+impl UsState {
+    pub const TODOS: [UsState; 5] = [UsState::Alabama, UsState::Alaska, UsState::California, UsState::NewYork, UsState::Texas];
+}
+
+// Os quarters comemorativos trazem o estado no verso
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoinWithState {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter(UsState),
+}
+
+impl CoinWithState {
+    pub fn moeda(&self) -> Coin {
+        match self {
+            CoinWithState::Penny => Coin::Penny,
+            CoinWithState::Nickel => Coin::Nickel,
+            CoinWithState::Dime => Coin::Dime,
+            CoinWithState::Quarter(_) => Coin::Quarter,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErroDinheiro {
+    MoedasDiferentes { esquerda: Currency, direita: Currency },
+    Estouro,
+    ValorInvalido(String),
+    DenominacaoInvalida(u32),
+    // Não há combinação de moedas (no estoque, se for um caixa) que dê o valor
+    SemTroco(Money),
+    // Acima de TROCO_MAXIMO unidades, quando o troco precisa da tabela
+    TrocoGrande { valor: Money, maximo: Money },
+    PagamentoInsuficiente { preco: Money, pago: Money },
+}
+
+impl fmt::Display for ErroDinheiro {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroDinheiro::MoedasDiferentes { esquerda, direita } => {
+                write!(f, "não dá para misturar {} e {}", esquerda.codigo(), direita.codigo())
+            }
+            ErroDinheiro::Estouro => write!(f, "valor grande demais"),
+            ErroDinheiro::ValorInvalido(texto) => write!(f, "valor inválido: '{}'", texto),
+            ErroDinheiro::DenominacaoInvalida(valor) => write!(f, "denominação de {} centavos não existe", valor),
+            ErroDinheiro::SemTroco(valor) => write!(f, "não há como dar {} de troco", valor),
+            ErroDinheiro::TrocoGrande { valor, maximo } => write!(f, "troco de {} passa do máximo de {}", valor, maximo),
+            ErroDinheiro::PagamentoInsuficiente { preco, pago } => write!(f, "pagou {} por algo que custa {}", pago, preco),
+        }
+    }
+}
+
+impl std::error::Error for ErroDinheiro {}
+
+// ---------- Valores ----------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Currency {
+    Usd,
+    Brl,
+}
+
+impl Currency {
+    pub fn codigo(&self) -> &'static str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Brl => "BRL",
+        }
+    }
+}
+
+// Quantia em centavos de uma moeda. Não implementa Ord: R$ 1 < $2 não
+// faz sentido, então comparar devolve erro com moedas diferentes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Money {
+    moeda: Currency,
+    centavos: i64,
+}
+
+impl Money {
+    pub fn new(centavos: i64, moeda: Currency) -> Money {
+        Money { moeda, centavos }
+    }
+
+    pub fn zero(moeda: Currency) -> Money {
+        Money::new(0, moeda)
+    }
+
+    pub fn centavos(&self) -> i64 {
+        self.centavos
+    }
+
+    pub fn moeda(&self) -> Currency {
+        self.moeda
+    }
+
+    fn mesma_moeda(&self, outro: &Money) -> Result<(), ErroDinheiro> {
+        if self.moeda == outro.moeda {
+            Ok(())
+        } else {
+            Err(ErroDinheiro::MoedasDiferentes { esquerda: self.moeda, direita: outro.moeda })
+        }
+    }
+
+    pub fn comparar(&self, outro: &Money) -> Result<Ordering, ErroDinheiro> {
+        self.mesma_moeda(outro)?;
+        Ok(self.centavos.cmp(&outro.centavos))
+    }
+
+    pub fn somar(&self, outro: Money) -> Result<Money, ErroDinheiro> {
+        self.mesma_moeda(&outro)?;
+        let centavos = self.centavos.checked_add(outro.centavos).ok_or(ErroDinheiro::Estouro)?;
+        Ok(Money::new(centavos, self.moeda))
+    }
+
+    pub fn subtrair(&self, outro: Money) -> Result<Money, ErroDinheiro> {
+        self.mesma_moeda(&outro)?;
+        let centavos = self.centavos.checked_sub(outro.centavos).ok_or(ErroDinheiro::Estouro)?;
+        Ok(Money::new(centavos, self.moeda))
+    }
+
+    pub fn multiplicar(&self, vezes: i64) -> Result<Money, ErroDinheiro> {
+        let centavos = self.centavos.checked_mul(vezes).ok_or(ErroDinheiro::Estouro)?;
+        Ok(Money::new(centavos, self.moeda))
+    }
+
+    // Divide em `partes` quase iguais sem sumir nenhum centavo: R$ 10,00 em 3
+    // dá 3,34 + 3,33 + 3,33 (os centavos que sobram vão para as primeiras)
+    pub fn repartir(&self, partes: usize) -> Vec<Money> {
+        if partes == 0 {
+            return Vec::new();
+        }
+        let base = self.centavos / partes as i64;
+        let sobra = (self.centavos % partes as i64).unsigned_abs() as usize;
+        let ajuste = self.centavos.signum();
+        (0..partes)
+            .map(|i| Money::new(if i < sobra { base + ajuste } else { base }, self.moeda))
+            .collect()
+    }
+}
+
+// $1.25 e R$ 1,25
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sinal = if self.centavos < 0 { "-" } else { "" };
+        let inteiro = self.centavos.unsigned_abs() / 100;
+        let fracao = self.centavos.unsigned_abs() % 100;
+        match self.moeda {
+            Currency::Usd => write!(f, "{}${}.{:02}", sinal, inteiro, fracao),
+            Currency::Brl => write!(f, "{}R$ {},{:02}", sinal, inteiro, fracao),
+        }
+    }
+}
+
+impl FromStr for Money {
+    type Err = ErroDinheiro;
+
+    // "$1.25", "R$ 1,25", "1.25 USD" ou "-12,5 BRL"
+    fn from_str(texto: &str) -> Result<Money, ErroDinheiro> {
+        let invalido = || ErroDinheiro::ValorInvalido(texto.to_string());
+        let limpo = texto.trim();
+        let (negativo, limpo) = match limpo.strip_prefix('-') {
+            Some(resto) => (true, resto.trim_start()),
+            None => (false, limpo),
+        };
+        let (numero, moeda) = if let Some(resto) = limpo.strip_prefix("R$") {
+            (resto.trim(), Currency::Brl)
+        } else if let Some(resto) = limpo.strip_prefix('$') {
+            (resto.trim(), Currency::Usd)
+        } else {
+            match limpo.rsplit_once(' ') {
+                Some((numero, "USD")) => (numero.trim(), Currency::Usd),
+                Some((numero, "BRL")) => (numero.trim(), Currency::Brl),
+                _ => return Err(invalido()),
+            }
+        };
+
+        // Parte inteira e até duas casas depois do ponto ou da vírgula
+        let (inteiro, fracao) = match numero.split_once(['.', ',']) {
+            Some((inteiro, fracao)) if (1..=2).contains(&fracao.len()) => (inteiro, fracao),
+            Some(_) => return Err(invalido()),
+            None => (numero, "0"),
+        };
+        let so_digitos = |parte: &str| !parte.is_empty() && parte.bytes().all(|b| b.is_ascii_digit());
+        if !so_digitos(inteiro) || !so_digitos(fracao) {
+            return Err(invalido());
+        }
+        let inteiro: i64 = inteiro.parse().map_err(|_| ErroDinheiro::Estouro)?;
+        // "12,5" são 12 reais e 50 centavos
+        let fracao: i64 = format!("{:0<2}", fracao).parse().map_err(|_| invalido())?;
+        let centavos = inteiro.checked_mul(100).and_then(|c| c.checked_add(fracao)).ok_or(ErroDinheiro::Estouro)?;
+        Ok(Money::new(if negativo { -centavos } else { centavos }, moeda))
+    }
+}
+
+// ---------- Denominações e troco ----------
+
+// Lista de (denominação em centavos, quantidade), da maior para a menor
+pub type Troco = Vec<(u32, u32)>;
+
+// Maior troco que a programação dinâmica aceita, em unidades do mdc das
+// denominações (1 centavo nos EUA, 5 no real): as tabelas têm uma posição
+// por unidade. O guloso, usado quando é ótimo, não tem limite.
+pub const TROCO_MAXIMO: u32 = 100_000;
+
+pub fn quantidade_de_moedas(troco: &Troco) -> u32 {
+    troco.iter().map(|&(_, quantidade)| quantidade).sum()
+}
+
+fn mdc(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// As moedas (e cédulas) de um país, em centavos
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Denominacoes {
+    moeda: Currency,
+    // Em ordem crescente, sem repetição
+    valores: Vec<u32>,
+    // mdc dos valores: todo troco possível é múltiplo dele
+    unidade: u32,
+    // Calculado uma vez, na construção
+    canonico: bool,
+}
+
+impl Denominacoes {
+    pub fn new(moeda: Currency, valores: &[u32]) -> Result<Denominacoes, ErroDinheiro> {
+        if let Some(&zero) = valores.iter().find(|&&valor| valor == 0) {
+            return Err(ErroDinheiro::DenominacaoInvalida(zero));
+        }
+        let mut valores = valores.to_vec();
+        valores.sort_unstable();
+        valores.dedup();
+        Ok(Denominacoes::montar(moeda, valores))
+    }
+
+    // Penny, Nickel, Dime e Quarter
+    pub fn eua() -> Denominacoes {
+        Denominacoes::montar(Currency::Usd, Coin::TODAS.iter().map(Coin::centavos).collect())
+    }
+
+    // Moedas de 5 centavos a R$ 1 e cédulas até R$ 200; a de 1 centavo saiu de
+    // circulação, então valores que não são múltiplos de 5 não têm troco
+    pub fn real() -> Denominacoes {
+        let valores = vec![5, 10, 25, 50, 100, 200, 500, 1000, 2000, 5000, 10000, 20000];
+        Denominacoes::montar(Currency::Brl, valores)
+    }
+
+    // `valores` já em ordem crescente, sem zeros nem repetições
+    fn montar(moeda: Currency, valores: Vec<u32>) -> Denominacoes {
+        let unidade = valores.iter().fold(0, |unidade, &valor| mdc(unidade, valor)).max(1);
+        let mut denominacoes = Denominacoes { moeda, valores, unidade, canonico: false };
+        denominacoes.canonico = denominacoes.verificar_canonico();
+        denominacoes
+    }
+
+    pub fn moeda(&self) -> Currency {
+        self.moeda
+    }
+
+    pub fn valores(&self) -> &[u32] {
+        &self.valores
+    }
+
+    fn centavos_do_troco(&self, valor: Money) -> Result<u32, ErroDinheiro> {
+        if valor.moeda != self.moeda {
+            return Err(ErroDinheiro::MoedasDiferentes { esquerda: self.moeda, direita: valor.moeda });
+        }
+        u32::try_from(valor.centavos).map_err(|_| ErroDinheiro::ValorInvalido(valor.to_string()))
+    }
+
+    // O troco em unidades, para as tabelas da programação dinâmica
+    fn unidades_do_troco(&self, valor: Money) -> Result<usize, ErroDinheiro> {
+        let centavos = self.centavos_do_troco(valor)?;
+        if centavos % self.unidade != 0 {
+            return Err(ErroDinheiro::SemTroco(valor));
+        }
+        let unidades = centavos / self.unidade;
+        if unidades > TROCO_MAXIMO {
+            let maximo = Money::new(TROCO_MAXIMO as i64 * self.unidade as i64, self.moeda);
+            return Err(ErroDinheiro::TrocoGrande { valor, maximo });
+        }
+        Ok(unidades as usize)
+    }
+
+    // Sempre a maior denominação que cabe. Rápido, mas só é ótimo em sistemas
+    // "canônicos" como o americano e o brasileiro: com {1, 3, 4}, 6 vira
+    // 4 + 1 + 1 em vez de 3 + 3
+    pub fn troco_guloso(&self, valor: Money) -> Result<Troco, ErroDinheiro> {
+        let mut resto = self.centavos_do_troco(valor)?;
+        let mut troco = Vec::new();
+        for &denominacao in self.valores.iter().rev() {
+            if resto >= denominacao {
+                troco.push((denominacao, resto / denominacao));
+                resto %= denominacao;
+            }
+        }
+        if resto == 0 {
+            Ok(troco)
+        } else {
+            Err(ErroDinheiro::SemTroco(valor))
+        }
+    }
+
+    // O mínimo de moedas: em sistemas canônicos é o próprio guloso; nos
+    // outros, programação dinâmica sobre cada valor de 0 até `valor`
+    pub fn troco_otimo(&self, valor: Money) -> Result<Troco, ErroDinheiro> {
+        if self.canonico {
+            return self.troco_guloso(valor);
+        }
+        let alvo = self.unidades_do_troco(valor)?;
+        let minimo = self.tabela_otima(alvo);
+        if minimo[alvo].is_none() {
+            return Err(ErroDinheiro::SemTroco(valor));
+        }
+
+        let mut contagem: BTreeMap<u32, u32> = BTreeMap::new();
+        let mut v = alvo;
+        while let Some((_, indice)) = minimo[v].filter(|_| v > 0) {
+            let denominacao = self.valores[indice];
+            *contagem.entry(denominacao).or_insert(0) += 1;
+            v -= (denominacao / self.unidade) as usize;
+        }
+        Ok(contagem.into_iter().rev().collect())
+    }
+
+    // minimo[v] = (quantidade de moedas, índice da última moeda usada) para
+    // formar v unidades
+    fn tabela_otima(&self, ate: usize) -> Vec<Option<(u32, usize)>> {
+        let mut minimo: Vec<Option<(u32, usize)>> = vec![None; ate + 1];
+        minimo[0] = Some((0, 0));
+        for v in 1..=ate {
+            minimo[v] = self
+                .valores
+                .iter()
+                .map(|&d| (d / self.unidade) as usize)
+                .enumerate()
+                .filter(|&(_, d)| d <= v)
+                .filter_map(|(indice, d)| minimo[v - d].map(|(quantidade, _)| (quantidade + 1, indice)))
+                .min();
+        }
+        minimo
+    }
+
+    // O guloso é ótimo para todos os valores? Se não for, existe um
+    // contraexemplo menor que a soma das duas maiores denominações (Kozen e
+    // Zaks), então basta testar até lá. O teorema pede uma moeda de uma
+    // unidade, e um limite acima de TROCO_MAXIMO não dá para testar: nos dois
+    // casos a resposta é não.
+    pub fn eh_canonico(&self) -> bool {
+        self.canonico
+    }
+
+    fn verificar_canonico(&self) -> bool {
+        let limite = match self.valores[..] {
+            [] | [_] => return true,
+            [menor, .., penultima, ultima] if menor == self.unidade => (penultima as u64 + ultima as u64) / self.unidade as u64,
+            _ => return false,
+        };
+        if limite > TROCO_MAXIMO as u64 {
+            return false;
+        }
+        let limite = limite as usize;
+        let minimo = self.tabela_otima(limite);
+        (1..limite).all(|unidades| {
+            let valor = Money::new(unidades as i64 * self.unidade as i64, self.moeda);
+            let guloso = self.troco_guloso(valor).ok();
+            guloso.map(|troco| quantidade_de_moedas(&troco)) == minimo[unidades].map(|(quantidade, _)| quantidade)
+        })
+    }
+}
+
+// ---------- Caixa ----------
+
+// Gaveta de caixa: o troco só pode sair das moedas que estão nela
+#[derive(Debug, Clone)]
+pub struct Caixa {
+    denominacoes: Denominacoes,
+    estoque: BTreeMap<u32, u32>,
+}
+
+impl Caixa {
+    pub fn new(denominacoes: Denominacoes) -> Caixa {
+        Caixa { denominacoes, estoque: BTreeMap::new() }
+    }
+
+    pub fn depositar(&mut self, denominacao: u32, quantidade: u32) -> Result<(), ErroDinheiro> {
+        if !self.denominacoes.valores.contains(&denominacao) {
+            return Err(ErroDinheiro::DenominacaoInvalida(denominacao));
+        }
+        let atual = self.estoque.entry(denominacao).or_insert(0);
+        *atual = atual.checked_add(quantidade).ok_or(ErroDinheiro::Estouro)?;
+        Ok(())
+    }
+
+    pub fn quantidade(&self, denominacao: u32) -> u32 {
+        self.estoque.get(&denominacao).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> Money {
+        let centavos = self.estoque.iter().map(|(&d, &quantidade)| d as i64 * quantidade as i64).sum();
+        Money::new(centavos, self.denominacoes.moeda)
+    }
+
+    // Troco com o mínimo de moedas usando só o estoque; se der certo, as
+    // moedas saem da gaveta. Aqui o guloso não serve nem em sistema canônico
+    // (pode faltar a moeda que ele escolhe), então vale o limite TROCO_MAXIMO.
+    pub fn dar_troco(&mut self, valor: Money) -> Result<Troco, ErroDinheiro> {
+        let unidade = self.denominacoes.unidade;
+        let alvo = self.denominacoes.unidades_do_troco(valor)?;
+        let disponiveis: Vec<(u32, u32)> =
+            self.estoque.iter().filter(|(_, &quantidade)| quantidade > 0).map(|(&d, &quantidade)| (d, quantidade)).collect();
+
+        // Mochila limitada, uma denominação por vez: minimo[v] é o mínimo de
+        // moedas para v unidades com as denominações vistas até agora, e
+        // usadas[j][v] quantas moedas da j-ésima entram nesse mínimo
+        let mut minimo: Vec<Option<u32>> = vec![None; alvo + 1];
+        minimo[0] = Some(0);
+        let mut usadas: Vec<Vec<u32>> = Vec::with_capacity(disponiveis.len());
+        for &(d, estoque) in &disponiveis {
+            let passo = (d / unidade) as usize;
+            let anterior = minimo.clone();
+            let mut quantas = vec![0; alvo + 1];
+            // Nos valores v = r + t·passo, usar k moedas custa
+            // anterior[v − k·passo] + k. A fila guarda os t candidatos com
+            // anterior − t crescente e descarta os que pediriam mais moedas
+            // do que há no estoque.
+            for r in 0..passo.min(alvo + 1) {
+                let mut fila: VecDeque<(usize, i64)> = VecDeque::new();
+                for (t, v) in (r..=alvo).step_by(passo).enumerate() {
+                    if let Some(moedas) = anterior[v] {
+                        let chave = moedas as i64 - t as i64;
+                        while fila.back().is_some_and(|&(_, outra)| outra >= chave) {
+                            fila.pop_back();
+                        }
+                        fila.push_back((t, chave));
+                    }
+                    while fila.front().is_some_and(|&(s, _)| t - s > estoque as usize) {
+                        fila.pop_front();
+                    }
+                    if let Some(&(s, chave)) = fila.front() {
+                        minimo[v] = Some((chave + t as i64) as u32);
+                        quantas[v] = (t - s) as u32;
+                    }
+                }
+            }
+            usadas.push(quantas);
+        }
+        if minimo[alvo].is_none() {
+            return Err(ErroDinheiro::SemTroco(valor));
+        }
+
+        // De volta pelas denominações, da maior para a menor
+        let mut troco = Troco::new();
+        let mut v = alvo;
+        for (&(d, _), quantas) in disponiveis.iter().zip(&usadas).rev() {
+            if quantas[v] > 0 {
+                troco.push((d, quantas[v]));
+                v -= quantas[v] as usize * (d / unidade) as usize;
+            }
+        }
+        for &(d, quantidade) in &troco {
+            *self.estoque.get_mut(&d).expect("veio do estoque") -= quantidade;
+        }
+        Ok(troco)
+    }
+
+    // Recebe o pagamento em moedas, guarda na gaveta e devolve o troco. Se
+    // não houver troco possível, o pagamento é devolvido e nada muda.
+    pub fn receber(&mut self, preco: Money, pagamento: &[(u32, u32)]) -> Result<Troco, ErroDinheiro> {
+        let mut depois = self.clone();
+        for &(d, quantidade) in pagamento {
+            depois.depositar(d, quantidade)?;
+        }
+        let pago = depois.total().subtrair(self.total())?;
+        if pago.comparar(&preco)? == Ordering::Less {
+            return Err(ErroDinheiro::PagamentoInsuficiente { preco, pago });
+        }
+        let moedas = depois.dar_troco(pago.subtrair(preco)?)?;
+        *self = depois;
+        Ok(moedas)
+    }
+}
+
+// ---------- Coleção de quarters ----------
+
+// Quantos quarters de cada estado o colecionador tem
+#[derive(Debug, Clone, Default)]
+pub struct Colecao {
+    quarters: BTreeMap<UsState, u32>,
+    // Moedas sem estado, que não contam para a coleção
+    outras: u32,
+}
+
+impl Colecao {
+    pub fn new() -> Colecao {
+        Colecao::default()
+    }
+
+    pub fn adicionar(&mut self, moeda: CoinWithState) {
+        match moeda {
+            CoinWithState::Quarter(estado) => *self.quarters.entry(estado).or_insert(0) += 1,
+            _ => self.outras += 1,
+        }
+    }
+
+    pub fn quantidade(&self, estado: UsState) -> u32 {
+        self.quarters.get(&estado).copied().unwrap_or(0)
+    }
+
+    pub fn faltando(&self) -> Vec<UsState> {
+        UsState::TODOS.into_iter().filter(|estado| !self.quarters.contains_key(estado)).collect()
+    }
+
+    // Repetidas: tudo além do primeiro quarter de cada estado
+    pub fn repetidas(&self) -> u32 {
+        self.quarters.values().map(|quantidade| quantidade - 1).sum()
+    }
+
+    pub fn mais_comum(&self) -> Option<(UsState, u32)> {
+        // max_by_key devolve o último dos empatados; rev() faz valer o primeiro estado
+        self.quarters.iter().rev().max_by_key(|(_, &quantidade)| quantidade).map(|(&estado, &quantidade)| (estado, quantidade))
+    }
+
+    pub fn valor_de_face(&self) -> Money {
+        let quarters: u32 = self.quarters.values().sum();
+        Money::new(quarters as i64 * Coin::Quarter.centavos() as i64, Currency::Usd)
+    }
+}
+
+impl fmt::Display for Colecao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let completos = UsState::TODOS.len() - self.faltando().len();
+        writeln!(f, "Coleção: {}/{} estados ({:.0}%), valor de face {}",
+            completos,
+            UsState::TODOS.len(),
+            completos as f64 * 100.0 / UsState::TODOS.len() as f64,
+            self.valor_de_face())?;
+        for estado in UsState::TODOS {
+            writeln!(f, "  {:<12} {}", format!("{:?}", estado), "●".repeat(self.quantidade(estado) as usize))?;
+        }
+        write!(f, "  {} repetidas, {} moedas sem estado", self.repetidas(), self.outras)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_money() {
+        let preco: Money = "R$ 10,00".parse().unwrap();
+        assert_eq!(preco, Money::new(1000, Currency::Brl));
+        assert_eq!("1.5 USD".parse::<Money>().unwrap().to_string(), "$1.50");
+        assert_eq!("-$0.07".parse::<Money>().unwrap().to_string(), "-$0.07");
+        assert!("R$ 1,234".parse::<Money>().is_err());
+        assert!("10 EUR".parse::<Money>().is_err());
+
+        // 0.1 + 0.2 com f64 dá 0.30000000000000004; em centavos é exato
+        let soma = Money::new(10, Currency::Usd).somar(Money::new(20, Currency::Usd)).unwrap();
+        assert_eq!(soma, Money::new(30, Currency::Usd));
+        assert_eq!(
+            preco.somar(soma),
+            Err(ErroDinheiro::MoedasDiferentes { esquerda: Currency::Brl, direita: Currency::Usd })
+        );
+        let partes: Vec<String> = preco.repartir(3).iter().map(|parte| parte.to_string()).collect();
+        assert_eq!(partes, ["R$ 3,34", "R$ 3,33", "R$ 3,33"]);
+        assert_eq!(Money::new(i64::MAX, Currency::Usd).multiplicar(2), Err(ErroDinheiro::Estouro));
+
+        assert_eq!(soma.comparar(&Money::new(-5, Currency::Usd)), Ok(Ordering::Greater));
+        assert_eq!(soma.comparar(&Money::new(30, Currency::Usd)), Ok(Ordering::Equal));
+        assert_eq!(
+            preco.comparar(&soma),
+            Err(ErroDinheiro::MoedasDiferentes { esquerda: Currency::Brl, direita: Currency::Usd })
+        );
+    }
+
+    #[test]
+    fn test_troco() {
+        let eua = Denominacoes::eua();
+        let valor = Money::new(68, Currency::Usd);
+        assert_eq!(eua.troco_guloso(valor), Ok(vec![(25, 2), (10, 1), (5, 1), (1, 3)]));
+        assert_eq!(eua.troco_otimo(valor), eua.troco_guloso(valor));
+        assert!(eua.eh_canonico());
+        assert!(Denominacoes::real().eh_canonico());
+
+        let estranho = Denominacoes::new(Currency::Usd, &[4, 3, 1]).unwrap();
+        let seis = Money::new(6, Currency::Usd);
+        assert_eq!(estranho.troco_guloso(seis), Ok(vec![(4, 1), (1, 2)]));
+        assert_eq!(estranho.troco_otimo(seis), Ok(vec![(3, 2)]));
+        assert!(!estranho.eh_canonico());
+
+        let real = Denominacoes::real();
+        assert_eq!(real.troco_otimo(Money::new(3, Currency::Brl)), Err(ErroDinheiro::SemTroco(Money::new(3, Currency::Brl))));
+        assert_eq!(real.troco_otimo(Money::new(385, Currency::Brl)), Ok(vec![(200, 1), (100, 1), (50, 1), (25, 1), (10, 1)]));
+    }
+
+    #[test]
+    fn test_troco_grande() {
+        // Canônico: o guloso responde sem tabela, para qualquer valor
+        let milhoes = Money::new(1_000_000_000, Currency::Usd);
+        assert_eq!(Denominacoes::eua().troco_otimo(milhoes), Ok(vec![(25, 40_000_000)]));
+
+        // Fora dele, a tabela vai até TROCO_MAXIMO unidades do mdc
+        let estranho = Denominacoes::new(Currency::Usd, &[40, 30, 10]).unwrap();
+        assert!(!estranho.eh_canonico());
+        let no_limite = Money::new(TROCO_MAXIMO as i64 * 10, Currency::Usd);
+        assert_eq!(quantidade_de_moedas(&estranho.troco_otimo(no_limite).unwrap()), TROCO_MAXIMO / 4);
+        let acima = Money::new(TROCO_MAXIMO as i64 * 10 + 10, Currency::Usd);
+        assert_eq!(estranho.troco_otimo(acima), Err(ErroDinheiro::TrocoGrande { valor: acima, maximo: no_limite }));
+        assert_eq!(estranho.troco_otimo(Money::new(65, Currency::Usd)), Err(ErroDinheiro::SemTroco(Money::new(65, Currency::Usd))));
+        // Sem moeda de uma unidade o teorema não vale, e o sistema não é tratado como canônico
+        assert!(!Denominacoes::new(Currency::Usd, &[3, 5]).unwrap().eh_canonico());
+        assert_eq!(Denominacoes::new(Currency::Usd, &[3, 5]).unwrap().troco_otimo(Money::new(9, Currency::Usd)), Ok(vec![(3, 3)]));
+
+        let mut caixa = Caixa::new(Denominacoes::eua());
+        caixa.depositar(1, 200_000).unwrap();
+        caixa.depositar(25, 1_000).unwrap();
+        let troco = caixa.dar_troco(Money::new(TROCO_MAXIMO as i64, Currency::Usd)).unwrap();
+        assert_eq!(troco, vec![(25, 1_000), (1, 75_000)]);
+        assert!(matches!(
+            caixa.dar_troco(Money::new(TROCO_MAXIMO as i64 + 1, Currency::Usd)),
+            Err(ErroDinheiro::TrocoGrande { .. })
+        ));
+    }
+
+    #[test]
+    fn test_caixa() {
+        let mut caixa = Caixa::new(Denominacoes::eua());
+        caixa.depositar(25, 1).unwrap();
+        caixa.depositar(10, 3).unwrap();
+        caixa.depositar(1, 2).unwrap();
+        assert_eq!(caixa.depositar(50, 1), Err(ErroDinheiro::DenominacaoInvalida(50)));
+
+        // Só um quarter na gaveta: 30 centavos saem como 3 dimes
+        assert_eq!(caixa.dar_troco(Money::new(30, Currency::Usd)), Ok(vec![(10, 3)]));
+        assert_eq!(caixa.total(), Money::new(27, Currency::Usd));
+
+        // Preço 70, paga com 3 quarters: troco de 5 não existe (não há nickel)
+        let preco = Money::new(70, Currency::Usd);
+        assert_eq!(caixa.receber(preco, &[(25, 3)]), Err(ErroDinheiro::SemTroco(Money::new(5, Currency::Usd))));
+        assert_eq!(caixa.total(), Money::new(27, Currency::Usd));
+        // Preço 73: troco de 2 pennies, e os quarters ficam na gaveta
+        assert_eq!(caixa.receber(Money::new(73, Currency::Usd), &[(25, 3)]), Ok(vec![(1, 2)]));
+        assert_eq!(caixa.quantidade(25), 4);
+        assert!(matches!(caixa.receber(preco, &[(10, 1)]), Err(ErroDinheiro::PagamentoInsuficiente { .. })));
+        // Pagar em outra moeda não chega a comparar valores
+        let em_reais = Money::new(10, Currency::Brl);
+        assert_eq!(
+            caixa.receber(em_reais, &[(25, 1)]),
+            Err(ErroDinheiro::MoedasDiferentes { esquerda: Currency::Usd, direita: Currency::Brl })
+        );
+    }
+
+    #[test]
+    fn test_colecao() {
+        let mut colecao = Colecao::new();
+        for moeda in [
+            CoinWithState::Quarter(UsState::Texas),
+            CoinWithState::Quarter(UsState::Alaska),
+            CoinWithState::Dime,
+            CoinWithState::Quarter(UsState::Texas),
+        ] {
+            colecao.adicionar(moeda);
+        }
+        assert_eq!(colecao.quantidade(UsState::Texas), 2);
+        assert_eq!(colecao.faltando(), [UsState::Alabama, UsState::California, UsState::NewYork]);
+        assert_eq!(colecao.repetidas(), 1);
+        assert_eq!(colecao.mais_comum(), Some((UsState::Texas, 2)));
+        assert_eq!(colecao.valor_de_face(), Money::new(75, Currency::Usd));
+        assert!(colecao.to_string().starts_with("Coleção: 2/5 estados (40%), valor de face $0.75"));
+    }
+}
@@ -1,33 +1,12 @@
-#[derive(Debug)]
-enum IpAddr {
-    V4(u8, u8, u8, u8),
-    V6(String),
-}
-
-#[derive(Debug)]
-enum Message {
-    Quit,
-    Move { x: i32, y: i32 },
-    Write(String),
-    ChangeColor(i32, i32, i32),
-}
+mod dinheiro;
+mod ip;
+mod maquina;
+mod mensagem;
 
-#[derive(Debug)]
-enum Coin {
-    Penny,
-    Nickel,
-    Dime,
-    Quarter,
-}
-
-#[derive(Debug)]
-enum UsState {
-    Alabama,
-    Alaska,
-    California,
-    NewYork,
-    Texas,
-}
+use dinheiro::{Caixa, Coin, CoinWithState, Colecao, Currency, Denominacoes, Money, UsState};
+use ip::{IpAddr, Rede};
+use maquina::{Maquina, Nomeado};
+use mensagem::Message;
 
 impl Message {
     fn call(&self) {
@@ -49,10 +28,12 @@ fn main() {
     
     // Usando enums simples
     let home = IpAddr::V4(127, 0, 0, 1);
-    let loopback = IpAddr::V6(String::from("::1"));
+    let loopback: IpAddr = "0:0:0:0:0:0:0:1".parse().expect("endereço válido");
     
     println!("Home IP: {:?}", home);
-    println!("Loopback IP: {:?}", loopback);
+    println!("Loopback IP: {:?} = {}", loopback, loopback);
+    
+    demonstrar_enderecos_ip();
     
     // Usando enums com dados
     let quit = Message::Quit;
@@ -71,6 +52,8 @@ fn main() {
     write_msg.process();
     color_msg.process();
     
+    demonstrar_protocolo(&[quit, move_msg, write_msg, color_msg]);
+    
     // Trabalhando com Option
     let some_number = Some(5);
     let some_string = Some("hello");
@@ -125,37 +108,138 @@ fn main() {
     println!("Moeda da Califórnia: {}", value_in_cents_with_state(quarter_ca));
     println!("Moeda de Nova York: {}", value_in_cents_with_state(quarter_ny));
     
-    // Demonstração de enum como estado
-    println!("\n--- Enum como Estado ---");
-    let mut game_state = GameState::Menu;
-    println!("Estado inicial: {:?}", game_state);
+    demonstrar_dinheiro();
     
-    game_state = GameState::Playing { score: 100, level: 1 };
-    println!("Estado de jogo: {:?}", game_state);
+    // Demonstração de enum como estado: só as transições da tabela são aceitas
+    println!("\n--- Enum como Estado ---");
+    let mut jogo = maquina_do_jogo();
+    println!("Estado inicial: {:?}", jogo.estado());
     
-    game_state = GameState::Paused;
-    println!("Estado pausado: {:?}", game_state);
+    let eventos = [
+        EventoJogo::Iniciar,
+        EventoJogo::Pontuar(600),
+        EventoJogo::SubirNivel,
+        EventoJogo::Pontuar(900),
+        EventoJogo::SubirNivel,
+        EventoJogo::Pausar,
+        EventoJogo::Pontuar(50),
+        EventoJogo::Continuar,
+        EventoJogo::Perder,
+        EventoJogo::Pausar,
+        EventoJogo::VoltarAoMenu,
+    ];
+    for evento in eventos {
+        match jogo.disparar(evento) {
+            Ok(estado) => println!("  -> {:?}", estado),
+            Err(e) => println!("  Erro: {}", e),
+        }
+    }
     
-    game_state = GameState::GameOver { final_score: 1500 };
-    println!("Estado final: {:?}", game_state);
-}
-
-#[derive(Debug)]
-enum CoinWithState {
-    Penny,
-    Nickel,
-    Dime,
-    Quarter(UsState),
+    println!("\nHistórico:");
+    for (i, registro) in jogo.historico().iter().enumerate() {
+        println!("  {:>2}. {}", i + 1, registro);
+    }
+    println!("\nDiagrama (Graphviz):\n{}", jogo.para_dot("jogo"));
 }
 
 #[derive(Debug)]
 enum GameState {
     Menu,
     Playing { score: u32, level: u32 },
-    Paused,
+    // Guarda o jogo para continuar de onde parou
+    Paused { score: u32, level: u32 },
     GameOver { final_score: u32 },
 }
 
+#[derive(Debug)]
+enum EventoJogo {
+    Iniciar,
+    Pontuar(u32),
+    SubirNivel,
+    Pausar,
+    Continuar,
+    Perder,
+    VoltarAoMenu,
+}
+
+impl Nomeado for GameState {
+    fn nome(&self) -> &'static str {
+        match self {
+            GameState::Menu => "Menu",
+            GameState::Playing { .. } => "Playing",
+            GameState::Paused { .. } => "Paused",
+            GameState::GameOver { .. } => "GameOver",
+        }
+    }
+}
+
+impl Nomeado for EventoJogo {
+    fn nome(&self) -> &'static str {
+        match self {
+            EventoJogo::Iniciar => "Iniciar",
+            EventoJogo::Pontuar(_) => "Pontuar",
+            EventoJogo::SubirNivel => "SubirNivel",
+            EventoJogo::Pausar => "Pausar",
+            EventoJogo::Continuar => "Continuar",
+            EventoJogo::Perder => "Perder",
+            EventoJogo::VoltarAoMenu => "VoltarAoMenu",
+        }
+    }
+}
+
+// As transições de `GameState` permitidas; GameOver -> Paused, por exemplo, não existe
+fn maquina_do_jogo() -> Maquina<GameState, EventoJogo> {
+    // Cada ação só é chamada no estado de origem da sua transição
+    fn placar(estado: &GameState) -> (u32, u32) {
+        match *estado {
+            GameState::Playing { score, level } | GameState::Paused { score, level } => (score, level),
+            _ => unreachable!("só Playing e Paused têm placar"),
+        }
+    }
+
+    Maquina::new(GameState::Menu)
+        .transicao("Menu", "Iniciar", "Playing", |_, _| GameState::Playing { score: 0, level: 1 })
+        .transicao("Playing", "Pontuar", "Playing", |estado, evento| {
+            let (score, level) = placar(estado);
+            let pontos = match evento {
+                EventoJogo::Pontuar(pontos) => *pontos,
+                _ => 0,
+            };
+            GameState::Playing { score: score.saturating_add(pontos), level }
+        })
+        .transicao_com_guarda(
+            "Playing",
+            "SubirNivel",
+            "Playing",
+            ("score >= 1000 * level", |estado: &GameState, _: &EventoJogo| {
+                let (score, level) = placar(estado);
+                score >= 1000 * level
+            }),
+            |estado, _| {
+                let (score, level) = placar(estado);
+                GameState::Playing { score, level: level + 1 }
+            },
+        )
+        .transicao("Playing", "Pausar", "Paused", |estado, _| {
+            let (score, level) = placar(estado);
+            GameState::Paused { score, level }
+        })
+        .transicao("Paused", "Continuar", "Playing", |estado, _| {
+            let (score, level) = placar(estado);
+            GameState::Playing { score, level }
+        })
+        .transicao("Playing", "Perder", "GameOver", |estado, _| GameState::GameOver { final_score: placar(estado).0 })
+        .transicao("Paused", "VoltarAoMenu", "Menu", |_, _| GameState::Menu)
+        .transicao("GameOver", "VoltarAoMenu", "Menu", |_, _| GameState::Menu)
+        .ao_entrar("Playing", |estado| println!("  [entrando no jogo: {:?}]", estado))
+        .ao_sair("Playing", |_| println!("  [saindo do jogo]"))
+        .ao_entrar("GameOver", |estado| {
+            if let GameState::GameOver { final_score } = estado {
+                println!("  [fim de jogo! pontuação final: {}]", final_score);
+            }
+        })
+}
+
 fn value_in_cents(coin: Coin) -> u8 {
     match coin {
         Coin::Penny => {
@@ -180,6 +264,157 @@ fn value_in_cents_with_state(coin: CoinWithState) -> u8 {
     }
 }
 
+// Valores sem arredondamento, troco em dólar e em real, caixa e coleção de quarters
+fn demonstrar_dinheiro() {
+    println!("\n--- Dinheiro ---");
+    let total = Coin::TODAS
+        .iter()
+        .map(|coin| Money::new(coin.centavos() as i64, Currency::Usd))
+        .try_fold(Money::zero(Currency::Usd), |soma, valor| soma.somar(valor));
+    println!("Uma moeda de cada: {}", total.expect("cabe em i64"));
+
+    let conta: Money = "R$ 100,00".parse().expect("valor válido");
+    let partes: Vec<String> = conta.repartir(3).iter().map(|parte| parte.to_string()).collect();
+    println!("{} dividido por 3: {}", conta, partes.join(" + "));
+    let dobro = conta.multiplicar(2).expect("cabe em i64");
+    println!("O dobro: {} = {} centavos de {:?}", dobro, dobro.centavos(), dobro.moeda());
+    if let Err(e) = conta.somar(Money::new(100, Currency::Usd)) {
+        println!("{} + $1.00: {}", conta, e);
+    }
+
+    let valores = [("EUA", Denominacoes::eua(), 68), ("Real", Denominacoes::real(), 385), ("Real", Denominacoes::real(), 7)];
+    for (nome, denominacoes, centavos) in valores {
+        let valor = Money::new(centavos, denominacoes.moeda());
+        match denominacoes.troco_otimo(valor) {
+            Ok(troco) => println!("Troco de {} ({}): {:?}, {} moedas", valor, nome, troco, dinheiro::quantidade_de_moedas(&troco)),
+            Err(e) => println!("Troco de {} ({}): {}", valor, nome, e),
+        }
+    }
+
+    // Com {1, 3, 4} o guloso erra: 6 = 4 + 1 + 1, mas 3 + 3 usa menos moedas
+    let estranho = Denominacoes::new(Currency::Usd, &[1, 3, 4]).expect("denominações válidas");
+    let seis = Money::new(6, Currency::Usd);
+    println!("{:?} é canônico? {}", estranho.valores(), estranho.eh_canonico());
+    println!("  guloso: {:?}", estranho.troco_guloso(seis));
+    println!("  ótimo:  {:?}", estranho.troco_otimo(seis));
+
+    let mut caixa = Caixa::new(Denominacoes::eua());
+    for (coin, quantidade) in [(Coin::Quarter, 1), (Coin::Dime, 3), (Coin::Penny, 2)] {
+        caixa.depositar(coin.centavos(), quantidade).expect("moeda americana");
+    }
+    println!("Caixa com {} ({} dimes)", caixa.total(), caixa.quantidade(Coin::Dime.centavos()));
+    for preco in [70, 73] {
+        let preco = Money::new(preco, Currency::Usd);
+        match caixa.receber(preco, &[(Coin::Quarter.centavos(), 3)]) {
+            Ok(troco) => println!("  Venda de {} paga com 3 quarters: troco {:?}", preco, troco),
+            Err(e) => println!("  Venda de {} paga com 3 quarters: {}", preco, e),
+        }
+    }
+    if let Err(e) = caixa.receber(Money::new(70, Currency::Usd), &[(Coin::Dime.centavos(), 1)]) {
+        println!("  {}", e);
+    }
+    println!("Caixa agora com {}", caixa.total());
+
+    let mut colecao = Colecao::new();
+    for moeda in [
+        CoinWithState::Quarter(UsState::Texas),
+        CoinWithState::Quarter(UsState::California),
+        CoinWithState::Penny,
+        CoinWithState::Quarter(UsState::Texas),
+        CoinWithState::Nickel,
+        CoinWithState::Dime,
+        CoinWithState::Quarter(UsState::Alaska),
+    ] {
+        println!("Guardando {:?} ({:?})", moeda, moeda.moeda());
+        colecao.adicionar(moeda);
+    }
+    println!("{}", colecao);
+    println!("Falta: {:?}", colecao.faltando());
+    if let Some((estado, quantidade)) = colecao.mais_comum() {
+        println!("Mais comum: {:?} ({})", estado, quantidade);
+    }
+}
+
+// Leitura, forma canônica, redes e classificação de endereços IP
+fn demonstrar_enderecos_ip() {
+    println!("\n--- Endereços IP ---");
+    for texto in ["192.168.0.10", "2001:0DB8:0000:0000:0000:ff00:0042:8329", "fe80::1%eth0", "::ffff:10.0.0.1", "256.1.1.1", "1::2::3"] {
+        match texto.parse::<IpAddr>() {
+            Ok(endereco) => println!("{:<40} -> {} ({:?})", texto, endereco, endereco.categoria()),
+            Err(e) => println!("{:<40} -> erro: {}", texto, e),
+        }
+    }
+
+    let rede: Rede = "192.168.1.77/26".parse().expect("rede válida");
+    println!("\nRede {}: máscara {}, broadcast {}, {} endereços",
+        rede,
+        rede.mascara(),
+        rede.broadcast().expect("IPv4 tem broadcast"),
+        rede.tamanho());
+    for texto in ["192.168.1.100", "192.168.1.200", "::1"] {
+        let endereco: IpAddr = texto.parse().expect("endereço válido");
+        match rede.contem(&endereco) {
+            Ok(contem) => println!("  {} está na rede? {}", endereco, contem),
+            Err(e) => println!("  {}: {}", endereco, e),
+        }
+    }
+
+    let bloco: Rede = "10.0.0.0/24".parse().expect("rede válida");
+    let sub_redes: Vec<String> = bloco.sub_redes(26).expect("prefixo válido").map(|rede| rede.to_string()).collect();
+    println!("{} em /26: {}", bloco, sub_redes.join(", "));
+
+    let v6: Rede = "2001:db8:abcd:12::/64".parse().expect("rede válida");
+    println!("{} ({} bits de prefixo) vai de {} a {}", v6, v6.prefixo(), v6.endereco_de_rede(), v6.ultimo());
+
+    // Ida e volta com std::net; a zona se perde, já que std::net não a guarda
+    let com_zona: IpAddr = "fe80::1%eth0".parse().expect("endereço válido");
+    let padrao = std::net::IpAddr::from(&com_zona);
+    let de_volta = IpAddr::from(padrao);
+    println!("std::net: {} -> {} -> {} ({} bits, valor {:#x})", com_zona, padrao, de_volta, de_volta.largura(), de_volta.bits());
+    println!("Ipv4Addr::LOCALHOST: {}", IpAddr::from(std::net::Ipv4Addr::LOCALHOST));
+}
+
+// As mensagens pelos três formatos do protocolo: binário, texto e JSON
+fn demonstrar_protocolo(mensagens: &[Message]) {
+    println!("\n--- Protocolo de Mensagens ---");
+    let mut fluxo = Vec::new();
+    for mensagem in mensagens {
+        let quadro = match mensagem.codificar() {
+            Ok(quadro) => quadro,
+            Err(e) => {
+                println!("Erro: {}", e);
+                continue;
+            }
+        };
+        println!("{:<24} binário {:02x?}", mensagem.to_string(), quadro);
+        println!("{:<24} JSON    {}", "", serde_json::to_string(mensagem).expect("Message sempre vira JSON"));
+        fluxo.extend(quadro);
+    }
+
+    // Os quadros chegam grudados em um fluxo só; cada um diz o próprio tamanho
+    let mut resto = &fluxo[..];
+    while !resto.is_empty() {
+        match Message::decodificar(resto) {
+            Ok((mensagem, usados)) => {
+                processar_mensagem(mensagem);
+                resto = &resto[usados..];
+            }
+            Err(e) => {
+                println!("Erro: {}", e);
+                break;
+            }
+        }
+    }
+
+    println!("Quadro pela metade: {}", Message::decodificar(&fluxo[..3]).unwrap_err());
+    for linha in ["move 3 -4", "WRITE linha 1\\nlinha 2", "COLOR 255 0", "JUMP 1 2"] {
+        match linha.parse::<Message>() {
+            Ok(mensagem) => println!("'{}' -> {:?}", linha, mensagem),
+            Err(e) => println!("'{}' -> erro: {}", linha, e),
+        }
+    }
+}
+
 // Função que retorna Option
 fn dividir(a: f64, b: f64) -> Option<f64> {
     if b != 0.0 {
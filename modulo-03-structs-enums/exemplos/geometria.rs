@@ -0,0 +1,776 @@
+// Geometria 2D: formas que implementam o trait Shape, testes de interseção
+// e de contenção entre quaisquer duas delas, caixas delimitadoras, fecho
+// convexo e transformações afins.
+//
+// Cada forma se descreve como um Contorno (círculo, segmento ou polígono) e
+// os algoritmos genéricos trabalham em cima dele; assim `can_hold` e
+// `intersecta` funcionam para qualquer par de formas.
+
+use std::f64::consts::PI;
+use std::fmt;
+
+// Tolerância para comparações com f64: pontos a menos que isso são iguais
+const EPS: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn distancia(&self, outro: Point) -> f64 {
+        (self.x - outro.x).hypot(self.y - outro.y)
+    }
+
+    fn menos(&self, outro: Point) -> Point {
+        Point::new(self.x - outro.x, self.y - outro.y)
+    }
+
+    // O ponto a uma fração `t` do caminho até `outro`
+    fn interpolar(&self, outro: Point, t: f64) -> Point {
+        Point::new(self.x + t * (outro.x - self.x), self.y + t * (outro.y - self.y))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErroGeometria {
+    PoucosVertices(usize),
+}
+
+impl fmt::Display for ErroGeometria {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroGeometria::PoucosVertices(n) => write!(f, "um polígono precisa de pelo menos 3 vértices, não {}", n),
+        }
+    }
+}
+
+impl std::error::Error for ErroGeometria {}
+
+// ---------- Caixa delimitadora ----------
+
+// Menor retângulo alinhado aos eixos que cobre a forma. A de uma forma
+// vazia tem min > max e não se sobrepõe a nada.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Limites {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Limites {
+    fn de_pontos(pontos: &[Point]) -> Limites {
+        let mut limites = Limites {
+            min: Point::new(f64::INFINITY, f64::INFINITY),
+            max: Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY),
+        };
+        for p in pontos {
+            limites.min = Point::new(limites.min.x.min(p.x), limites.min.y.min(p.y));
+            limites.max = Point::new(limites.max.x.max(p.x), limites.max.y.max(p.y));
+        }
+        limites
+    }
+
+    pub fn largura(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    pub fn altura(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+
+    // As caixas se sobrepõem (encostar conta)? É o teste barato que vem antes
+    // dos testes exatos de interseção
+    pub fn sobrepoe(&self, outra: &Limites) -> bool {
+        self.min.x <= outra.max.x + EPS
+            && outra.min.x <= self.max.x + EPS
+            && self.min.y <= outra.max.y + EPS
+            && outra.min.y <= self.max.y + EPS
+    }
+
+    // A região comum às duas caixas
+    pub fn intersecao(&self, outra: &Limites) -> Option<Limites> {
+        if !self.sobrepoe(outra) {
+            return None;
+        }
+        Some(Limites {
+            min: Point::new(self.min.x.max(outra.min.x), self.min.y.max(outra.min.y)),
+            max: Point::new(self.max.x.min(outra.max.x), self.max.y.min(outra.max.y)),
+        })
+    }
+}
+
+// ---------- Transformações afins ----------
+
+// x' = a·x + b·y + e
+// y' = c·x + d·y + f
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Afim {
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+    e: f64,
+    f: f64,
+}
+
+impl Afim {
+    pub fn identidade() -> Afim {
+        Afim { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: 0.0, f: 0.0 }
+    }
+
+    pub fn translacao(dx: f64, dy: f64) -> Afim {
+        Afim { e: dx, f: dy, ..Afim::identidade() }
+    }
+
+    // Ângulo em radianos, anti-horário, em torno da origem
+    pub fn rotacao(angulo: f64) -> Afim {
+        let (seno, cosseno) = angulo.sin_cos();
+        Afim { a: cosseno, b: -seno, c: seno, d: cosseno, e: 0.0, f: 0.0 }
+    }
+
+    pub fn rotacao_em(centro: Point, angulo: f64) -> Afim {
+        Afim::translacao(-centro.x, -centro.y).depois(&Afim::rotacao(angulo)).depois(&Afim::translacao(centro.x, centro.y))
+    }
+
+    pub fn escala(sx: f64, sy: f64) -> Afim {
+        Afim { a: sx, d: sy, ..Afim::identidade() }
+    }
+
+    pub fn cisalhamento(kx: f64, ky: f64) -> Afim {
+        Afim { b: kx, c: ky, ..Afim::identidade() }
+    }
+
+    // Aplica `self` e em seguida `outra`
+    pub fn depois(&self, outra: &Afim) -> Afim {
+        Afim {
+            a: outra.a * self.a + outra.b * self.c,
+            b: outra.a * self.b + outra.b * self.d,
+            c: outra.c * self.a + outra.d * self.c,
+            d: outra.c * self.b + outra.d * self.d,
+            e: outra.a * self.e + outra.b * self.f + outra.e,
+            f: outra.c * self.e + outra.d * self.f + outra.f,
+        }
+    }
+
+    pub fn inversa(&self) -> Option<Afim> {
+        let det = self.determinante();
+        if det.abs() < EPS {
+            return None;
+        }
+        let (a, b, c, d) = (self.d / det, -self.b / det, -self.c / det, self.a / det);
+        Some(Afim { a, b, c, d, e: -(a * self.e + b * self.f), f: -(c * self.e + d * self.f) })
+    }
+
+    pub fn aplicar(&self, p: Point) -> Point {
+        Point::new(self.a * p.x + self.b * p.y + self.e, self.c * p.x + self.d * p.y + self.f)
+    }
+
+    fn determinante(&self) -> f64 {
+        self.a * self.d - self.b * self.c
+    }
+
+    // Rotação + escala uniforme + translação, sem espelhar: círculos
+    // continuam círculos e retângulos continuam retângulos
+    fn semelhanca(&self) -> Option<(f64, f64)> {
+        let mesma_escala = (self.a - self.d).abs() < EPS && (self.b + self.c).abs() < EPS;
+        if mesma_escala && self.determinante() > EPS {
+            Some((self.determinante().sqrt(), self.c.atan2(self.a)))
+        } else {
+            None
+        }
+    }
+}
+
+// ---------- Contorno ----------
+
+// O que os algoritmos genéricos enxergam de cada forma. Polígonos podem ser
+// côncavos, mas não podem se cruzar. Um Shape de fora pode devolver qualquer
+// lista de vértices: veja `normalizar`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Contorno {
+    Circulo(Point, f64),
+    Segmento(Point, Point),
+    Poligono(Vec<Point>),
+}
+
+impl Contorno {
+    fn vertices(&self) -> Vec<Point> {
+        match self {
+            Contorno::Circulo(centro, _) => vec![*centro],
+            Contorno::Segmento(a, b) => vec![*a, *b],
+            Contorno::Poligono(vertices) => vertices.clone(),
+        }
+    }
+
+    fn arestas(&self) -> Vec<(Point, Point)> {
+        match self {
+            Contorno::Circulo(..) => Vec::new(),
+            Contorno::Segmento(a, b) => vec![(*a, *b)],
+            Contorno::Poligono(vertices) => {
+                vertices.iter().zip(vertices.iter().cycle().skip(1)).map(|(a, b)| (*a, *b)).collect()
+            }
+        }
+    }
+
+    // Pontos na borda contam como dentro
+    fn contem_ponto(&self, p: Point) -> bool {
+        match self {
+            Contorno::Circulo(centro, raio) => centro.distancia(p) <= raio + EPS,
+            Contorno::Segmento(a, b) => no_segmento(p, *a, *b),
+            Contorno::Poligono(_) => {
+                let arestas = self.arestas();
+                if arestas.iter().any(|&(a, b)| no_segmento(p, a, b)) {
+                    return true;
+                }
+                // Raio para a direita: dentro se cruza a borda um número ímpar de vezes
+                let mut dentro = false;
+                for (a, b) in arestas {
+                    if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y) {
+                        dentro = !dentro;
+                    }
+                }
+                dentro
+            }
+        }
+    }
+
+    fn intersecta(&self, outro: &Contorno) -> bool {
+        match (self, outro) {
+            (Contorno::Circulo(c1, r1), Contorno::Circulo(c2, r2)) => c1.distancia(*c2) <= r1 + r2 + EPS,
+            (Contorno::Circulo(centro, raio), forma) | (forma, Contorno::Circulo(centro, raio)) => {
+                forma.contem_ponto(*centro)
+                    || forma.arestas().iter().any(|&(a, b)| distancia_ao_segmento(*centro, a, b) <= raio + EPS)
+            }
+            _ => {
+                let arestas = outro.arestas();
+                let bordas_se_tocam =
+                    self.arestas().iter().any(|&(a, b)| arestas.iter().any(|&(c, d)| segmentos_se_tocam(a, b, c, d)));
+                // Sem bordas se tocando, só se uma estiver inteira dentro da outra
+                let dentro = |forma: &Contorno, outra: &Contorno| outra.vertices().first().is_some_and(|&p| forma.contem_ponto(p));
+                bordas_se_tocam || dentro(self, outro) || dentro(outro, self)
+            }
+        }
+    }
+
+    // `outro` está inteiro dentro de `self` (bordas podem encostar)?
+    fn contem(&self, outro: &Contorno) -> bool {
+        match (self, outro) {
+            (Contorno::Circulo(c1, r1), Contorno::Circulo(c2, r2)) => c1.distancia(*c2) + r2 <= r1 + EPS,
+            // Círculos são convexos: basta os vértices estarem dentro
+            (Contorno::Circulo(..), forma) => forma.vertices().iter().all(|&p| self.contem_ponto(p)),
+            (Contorno::Segmento(..), Contorno::Circulo(_, raio)) => *raio <= EPS && self.contem_ponto(outro.vertices()[0]),
+            (Contorno::Segmento(..), forma) => forma.vertices().iter().all(|&p| self.contem_ponto(p)),
+            (Contorno::Poligono(_), Contorno::Circulo(centro, raio)) => {
+                self.contem_ponto(*centro)
+                    && self.arestas().iter().all(|&(a, b)| distancia_ao_segmento(*centro, a, b) >= raio - EPS)
+            }
+            (Contorno::Poligono(_), forma) => {
+                // Num polígono côncavo, uma aresta com as duas pontas dentro
+                // ainda pode sair por um "dente", mesmo passando só pelos
+                // vértices dele. Cortada em todo ponto onde toca a borda, cada
+                // pedaço fica inteiro dentro ou inteiro fora: basta o ponto médio.
+                let bordas = self.arestas();
+                forma.vertices().iter().all(|&p| self.contem_ponto(p))
+                    && forma.arestas().iter().all(|&(a, b)| {
+                        cortes(a, b, &bordas).windows(2).all(|par| self.contem_ponto(a.interpolar(b, (par[0] + par[1]) / 2.0)))
+                    })
+            }
+        }
+    }
+}
+
+// Polígonos com menos de três vértices viram o que de fato são: um ponto
+// (círculo de raio zero) ou um segmento. O vazio continua vazio e funciona
+// como uma forma sem nenhum ponto: não contém nem toca nada, e cabe em tudo.
+fn normalizar(contorno: Contorno) -> Contorno {
+    match contorno {
+        Contorno::Poligono(vertices) => match vertices[..] {
+            [p] => Contorno::Circulo(p, 0.0),
+            [a, b] => Contorno::Segmento(a, b),
+            _ => Contorno::Poligono(vertices),
+        },
+        contorno => contorno,
+    }
+}
+
+// Positivo se a → b → c gira no sentido anti-horário
+fn orientacao(a: Point, b: Point, c: Point) -> f64 {
+    let (u, v) = (b.menos(a), c.menos(a));
+    u.x * v.y - u.y * v.x
+}
+
+fn sinal(valor: f64) -> i8 {
+    if valor > EPS {
+        1
+    } else if valor < -EPS {
+        -1
+    } else {
+        0
+    }
+}
+
+fn no_segmento(p: Point, a: Point, b: Point) -> bool {
+    sinal(orientacao(a, b, p)) == 0
+        && p.x >= a.x.min(b.x) - EPS
+        && p.x <= a.x.max(b.x) + EPS
+        && p.y >= a.y.min(b.y) - EPS
+        && p.y <= a.y.max(b.y) + EPS
+}
+
+// Os segmentos se cruzam num ponto interior aos dois (encostar não conta)
+fn cruzam_propriamente(a: Point, b: Point, c: Point, d: Point) -> bool {
+    sinal(orientacao(c, d, a)) * sinal(orientacao(c, d, b)) < 0 && sinal(orientacao(a, b, c)) * sinal(orientacao(a, b, d)) < 0
+}
+
+fn segmentos_se_tocam(a: Point, b: Point, c: Point, d: Point) -> bool {
+    cruzam_propriamente(a, b, c, d) || no_segmento(a, c, d) || no_segmento(b, c, d) || no_segmento(c, a, b) || no_segmento(d, a, b)
+}
+
+// Frações de a → b (de 0 a 1, em ordem) onde o segmento toca alguma das
+// `bordas`: as pontas, os cruzamentos e os vértices da borda sobre ele
+fn cortes(a: Point, b: Point, bordas: &[(Point, Point)]) -> Vec<f64> {
+    let ab = b.menos(a);
+    let comprimento = ab.x * ab.x + ab.y * ab.y;
+    let mut fracoes = vec![0.0, 1.0];
+    if comprimento < EPS {
+        return fracoes;
+    }
+    for &(c, d) in bordas {
+        for p in [c, d] {
+            if no_segmento(p, a, b) {
+                let ap = p.menos(a);
+                fracoes.push(((ap.x * ab.x + ap.y * ab.y) / comprimento).clamp(0.0, 1.0));
+            }
+        }
+        if cruzam_propriamente(a, b, c, d) {
+            let (oa, ob) = (orientacao(c, d, a), orientacao(c, d, b));
+            fracoes.push(oa / (oa - ob));
+        }
+    }
+    fracoes.sort_by(f64::total_cmp);
+    fracoes.dedup_by(|t, anterior| *t - *anterior < EPS);
+    fracoes
+}
+
+fn distancia_ao_segmento(p: Point, a: Point, b: Point) -> f64 {
+    let (ab, ap) = (b.menos(a), p.menos(a));
+    let comprimento = ab.x * ab.x + ab.y * ab.y;
+    if comprimento < EPS {
+        return p.distancia(a);
+    }
+    let t = ((ap.x * ab.x + ap.y * ab.y) / comprimento).clamp(0.0, 1.0);
+    p.distancia(Point::new(a.x + t * ab.x, a.y + t * ab.y))
+}
+
+// ---------- Shape ----------
+
+pub trait Shape: fmt::Debug {
+    fn contorno(&self) -> Contorno;
+
+    fn area(&self) -> f64 {
+        match normalizar(self.contorno()) {
+            Contorno::Circulo(_, raio) => PI * raio * raio,
+            Contorno::Segmento(..) => 0.0,
+            // Fórmula do laço (shoelace)
+            contorno => contorno.arestas().iter().map(|(a, b)| a.x * b.y - b.x * a.y).sum::<f64>().abs() / 2.0,
+        }
+    }
+
+    // Num segmento, o comprimento
+    fn perimeter(&self) -> f64 {
+        match normalizar(self.contorno()) {
+            Contorno::Circulo(_, raio) => 2.0 * PI * raio,
+            Contorno::Segmento(a, b) => a.distancia(b),
+            contorno => contorno.arestas().iter().map(|(a, b)| a.distancia(*b)).sum(),
+        }
+    }
+
+    fn limites(&self) -> Limites {
+        match normalizar(self.contorno()) {
+            Contorno::Circulo(centro, raio) => Limites {
+                min: Point::new(centro.x - raio, centro.y - raio),
+                max: Point::new(centro.x + raio, centro.y + raio),
+            },
+            contorno => Limites::de_pontos(&contorno.vertices()),
+        }
+    }
+
+    fn contem_ponto(&self, p: Point) -> bool {
+        normalizar(self.contorno()).contem_ponto(p)
+    }
+
+    // Têm algum ponto em comum, inclusive só na borda?
+    fn intersecta(&self, outra: &dyn Shape) -> bool {
+        self.limites().sobrepoe(&outra.limites()) && normalizar(self.contorno()).intersecta(&normalizar(outra.contorno()))
+    }
+
+    // `outra`, onde está, cabe inteira dentro desta forma. Para perguntar se
+    // cabe em outra posição, mova antes com `transformar`.
+    fn can_hold(&self, outra: &dyn Shape) -> bool {
+        normalizar(self.contorno()).contem(&normalizar(outra.contorno()))
+    }
+
+    // Círculos só continuam círculos em semelhanças (rotação, escala
+    // uniforme, translação); nos outros casos viram um polígono de 64 lados
+    // aproximando a elipse
+    fn transformar(&self, t: &Afim) -> Box<dyn Shape> {
+        match self.contorno() {
+            Contorno::Circulo(centro, raio) => match t.semelhanca() {
+                Some((escala, _)) => Box::new(Circle { centro: t.aplicar(centro), raio: raio * escala }),
+                None => {
+                    let vertices = (0..64)
+                        .map(|i| {
+                            let angulo = 2.0 * PI * i as f64 / 64.0;
+                            t.aplicar(Point::new(centro.x + raio * angulo.cos(), centro.y + raio * angulo.sin()))
+                        })
+                        .collect();
+                    Box::new(Polygon { vertices })
+                }
+            },
+            Contorno::Segmento(a, b) => Box::new(Segment { a: t.aplicar(a), b: t.aplicar(b) }),
+            Contorno::Poligono(vertices) => Box::new(Polygon { vertices: vertices.into_iter().map(|p| t.aplicar(p)).collect() }),
+        }
+    }
+}
+
+// ---------- Formas ----------
+
+// Retângulo alinhado aos eixos com o canto inferior esquerdo na origem
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rectangle {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rectangle {
+    pub fn square(size: f64) -> Rectangle {
+        Rectangle { width: size, height: size }
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.width == self.height
+    }
+}
+
+impl Shape for Rectangle {
+    fn contorno(&self) -> Contorno {
+        Contorno::Poligono(vec![
+            Point::new(0.0, 0.0),
+            Point::new(self.width, 0.0),
+            Point::new(self.width, self.height),
+            Point::new(0.0, self.height),
+        ])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RotatedRectangle {
+    pub center: Point,
+    pub width: f64,
+    pub height: f64,
+    // Em radianos, anti-horário
+    pub angle: f64,
+}
+
+impl Shape for RotatedRectangle {
+    fn contorno(&self) -> Contorno {
+        let girar = Afim::rotacao(self.angle).depois(&Afim::translacao(self.center.x, self.center.y));
+        let (w, h) = (self.width / 2.0, self.height / 2.0);
+        Contorno::Poligono([(-w, -h), (w, -h), (w, h), (-w, h)].iter().map(|&(x, y)| girar.aplicar(Point::new(x, y))).collect())
+    }
+
+    fn transformar(&self, t: &Afim) -> Box<dyn Shape> {
+        match t.semelhanca() {
+            Some((escala, angulo)) => Box::new(RotatedRectangle {
+                center: t.aplicar(self.center),
+                width: self.width * escala,
+                height: self.height * escala,
+                angle: self.angle + angulo,
+            }),
+            // Cisalhamento ou escala desigual: vira um paralelogramo
+            None => Box::new(Polygon { vertices: self.contorno().vertices().into_iter().map(|p| t.aplicar(p)).collect() }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circle {
+    pub centro: Point,
+    pub raio: f64,
+}
+
+impl Shape for Circle {
+    fn contorno(&self) -> Contorno {
+        Contorno::Circulo(self.centro, self.raio)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Triangle {
+    pub a: Point,
+    pub b: Point,
+    pub c: Point,
+}
+
+impl Shape for Triangle {
+    fn contorno(&self) -> Contorno {
+        Contorno::Poligono(vec![self.a, self.b, self.c])
+    }
+
+    // Transformação afim leva triângulo em triângulo
+    fn transformar(&self, t: &Afim) -> Box<dyn Shape> {
+        Box::new(Triangle { a: t.aplicar(self.a), b: t.aplicar(self.b), c: t.aplicar(self.c) })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub a: Point,
+    pub b: Point,
+}
+
+impl Shape for Segment {
+    fn contorno(&self) -> Contorno {
+        Contorno::Segmento(self.a, self.b)
+    }
+}
+
+// Polígono simples (as arestas não se cruzam), côncavo ou convexo, com os
+// vértices em qualquer sentido
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point>) -> Result<Polygon, ErroGeometria> {
+        if vertices.len() < 3 {
+            return Err(ErroGeometria::PoucosVertices(vertices.len()));
+        }
+        Ok(Polygon { vertices })
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    pub fn eh_convexo(&self) -> bool {
+        let n = self.vertices.len();
+        let sinais: Vec<i8> = (0..n)
+            .map(|i| sinal(orientacao(self.vertices[i], self.vertices[(i + 1) % n], self.vertices[(i + 2) % n])))
+            .filter(|&s| s != 0)
+            .collect();
+        sinais.iter().all(|&s| s == sinais[0])
+    }
+}
+
+impl Shape for Polygon {
+    fn contorno(&self) -> Contorno {
+        Contorno::Poligono(self.vertices.clone())
+    }
+}
+
+// ---------- Fecho convexo ----------
+
+// Menor polígono convexo que cobre os pontos (cadeia monótona de Andrew),
+// com os vértices no sentido anti-horário. None se os pontos forem colineares.
+pub fn envoltoria_convexa(pontos: &[Point]) -> Option<Polygon> {
+    let mut pontos = pontos.to_vec();
+    pontos.sort_by(|p, q| p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y)));
+    pontos.dedup_by(|p, q| p.distancia(*q) < EPS);
+
+    // Metade de baixo da esquerda para a direita, depois a de cima de volta
+    let mut fecho: Vec<Point> = Vec::new();
+    for passada in [pontos.clone(), pontos.into_iter().rev().collect()] {
+        let inicio = fecho.len();
+        for p in passada {
+            while fecho.len() >= inicio + 2 && orientacao(fecho[fecho.len() - 2], fecho[fecho.len() - 1], p) <= EPS {
+                fecho.pop();
+            }
+            fecho.push(p);
+        }
+        // O último ponto de cada metade é o primeiro da outra
+        fecho.pop();
+    }
+    Polygon::new(fecho).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn perto(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    fn quadrado(x: f64, y: f64, lado: f64) -> Polygon {
+        let pontos = [(x, y), (x + lado, y), (x + lado, y + lado), (x, y + lado)];
+        Polygon::new(pontos.iter().map(|&(x, y)| Point::new(x, y)).collect()).unwrap()
+    }
+
+    #[test]
+    fn test_medidas() {
+        let retangulo = Rectangle { width: 30.0, height: 50.0 };
+        assert_eq!(retangulo.area(), 1500.0);
+        assert_eq!(retangulo.perimeter(), 160.0);
+        assert!(perto(Circle { centro: Point::new(1.0, 1.0), raio: 2.0 }.area(), 4.0 * PI));
+        let triangulo = Triangle { a: Point::new(0.0, 0.0), b: Point::new(4.0, 0.0), c: Point::new(0.0, 3.0) };
+        assert_eq!((triangulo.area(), triangulo.perimeter()), (6.0, 12.0));
+
+        let girado = RotatedRectangle { center: Point::new(0.0, 0.0), width: 2.0, height: 2.0, angle: PI / 4.0 };
+        assert!(perto(girado.area(), 4.0));
+        assert!(perto(girado.limites().max.x, 2f64.sqrt()));
+        assert_eq!(Polygon::new(vec![Point::new(0.0, 0.0)]), Err(ErroGeometria::PoucosVertices(1)));
+    }
+
+    #[test]
+    fn test_intersecao_e_contencao() {
+        let circulo = Circle { centro: Point::new(5.0, 5.0), raio: 2.0 };
+        let grande = quadrado(0.0, 0.0, 10.0);
+        let longe = quadrado(20.0, 20.0, 1.0);
+        let encostado = quadrado(10.0, 0.0, 5.0);
+        let segmento = Segment { a: Point::new(-1.0, 5.0), b: Point::new(3.5, 5.0) };
+
+        assert!(grande.intersecta(&circulo));
+        assert!(!grande.intersecta(&longe));
+        assert!(grande.intersecta(&encostado));
+        assert!(segmento.intersecta(&circulo));
+        assert!(!segmento.intersecta(&Circle { centro: Point::new(5.0, 7.5), raio: 2.0 }));
+
+        assert!(grande.can_hold(&circulo));
+        assert!(!circulo.can_hold(&grande));
+        assert!(!grande.can_hold(&segmento));
+        assert!(Circle { centro: Point::new(5.0, 5.0), raio: 8.0 }.can_hold(&grande));
+
+        // Os retângulos da aula, ancorados na origem
+        let rect1 = Rectangle { width: 30.0, height: 50.0 };
+        assert!(rect1.can_hold(&Rectangle { width: 10.0, height: 40.0 }));
+        assert!(!Rectangle::square(20.0).can_hold(&rect1));
+
+        // Um "U": o segmento que atravessa a abertura tem as pontas dentro, mas sai
+        let u = Polygon::new(
+            [(0.0, 0.0), (6.0, 0.0), (6.0, 6.0), (4.0, 6.0), (4.0, 2.0), (2.0, 2.0), (2.0, 6.0), (0.0, 6.0)]
+                .iter()
+                .map(|&(x, y)| Point::new(x, y))
+                .collect(),
+        )
+        .unwrap();
+        assert!(!u.eh_convexo());
+        assert!(u.contem_ponto(Point::new(1.0, 5.0)));
+        assert!(!u.contem_ponto(Point::new(3.0, 5.0)));
+        assert!(u.contem_ponto(Point::new(3.0, 2.0)));
+        assert!(!u.can_hold(&Segment { a: Point::new(1.0, 5.0), b: Point::new(5.0, 5.0) }));
+        assert!(u.can_hold(&Segment { a: Point::new(1.0, 1.0), b: Point::new(5.0, 1.0) }));
+
+        // Dois entalhes, cada um ligado à borda por um canal, que a diagonal
+        // atravessa entrando e saindo pelos cantos: sem nenhum cruzamento
+        // próprio e com o ponto médio (10, 10) dentro, mas passa por fora
+        let entalhado = Polygon::new(
+            [
+                (0.0, 0.0),
+                (20.0, 0.0),
+                (20.0, 4.0),
+                (6.0, 4.0),
+                (4.0, 4.0),
+                (4.0, 6.0),
+                (6.0, 6.0),
+                (6.0, 5.0),
+                (20.0, 5.0),
+                (20.0, 20.0),
+                (0.0, 20.0),
+                (0.0, 16.0),
+                (14.0, 16.0),
+                (16.0, 16.0),
+                (16.0, 14.0),
+                (14.0, 14.0),
+                (14.0, 15.0),
+                (0.0, 15.0),
+            ]
+            .iter()
+            .map(|&(x, y)| Point::new(x, y))
+            .collect(),
+        )
+        .unwrap();
+        assert!(!entalhado.contem_ponto(Point::new(5.0, 5.0)));
+        assert!(entalhado.contem_ponto(Point::new(10.0, 10.0)));
+        assert!(!entalhado.can_hold(&Segment { a: Point::new(0.5, 0.5), b: Point::new(19.5, 19.5) }));
+        assert!(entalhado.can_hold(&Segment { a: Point::new(0.5, 0.5), b: Point::new(3.5, 3.5) }));
+        // Encostar na borda ao longo de uma aresta continua valendo
+        assert!(entalhado.can_hold(&Segment { a: Point::new(0.0, 0.0), b: Point::new(20.0, 0.0) }));
+        assert!(entalhado.can_hold(&quadrado(7.0, 6.0, 6.0)));
+    }
+
+    // Shapes de fora do módulo podem devolver qualquer lista de vértices
+    #[derive(Debug)]
+    struct Pontos(Vec<Point>);
+
+    impl Shape for Pontos {
+        fn contorno(&self) -> Contorno {
+            Contorno::Poligono(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn test_contornos_degenerados() {
+        let grande = quadrado(0.0, 0.0, 10.0);
+        let circulo = Circle { centro: Point::new(5.0, 5.0), raio: 2.0 };
+
+        let vazio = Pontos(Vec::new());
+        assert_eq!((vazio.area(), vazio.perimeter()), (0.0, 0.0));
+        assert!(!vazio.limites().sobrepoe(&grande.limites()));
+        assert!(!vazio.contem_ponto(Point::new(0.0, 0.0)));
+        assert!(!grande.intersecta(&vazio) && !vazio.intersecta(&circulo));
+        assert!(grande.can_hold(&vazio) && circulo.can_hold(&vazio));
+        assert!(!vazio.can_hold(&circulo));
+
+        let ponto = Pontos(vec![Point::new(3.0, 3.0)]);
+        assert_eq!(ponto.limites(), Limites { min: Point::new(3.0, 3.0), max: Point::new(3.0, 3.0) });
+        assert!(grande.can_hold(&ponto) && ponto.intersecta(&grande));
+
+        let traco = Pontos(vec![Point::new(1.0, 1.0), Point::new(4.0, 5.0)]);
+        assert_eq!((traco.area(), traco.perimeter()), (0.0, 5.0));
+        assert!(grande.can_hold(&traco) && !traco.intersecta(&Circle { centro: Point::new(8.0, 1.0), raio: 1.0 }));
+    }
+
+    #[test]
+    fn test_envoltoria() {
+        let pontos: Vec<Point> = [(0.0, 0.0), (2.0, 0.0), (1.0, 1.0), (2.0, 2.0), (0.0, 2.0), (1.0, 0.0), (0.5, 1.5)]
+            .iter()
+            .map(|&(x, y)| Point::new(x, y))
+            .collect();
+        let fecho = envoltoria_convexa(&pontos).unwrap();
+        assert_eq!(fecho, quadrado(0.0, 0.0, 2.0));
+        assert!(fecho.eh_convexo());
+        assert!(envoltoria_convexa(&[Point::new(0.0, 0.0), Point::new(1.0, 1.0), Point::new(2.0, 2.0)]).is_none());
+    }
+
+    #[test]
+    fn test_transformacoes() {
+        let giro = Afim::rotacao_em(Point::new(1.0, 1.0), PI / 2.0);
+        let p = giro.aplicar(Point::new(2.0, 1.0));
+        assert!(perto(p.x, 1.0) && perto(p.y, 2.0));
+        let volta = giro.inversa().unwrap().aplicar(p);
+        assert!(perto(volta.x, 2.0) && perto(volta.y, 1.0));
+        assert_eq!(Afim::escala(0.0, 1.0).inversa(), None);
+
+        let circulo = Circle { centro: Point::new(0.0, 0.0), raio: 1.0 };
+        let dobro = circulo.transformar(&Afim::escala(2.0, 2.0).depois(&Afim::translacao(3.0, 0.0)));
+        assert_eq!(dobro.contorno(), Contorno::Circulo(Point::new(3.0, 0.0), 2.0));
+        // Escala desigual: elipse aproximada, área perto de π·2·1
+        let elipse = circulo.transformar(&Afim::escala(2.0, 1.0));
+        assert!(matches!(elipse.contorno(), Contorno::Poligono(_)));
+        assert!((elipse.area() - 2.0 * PI).abs() < 0.05);
+
+        let retangulo = RotatedRectangle { center: Point::new(0.0, 0.0), width: 4.0, height: 2.0, angle: 0.0 };
+        let girado = retangulo.transformar(&Afim::rotacao(PI / 2.0));
+        assert!(perto(girado.limites().altura(), 4.0));
+        let cisalhado = retangulo.transformar(&Afim::cisalhamento(1.0, 0.0));
+        assert!(perto(cisalhado.area(), 8.0));
+        assert!(perto(cisalhado.limites().largura(), 6.0));
+    }
+}
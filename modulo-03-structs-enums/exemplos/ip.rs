@@ -0,0 +1,506 @@
+// Endereços IP de verdade em volta do enum IpAddr: leitura e validação,
+// forma canônica (RFC 5952 para IPv6), redes CIDR e classificação.
+
+use std::fmt;
+use std::net;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IpAddr {
+    V4(u8, u8, u8, u8),
+    // Oito grupos de 16 bits e a zona opcional, que diz por qual interface
+    // sair em endereços link-local (o "eth0" de fe80::1%eth0)
+    V6([u16; 8], Option<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErroIp {
+    OctetoInvalido(String),
+    QuantidadeDeOctetos(usize),
+    GrupoInvalido(String),
+    QuantidadeDeGrupos(usize),
+    // "::" só pode aparecer uma vez
+    CompressaoDupla,
+    ZonaVazia,
+    PrefixoInvalido(String),
+    PrefixoForaDoLimite { prefixo: u8, maximo: u8 },
+    // Ex: perguntar se uma rede IPv4 contém um endereço IPv6
+    VersoesDiferentes,
+}
+
+impl fmt::Display for ErroIp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroIp::OctetoInvalido(texto) => write!(f, "octeto inválido: '{}' (esperado 0 a 255, sem zeros à esquerda)", texto),
+            ErroIp::QuantidadeDeOctetos(n) => write!(f, "IPv4 tem 4 octetos, não {}", n),
+            ErroIp::GrupoInvalido(texto) => write!(f, "grupo IPv6 inválido: '{}' (esperado 1 a 4 dígitos hexadecimais)", texto),
+            ErroIp::QuantidadeDeGrupos(n) => write!(f, "IPv6 tem 8 grupos, não {}", n),
+            ErroIp::CompressaoDupla => write!(f, "'::' só pode aparecer uma vez"),
+            ErroIp::ZonaVazia => write!(f, "zona vazia depois de '%'"),
+            ErroIp::PrefixoInvalido(texto) => write!(f, "prefixo inválido: '{}'", texto),
+            ErroIp::PrefixoForaDoLimite { prefixo, maximo } => write!(f, "prefixo /{} maior que /{}", prefixo, maximo),
+            ErroIp::VersoesDiferentes => write!(f, "não dá para misturar IPv4 e IPv6"),
+        }
+    }
+}
+
+impl std::error::Error for ErroIp {}
+
+// ---------- Leitura ----------
+
+impl FromStr for IpAddr {
+    type Err = ErroIp;
+
+    fn from_str(texto: &str) -> Result<IpAddr, ErroIp> {
+        if texto.contains(':') {
+            ler_v6(texto)
+        } else {
+            let [a, b, c, d] = ler_v4(texto)?;
+            Ok(IpAddr::V4(a, b, c, d))
+        }
+    }
+}
+
+fn ler_v4(texto: &str) -> Result<[u8; 4], ErroIp> {
+    let partes: Vec<&str> = texto.split('.').collect();
+    if partes.len() != 4 {
+        return Err(ErroIp::QuantidadeDeOctetos(partes.len()));
+    }
+    let mut octetos = [0; 4];
+    for (octeto, parte) in octetos.iter_mut().zip(&partes) {
+        // "010" seria octal em algumas implementações: melhor recusar
+        let valido = !parte.is_empty()
+            && parte.len() <= 3
+            && parte.bytes().all(|b| b.is_ascii_digit())
+            && !(parte.len() > 1 && parte.starts_with('0'));
+        *octeto = parte
+            .parse()
+            .ok()
+            .filter(|_| valido)
+            .ok_or_else(|| ErroIp::OctetoInvalido(parte.to_string()))?;
+    }
+    Ok(octetos)
+}
+
+fn ler_v6(texto: &str) -> Result<IpAddr, ErroIp> {
+    let (endereco, zona) = match texto.split_once('%') {
+        Some((_, "")) => return Err(ErroIp::ZonaVazia),
+        Some((endereco, zona)) => (endereco, Some(zona.to_string())),
+        None => (texto, None),
+    };
+
+    // Com "::", os grupos antes e depois da compressão; os que faltam são
+    // zeros. Só o fim do endereço inteiro pode ser um IPv4, nunca o de `antes`.
+    let (antes, depois) = match endereco.split_once("::") {
+        Some((_, depois)) if depois.contains("::") => return Err(ErroIp::CompressaoDupla),
+        Some((antes, depois)) => (ler_grupos(antes, false)?, Some(ler_grupos(depois, true)?)),
+        None => (ler_grupos(endereco, true)?, None),
+    };
+
+    let mut segmentos = [0; 8];
+    match depois {
+        None if antes.len() == 8 => segmentos.copy_from_slice(&antes),
+        None => return Err(ErroIp::QuantidadeDeGrupos(antes.len())),
+        // "::" vale por pelo menos um grupo
+        Some(depois) if antes.len() + depois.len() <= 7 => {
+            segmentos[..antes.len()].copy_from_slice(&antes);
+            segmentos[8 - depois.len()..].copy_from_slice(&depois);
+        }
+        Some(depois) => return Err(ErroIp::QuantidadeDeGrupos(antes.len() + depois.len() + 1)),
+    }
+    Ok(IpAddr::V6(segmentos, zona))
+}
+
+// Grupos separados por ':'; com `aceita_v4`, o último pode ser um IPv4
+// (::ffff:192.0.2.1)
+fn ler_grupos(texto: &str, aceita_v4: bool) -> Result<Vec<u16>, ErroIp> {
+    if texto.is_empty() {
+        return Ok(Vec::new());
+    }
+    let partes: Vec<&str> = texto.split(':').collect();
+    let mut grupos = Vec::new();
+    for (i, parte) in partes.iter().enumerate() {
+        if aceita_v4 && i == partes.len() - 1 && parte.contains('.') {
+            let [a, b, c, d] = ler_v4(parte)?;
+            grupos.push(u16::from_be_bytes([a, b]));
+            grupos.push(u16::from_be_bytes([c, d]));
+        } else if (1..=4).contains(&parte.len()) && parte.bytes().all(|b| b.is_ascii_hexdigit()) {
+            grupos.push(u16::from_str_radix(parte, 16).expect("só dígitos hexadecimais"));
+        } else {
+            return Err(ErroIp::GrupoInvalido(parte.to_string()));
+        }
+    }
+    Ok(grupos)
+}
+
+// ---------- Forma canônica ----------
+
+impl fmt::Display for IpAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IpAddr::V4(a, b, c, d) => write!(f, "{}.{}.{}.{}", a, b, c, d),
+            IpAddr::V6(segmentos, zona) => {
+                escrever_v6(f, segmentos)?;
+                match zona {
+                    Some(zona) => write!(f, "%{}", zona),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+// RFC 5952: hexadecimal minúsculo sem zeros à esquerda, a maior sequência de
+// dois ou mais grupos zerados vira "::" (a primeira, em caso de empate), e
+// IPv4 mapeado em IPv6 termina com os quatro octetos
+fn escrever_v6(f: &mut fmt::Formatter, segmentos: &[u16; 8]) -> fmt::Result {
+    if segmentos[..6] == [0, 0, 0, 0, 0, 0xffff] {
+        let [a, b] = segmentos[6].to_be_bytes();
+        let [c, d] = segmentos[7].to_be_bytes();
+        return write!(f, "::ffff:{}.{}.{}.{}", a, b, c, d);
+    }
+
+    // (início, tamanho) da maior sequência de zeros
+    let mut maior = (0, 0);
+    let mut i = 0;
+    while i < 8 {
+        let tamanho = segmentos[i..].iter().take_while(|&&grupo| grupo == 0).count();
+        if tamanho > maior.1 {
+            maior = (i, tamanho);
+        }
+        i += tamanho.max(1);
+    }
+
+    let escrever = |f: &mut fmt::Formatter, grupos: &[u16]| -> fmt::Result {
+        let textos: Vec<String> = grupos.iter().map(|grupo| format!("{:x}", grupo)).collect();
+        write!(f, "{}", textos.join(":"))
+    };
+    match maior {
+        (inicio, tamanho) if tamanho >= 2 => {
+            escrever(f, &segmentos[..inicio])?;
+            write!(f, "::")?;
+            escrever(f, &segmentos[inicio + tamanho..])
+        }
+        _ => escrever(f, segmentos),
+    }
+}
+
+// ---------- Bits ----------
+
+impl IpAddr {
+    pub fn largura(&self) -> u8 {
+        match self {
+            IpAddr::V4(..) => 32,
+            IpAddr::V6(..) => 128,
+        }
+    }
+
+    // O endereço como número, para as contas de máscara
+    pub fn bits(&self) -> u128 {
+        match self {
+            IpAddr::V4(a, b, c, d) => u32::from_be_bytes([*a, *b, *c, *d]) as u128,
+            IpAddr::V6(segmentos, _) => segmentos.iter().fold(0, |bits, &grupo| bits << 16 | grupo as u128),
+        }
+    }
+
+    // Mesma versão de `self`, outros bits; a zona não é copiada
+    fn com_bits(&self, bits: u128) -> IpAddr {
+        match self {
+            IpAddr::V4(..) => {
+                let [a, b, c, d] = (bits as u32).to_be_bytes();
+                IpAddr::V4(a, b, c, d)
+            }
+            IpAddr::V6(..) => {
+                let mut segmentos = [0; 8];
+                for (i, segmento) in segmentos.iter_mut().enumerate() {
+                    *segmento = (bits >> (16 * (7 - i))) as u16;
+                }
+                IpAddr::V6(segmentos, None)
+            }
+        }
+    }
+
+    pub fn categoria(&self) -> Categoria {
+        let dentro = |rede: &str| rede.parse::<Rede>().expect("rede fixa válida").contem(self).unwrap_or(false);
+        match self {
+            // IPv4 mapeado (::ffff:127.0.0.1) vale o que vale o IPv4
+            IpAddr::V6(segmentos, _) if segmentos[..6] == [0, 0, 0, 0, 0, 0xffff] => {
+                let [a, b] = segmentos[6].to_be_bytes();
+                let [c, d] = segmentos[7].to_be_bytes();
+                IpAddr::V4(a, b, c, d).categoria()
+            }
+            IpAddr::V4(0, 0, 0, 0) => Categoria::NaoEspecificado,
+            IpAddr::V4(255, 255, 255, 255) => Categoria::Broadcast,
+            IpAddr::V4(127, ..) => Categoria::Loopback,
+            IpAddr::V4(10, ..) | IpAddr::V4(192, 168, ..) => Categoria::Privado,
+            IpAddr::V4(172, b, ..) if (16..32).contains(b) => Categoria::Privado,
+            IpAddr::V4(169, 254, ..) => Categoria::LinkLocal,
+            IpAddr::V4(a, ..) if (224..240).contains(a) => Categoria::Multicast,
+            IpAddr::V4(..) if ["192.0.2.0/24", "198.51.100.0/24", "203.0.113.0/24"].iter().any(|rede| dentro(rede)) => {
+                Categoria::Documentacao
+            }
+            IpAddr::V6(segmentos, _) if segmentos == &[0; 8] => Categoria::NaoEspecificado,
+            IpAddr::V6(segmentos, _) if segmentos == &[0, 0, 0, 0, 0, 0, 0, 1] => Categoria::Loopback,
+            IpAddr::V6(..) if dentro("fc00::/7") => Categoria::Privado,
+            IpAddr::V6(..) if dentro("fe80::/10") => Categoria::LinkLocal,
+            IpAddr::V6(..) if dentro("ff00::/8") => Categoria::Multicast,
+            IpAddr::V6(..) if dentro("2001:db8::/32") => Categoria::Documentacao,
+            _ => Categoria::Global,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Categoria {
+    NaoEspecificado,
+    Loopback,
+    // 10/8, 172.16/12 e 192.168/16; em IPv6, os endereços locais únicos fc00::/7
+    Privado,
+    LinkLocal,
+    Multicast,
+    Broadcast,
+    // Faixas reservadas para exemplos em documentação
+    Documentacao,
+    Global,
+}
+
+// ---------- Redes CIDR ----------
+
+// Endereço de rede + tamanho do prefixo, ex: 192.168.1.0/24
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rede {
+    endereco: IpAddr,
+    prefixo: u8,
+}
+
+impl Rede {
+    // Os bits de host de `endereco` são zerados: 192.168.1.77/24 vira 192.168.1.0/24
+    pub fn new(endereco: IpAddr, prefixo: u8) -> Result<Rede, ErroIp> {
+        let maximo = endereco.largura();
+        if prefixo > maximo {
+            return Err(ErroIp::PrefixoForaDoLimite { prefixo, maximo });
+        }
+        let rede = Rede { endereco: endereco.clone(), prefixo };
+        Ok(Rede { endereco: endereco.com_bits(endereco.bits() & rede.mascara_bits()), prefixo })
+    }
+
+    pub fn prefixo(&self) -> u8 {
+        self.prefixo
+    }
+
+    fn mascara_bits(&self) -> u128 {
+        let largura = self.endereco.largura() as u32;
+        let uns = u128::MAX.checked_shl(largura - self.prefixo as u32).unwrap_or(0);
+        uns & (u128::MAX >> (128 - largura))
+    }
+
+    pub fn mascara(&self) -> IpAddr {
+        self.endereco.com_bits(self.mascara_bits())
+    }
+
+    pub fn endereco_de_rede(&self) -> IpAddr {
+        self.endereco.clone()
+    }
+
+    // Último endereço da rede; em IPv4 é o de broadcast
+    pub fn ultimo(&self) -> IpAddr {
+        let host = !self.mascara_bits() & (u128::MAX >> (128 - self.endereco.largura() as u32));
+        self.endereco.com_bits(self.endereco.bits() | host)
+    }
+
+    // IPv6 não tem broadcast
+    pub fn broadcast(&self) -> Option<IpAddr> {
+        match self.endereco {
+            IpAddr::V4(..) => Some(self.ultimo()),
+            IpAddr::V6(..) => None,
+        }
+    }
+
+    // Quantidade de endereços; um /0 IPv6 (2^128) satura em u128::MAX
+    pub fn tamanho(&self) -> u128 {
+        1u128.checked_shl((self.endereco.largura() - self.prefixo) as u32).unwrap_or(u128::MAX)
+    }
+
+    pub fn contem(&self, endereco: &IpAddr) -> Result<bool, ErroIp> {
+        if endereco.largura() != self.endereco.largura() {
+            return Err(ErroIp::VersoesDiferentes);
+        }
+        Ok(endereco.bits() & self.mascara_bits() == self.endereco.bits())
+    }
+
+    // As sub-redes de prefixo `novo_prefixo`, em ordem: 10.0.0.0/24 em /26
+    // dá 10.0.0.0/26, 10.0.0.64/26, 10.0.0.128/26 e 10.0.0.192/26
+    pub fn sub_redes(&self, novo_prefixo: u8) -> Result<impl Iterator<Item = Rede> + '_, ErroIp> {
+        let maximo = self.endereco.largura();
+        if novo_prefixo < self.prefixo || novo_prefixo > maximo {
+            return Err(ErroIp::PrefixoForaDoLimite { prefixo: novo_prefixo, maximo });
+        }
+        let passo = 1u128.checked_shl((maximo - novo_prefixo) as u32).unwrap_or(0);
+        let quantidade = 1u128.checked_shl((novo_prefixo - self.prefixo) as u32).unwrap_or(u128::MAX);
+        Ok((0..quantidade).map(move |i| Rede {
+            endereco: self.endereco.com_bits(self.endereco.bits() + i * passo),
+            prefixo: novo_prefixo,
+        }))
+    }
+}
+
+impl FromStr for Rede {
+    type Err = ErroIp;
+
+    // Sem "/", o prefixo é o endereço inteiro (/32 ou /128)
+    fn from_str(texto: &str) -> Result<Rede, ErroIp> {
+        let (endereco, prefixo) = match texto.split_once('/') {
+            Some((endereco, prefixo)) => {
+                let valido = !prefixo.is_empty() && prefixo.len() <= 3 && prefixo.bytes().all(|b| b.is_ascii_digit());
+                let prefixo = prefixo
+                    .parse()
+                    .ok()
+                    .filter(|_| valido)
+                    .ok_or_else(|| ErroIp::PrefixoInvalido(prefixo.to_string()))?;
+                (endereco.parse::<IpAddr>()?, Some(prefixo))
+            }
+            None => (texto.parse::<IpAddr>()?, None),
+        };
+        let prefixo = prefixo.unwrap_or(endereco.largura());
+        Rede::new(endereco, prefixo)
+    }
+}
+
+impl fmt::Display for Rede {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.endereco, self.prefixo)
+    }
+}
+
+// ---------- std::net ----------
+
+impl From<net::Ipv4Addr> for IpAddr {
+    fn from(endereco: net::Ipv4Addr) -> IpAddr {
+        let [a, b, c, d] = endereco.octets();
+        IpAddr::V4(a, b, c, d)
+    }
+}
+
+impl From<net::Ipv6Addr> for IpAddr {
+    fn from(endereco: net::Ipv6Addr) -> IpAddr {
+        IpAddr::V6(endereco.segments(), None)
+    }
+}
+
+impl From<net::IpAddr> for IpAddr {
+    fn from(endereco: net::IpAddr) -> IpAddr {
+        match endereco {
+            net::IpAddr::V4(endereco) => endereco.into(),
+            net::IpAddr::V6(endereco) => endereco.into(),
+        }
+    }
+}
+
+// std::net::IpAddr não tem zona: ela se perde na conversão
+impl From<&IpAddr> for net::IpAddr {
+    fn from(endereco: &IpAddr) -> net::IpAddr {
+        match endereco {
+            IpAddr::V4(a, b, c, d) => net::IpAddr::V4(net::Ipv4Addr::new(*a, *b, *c, *d)),
+            IpAddr::V6(segmentos, _) => net::IpAddr::V6(net::Ipv6Addr::from(*segmentos)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forma_canonica() {
+        // std::net segue a mesma RFC 5952: as duas formas devem bater
+        let enderecos = [
+            "0.0.0.0",
+            "192.168.0.1",
+            "::",
+            "::1",
+            "1::",
+            "2001:DB8:0:0:0:0:0:1",
+            "2001:db8:0:1:1:1:1:1",
+            "2001:0:0:1:0:0:0:1",
+            "2001:db8:0:0:1:0:0:1",
+            "::ffff:192.0.2.128",
+            "fe80::1:2:3:4",
+            "1:2:3:4:5:6:7::",
+        ];
+        for texto in enderecos {
+            let nosso: IpAddr = texto.parse().unwrap();
+            let padrao: net::IpAddr = texto.parse().unwrap();
+            assert_eq!(nosso.to_string(), padrao.to_string(), "{}", texto);
+            assert_eq!(net::IpAddr::from(&nosso), padrao);
+            assert_eq!(IpAddr::from(padrao), nosso);
+        }
+        assert_eq!("fe80::1%eth0".parse::<IpAddr>().unwrap(), IpAddr::V6([0xfe80, 0, 0, 0, 0, 0, 0, 1], Some("eth0".into())));
+        assert_eq!("FE80:0::01%eth0".parse::<IpAddr>().unwrap().to_string(), "fe80::1%eth0");
+    }
+
+    #[test]
+    fn test_enderecos_invalidos() {
+        let casos = [
+            ("256.0.0.1", ErroIp::OctetoInvalido("256".into())),
+            ("01.2.3.4", ErroIp::OctetoInvalido("01".into())),
+            ("1.2.3", ErroIp::QuantidadeDeOctetos(3)),
+            ("1::2::3", ErroIp::CompressaoDupla),
+            ("1:2:3:4:5:6:7", ErroIp::QuantidadeDeGrupos(7)),
+            ("1:2:3:4::5:6:7:8", ErroIp::QuantidadeDeGrupos(9)),
+            ("12345::", ErroIp::GrupoInvalido("12345".into())),
+            ("fe80::1%", ErroIp::ZonaVazia),
+            // IPv4 só no último grupo do endereço inteiro
+            ("1.2.3.4::", ErroIp::GrupoInvalido("1.2.3.4".into())),
+            ("1.2.3.4::1", ErroIp::GrupoInvalido("1.2.3.4".into())),
+        ];
+        for (texto, erro) in casos {
+            assert_eq!(texto.parse::<IpAddr>(), Err(erro), "{}", texto);
+        }
+    }
+
+    #[test]
+    fn test_redes() {
+        let rede: Rede = "192.168.1.77/26".parse().unwrap();
+        assert_eq!(rede.to_string(), "192.168.1.64/26");
+        assert_eq!(rede.mascara().to_string(), "255.255.255.192");
+        assert_eq!(rede.broadcast().unwrap().to_string(), "192.168.1.127");
+        assert_eq!(rede.tamanho(), 64);
+        assert_eq!(rede.contem(&"192.168.1.100".parse().unwrap()), Ok(true));
+        assert_eq!(rede.contem(&"192.168.1.128".parse().unwrap()), Ok(false));
+        assert_eq!(rede.contem(&"::1".parse().unwrap()), Err(ErroIp::VersoesDiferentes));
+
+        let sub_redes: Vec<String> = "10.0.0.0/24".parse::<Rede>().unwrap().sub_redes(26).unwrap().map(|rede| rede.to_string()).collect();
+        assert_eq!(sub_redes, ["10.0.0.0/26", "10.0.0.64/26", "10.0.0.128/26", "10.0.0.192/26"]);
+
+        let rede: Rede = "2001:db8:abcd:12::/64".parse().unwrap();
+        assert_eq!(rede.ultimo().to_string(), "2001:db8:abcd:12:ffff:ffff:ffff:ffff");
+        assert_eq!(rede.broadcast(), None);
+        assert_eq!("::/0".parse::<Rede>().unwrap().tamanho(), u128::MAX);
+        assert_eq!("0.0.0.0/0".parse::<Rede>().unwrap().mascara().to_string(), "0.0.0.0");
+        assert_eq!("10.0.0.0/33".parse::<Rede>(), Err(ErroIp::PrefixoForaDoLimite { prefixo: 33, maximo: 32 }));
+        assert_eq!("10.0.0.0/x".parse::<Rede>(), Err(ErroIp::PrefixoInvalido("x".into())));
+    }
+
+    #[test]
+    fn test_categorias() {
+        let casos = [
+            ("127.0.0.1", Categoria::Loopback),
+            ("172.20.1.1", Categoria::Privado),
+            ("172.32.1.1", Categoria::Global),
+            ("224.0.0.251", Categoria::Multicast),
+            ("198.51.100.7", Categoria::Documentacao),
+            ("255.255.255.255", Categoria::Broadcast),
+            ("::1", Categoria::Loopback),
+            ("fd12::1", Categoria::Privado),
+            ("fe80::1%eth0", Categoria::LinkLocal),
+            ("ff02::fb", Categoria::Multicast),
+            ("2606:4700::1111", Categoria::Global),
+            ("::ffff:127.0.0.1", Categoria::Loopback),
+            ("::ffff:10.1.2.3", Categoria::Privado),
+            ("::ffff:8.8.8.8", Categoria::Global),
+        ];
+        for (texto, categoria) in casos {
+            assert_eq!(texto.parse::<IpAddr>().unwrap().categoria(), categoria, "{}", texto);
+        }
+    }
+}
@@ -0,0 +1,390 @@
+// Máquina de estados genérica: o enum de estados e o enum de eventos do
+// cliente, mais uma tabela declarando quais transições são permitidas.
+//
+//     let maquina = Maquina::new(Catraca::Travada)
+//         .transicao("Travada", "Moeda", "Livre", |_, _| Catraca::Livre)
+//         .transicao("Livre", "Empurrar", "Travada", |_, _| Catraca::Travada);
+//
+// Estados e eventos podem carregar dados; a tabela olha só para o nome da
+// variante, e a ação da transição monta o novo estado a partir do antigo.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+// Nome da variante, usado na tabela de transições e no diagrama
+pub trait Nomeado {
+    fn nome(&self) -> &'static str;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErroTransicao {
+    // A tabela não tem nenhuma transição para esse evento nesse estado
+    Invalida { estado: &'static str, evento: &'static str },
+    // Existe transição, mas todas as guardas recusaram
+    Recusada { estado: &'static str, evento: &'static str, guardas: Vec<&'static str> },
+    // A ação montou um estado diferente do declarado na tabela; a máquina
+    // fica onde estava
+    DestinoErrado { esperado: &'static str, obtido: &'static str },
+}
+
+impl fmt::Display for ErroTransicao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroTransicao::Invalida { estado, evento } => write!(f, "'{}' não é permitido no estado {}", evento, estado),
+            ErroTransicao::Recusada { estado, evento, guardas } => {
+                write!(f, "'{}' recusado no estado {}: falhou [{}]", evento, estado, guardas.join(", "))
+            }
+            ErroTransicao::DestinoErrado { esperado, obtido } => {
+                write!(f, "a ação levou a {}, mas a tabela declara {}", obtido, esperado)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErroTransicao {}
+
+type Guarda<S, E> = Box<dyn Fn(&S, &E) -> bool>;
+type Acao<S, E> = Box<dyn Fn(&S, &E) -> S>;
+type Gancho<S> = Box<dyn FnMut(&S)>;
+
+struct Transicao<S, E> {
+    de: &'static str,
+    evento: &'static str,
+    para: &'static str,
+    // Descrição (para o diagrama e os erros) e condição
+    guarda: Option<(&'static str, Guarda<S, E>)>,
+    acao: Acao<S, E>,
+}
+
+// Uma linha do histórico: cada evento disparado, aceito ou não
+#[derive(Debug, Clone, PartialEq)]
+pub struct Registro {
+    pub de: String,
+    pub evento: String,
+    pub resultado: Result<String, ErroTransicao>,
+}
+
+impl fmt::Display for Registro {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.resultado {
+            Ok(para) => write!(f, "{} --{}--> {}", self.de, self.evento, para),
+            Err(erro) => write!(f, "{} --{}--> (recusado: {})", self.de, self.evento, erro),
+        }
+    }
+}
+
+pub struct Maquina<S, E> {
+    estado: S,
+    inicial: &'static str,
+    transicoes: Vec<Transicao<S, E>>,
+    ao_entrar: Vec<(&'static str, Gancho<S>)>,
+    ao_sair: Vec<(&'static str, Gancho<S>)>,
+    historico: Vec<Registro>,
+}
+
+impl<S: Nomeado + fmt::Debug, E: Nomeado + fmt::Debug> Maquina<S, E> {
+    pub fn new(inicial: S) -> Self {
+        Maquina {
+            inicial: inicial.nome(),
+            estado: inicial,
+            transicoes: Vec::new(),
+            ao_entrar: Vec::new(),
+            ao_sair: Vec::new(),
+            historico: Vec::new(),
+        }
+    }
+
+    pub fn transicao(
+        mut self,
+        de: &'static str,
+        evento: &'static str,
+        para: &'static str,
+        acao: impl Fn(&S, &E) -> S + 'static,
+    ) -> Self {
+        self.transicoes.push(Transicao { de, evento, para, guarda: None, acao: Box::new(acao) });
+        self
+    }
+
+    // Com várias transições para o mesmo estado e evento, vale a primeira
+    // declarada cuja guarda aceitar
+    pub fn transicao_com_guarda(
+        mut self,
+        de: &'static str,
+        evento: &'static str,
+        para: &'static str,
+        guarda: (&'static str, impl Fn(&S, &E) -> bool + 'static),
+        acao: impl Fn(&S, &E) -> S + 'static,
+    ) -> Self {
+        let (descricao, condicao) = guarda;
+        let guarda: Guarda<S, E> = Box::new(condicao);
+        self.transicoes.push(Transicao { de, evento, para, guarda: Some((descricao, guarda)), acao: Box::new(acao) });
+        self
+    }
+
+    // Os ganchos só rodam quando o nome do estado muda: uma transição de um
+    // estado para ele mesmo (ex: somar pontos) não sai nem entra de novo
+    pub fn ao_entrar(mut self, estado: &'static str, gancho: impl FnMut(&S) + 'static) -> Self {
+        self.ao_entrar.push((estado, Box::new(gancho)));
+        self
+    }
+
+    pub fn ao_sair(mut self, estado: &'static str, gancho: impl FnMut(&S) + 'static) -> Self {
+        self.ao_sair.push((estado, Box::new(gancho)));
+        self
+    }
+
+    pub fn estado(&self) -> &S {
+        &self.estado
+    }
+
+    pub fn historico(&self) -> &[Registro] {
+        &self.historico
+    }
+
+    pub fn disparar(&mut self, evento: E) -> Result<&S, ErroTransicao> {
+        let de = self.estado.nome();
+        let candidatas: Vec<&Transicao<S, E>> =
+            self.transicoes.iter().filter(|t| t.de == de && t.evento == evento.nome()).collect();
+
+        let escolhida = candidatas.iter().find(|t| match &t.guarda {
+            Some((_, condicao)) => condicao(&self.estado, &evento),
+            None => true,
+        });
+        let novo = match escolhida {
+            Some(transicao) => {
+                let novo = (transicao.acao)(&self.estado, &evento);
+                if novo.nome() == transicao.para {
+                    Ok(novo)
+                } else {
+                    Err(ErroTransicao::DestinoErrado { esperado: transicao.para, obtido: novo.nome() })
+                }
+            }
+            None if candidatas.is_empty() => Err(ErroTransicao::Invalida { estado: de, evento: evento.nome() }),
+            None => Err(ErroTransicao::Recusada {
+                estado: de,
+                evento: evento.nome(),
+                guardas: candidatas.iter().filter_map(|t| t.guarda.as_ref().map(|(descricao, _)| *descricao)).collect(),
+            }),
+        };
+
+        let mut registro = Registro { de: format!("{:?}", self.estado), evento: format!("{:?}", evento), resultado: Ok(String::new()) };
+        let novo = match novo {
+            Ok(novo) => novo,
+            Err(erro) => {
+                registro.resultado = Err(erro.clone());
+                self.historico.push(registro);
+                return Err(erro);
+            }
+        };
+
+        let mudou = novo.nome() != de;
+        if mudou {
+            for (_, gancho) in self.ao_sair.iter_mut().filter(|(estado, _)| *estado == de) {
+                gancho(&self.estado);
+            }
+        }
+        self.estado = novo;
+        if mudou {
+            let para = self.estado.nome();
+            for (_, gancho) in self.ao_entrar.iter_mut().filter(|(estado, _)| *estado == para) {
+                gancho(&self.estado);
+            }
+        }
+        registro.resultado = Ok(format!("{:?}", self.estado));
+        self.historico.push(registro);
+        Ok(&self.estado)
+    }
+
+    // A tabela como diagrama Graphviz: `dot -Tpng maquina.dot -o maquina.png`.
+    // Estados sem saída aparecem com contorno duplo.
+    pub fn para_dot(&self, nome: &str) -> String {
+        let mut estados = BTreeSet::from([self.inicial]);
+        for transicao in &self.transicoes {
+            estados.insert(transicao.de);
+            estados.insert(transicao.para);
+        }
+
+        let mut dot = format!("digraph {} {{\n    rankdir=LR;\n    __inicio [shape=point];\n", entre_aspas(nome));
+        for estado in &estados {
+            let forma = if self.transicoes.iter().any(|t| t.de == *estado) { "circle" } else { "doublecircle" };
+            dot.push_str(&format!("    {} [shape={}];\n", entre_aspas(estado), forma));
+        }
+        dot.push_str(&format!("    __inicio -> {};\n", entre_aspas(self.inicial)));
+        for transicao in &self.transicoes {
+            let rotulo = match &transicao.guarda {
+                Some((descricao, _)) => format!("{} [{}]", transicao.evento, descricao),
+                None => transicao.evento.to_string(),
+            };
+            dot.push_str(&format!(
+                "    {} -> {} [label={}];\n",
+                entre_aspas(transicao.de),
+                entre_aspas(transicao.para),
+                entre_aspas(&rotulo)
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// Identificador do DOT entre aspas: aceita espaços, hífens e palavras
+// reservadas como "node" ou "graph"
+fn entre_aspas(texto: &str) -> String {
+    format!("\"{}\"", texto.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // Catraca de metrô: a moeda destrava, passar trava de novo
+    #[derive(Debug, Clone, PartialEq)]
+    enum Catraca {
+        Travada { moedas: u32 },
+        Livre,
+        Quebrada,
+    }
+
+    #[derive(Debug)]
+    enum Gesto {
+        Moeda(u32),
+        Empurrar,
+        Chutar,
+    }
+
+    impl Nomeado for Catraca {
+        fn nome(&self) -> &'static str {
+            match self {
+                Catraca::Travada { .. } => "Travada",
+                Catraca::Livre => "Livre",
+                Catraca::Quebrada => "Quebrada",
+            }
+        }
+    }
+
+    impl Nomeado for Gesto {
+        fn nome(&self) -> &'static str {
+            match self {
+                Gesto::Moeda(_) => "Moeda",
+                Gesto::Empurrar => "Empurrar",
+                Gesto::Chutar => "Chutar",
+            }
+        }
+    }
+
+    fn catraca(eventos: Rc<RefCell<Vec<String>>>) -> Maquina<Catraca, Gesto> {
+        let (entrada, saida) = (eventos.clone(), eventos);
+        Maquina::new(Catraca::Travada { moedas: 0 })
+            .transicao_com_guarda(
+                "Travada",
+                "Moeda",
+                "Livre",
+                ("valor >= 5", |_: &Catraca, gesto: &Gesto| matches!(gesto, Gesto::Moeda(valor) if *valor >= 5)),
+                |_, _| Catraca::Livre,
+            )
+            .transicao("Livre", "Empurrar", "Travada", |_, _| Catraca::Travada { moedas: 0 })
+            .transicao("Travada", "Chutar", "Quebrada", |_, _| Catraca::Quebrada)
+            .ao_entrar("Livre", move |_| entrada.borrow_mut().push("destravou".to_string()))
+            .ao_sair("Livre", move |_| saida.borrow_mut().push("travou".to_string()))
+    }
+
+    #[test]
+    fn test_transicoes_e_ganchos() {
+        let eventos = Rc::new(RefCell::new(Vec::new()));
+        let mut maquina = catraca(eventos.clone());
+
+        assert_eq!(
+            maquina.disparar(Gesto::Moeda(1)),
+            Err(ErroTransicao::Recusada { estado: "Travada", evento: "Moeda", guardas: vec!["valor >= 5"] })
+        );
+        assert_eq!(maquina.disparar(Gesto::Empurrar), Err(ErroTransicao::Invalida { estado: "Travada", evento: "Empurrar" }));
+        assert_eq!(maquina.disparar(Gesto::Moeda(5)), Ok(&Catraca::Livre));
+        assert_eq!(maquina.disparar(Gesto::Empurrar), Ok(&Catraca::Travada { moedas: 0 }));
+        assert_eq!(maquina.disparar(Gesto::Chutar), Ok(&Catraca::Quebrada));
+        assert!(maquina.disparar(Gesto::Moeda(10)).is_err());
+        assert_eq!(*eventos.borrow(), ["destravou", "travou"]);
+
+        let historico: Vec<String> = maquina.historico().iter().map(|registro| registro.to_string()).collect();
+        assert_eq!(historico.len(), 6);
+        assert_eq!(historico[2], "Travada { moedas: 0 } --Moeda(5)--> Livre");
+        assert_eq!(historico[5], "Quebrada --Moeda(10)--> (recusado: 'Moeda' não é permitido no estado Quebrada)");
+    }
+
+    #[test]
+    fn test_dot() {
+        let dot = catraca(Rc::default()).para_dot("catraca");
+        assert!(dot.starts_with("digraph \"catraca\" {"));
+        assert!(dot.contains("__inicio -> \"Travada\";"));
+        assert!(dot.contains("\"Travada\" -> \"Livre\" [label=\"Moeda [valor >= 5]\"];"));
+        assert!(dot.contains("\"Quebrada\" [shape=doublecircle];"));
+        assert!(dot.contains("\"Livre\" [shape=circle];"));
+        assert!(catraca(Rc::default()).para_dot("meu \"grafo\"").starts_with("digraph \"meu \\\"grafo\\\"\" {"));
+    }
+
+    #[test]
+    fn test_transicao_para_o_mesmo_estado() {
+        let eventos = Rc::new(RefCell::new(Vec::new()));
+        let (entrada, saida) = (eventos.clone(), eventos.clone());
+        let mut maquina = Maquina::new(Catraca::Travada { moedas: 0 })
+            .transicao("Travada", "Moeda", "Travada", |estado, gesto| match (estado, gesto) {
+                (Catraca::Travada { moedas }, Gesto::Moeda(valor)) => Catraca::Travada { moedas: moedas + valor },
+                _ => estado.clone(),
+            })
+            .ao_entrar("Travada", move |_| entrada.borrow_mut().push("entrou"))
+            .ao_sair("Travada", move |_| saida.borrow_mut().push("saiu"));
+
+        maquina.disparar(Gesto::Moeda(2)).unwrap();
+        assert_eq!(maquina.disparar(Gesto::Moeda(3)), Ok(&Catraca::Travada { moedas: 5 }));
+        assert!(eventos.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_varias_guardas() {
+        // Vale a primeira declarada que aceitar, mesmo que outra também aceite
+        let mut maquina = Maquina::new(Catraca::Travada { moedas: 0 })
+            .transicao_com_guarda(
+                "Travada",
+                "Moeda",
+                "Quebrada",
+                ("valor > 100", |_: &Catraca, gesto: &Gesto| matches!(gesto, Gesto::Moeda(valor) if *valor > 100)),
+                |_, _| Catraca::Quebrada,
+            )
+            .transicao_com_guarda(
+                "Travada",
+                "Moeda",
+                "Livre",
+                ("valor >= 5", |_: &Catraca, gesto: &Gesto| matches!(gesto, Gesto::Moeda(valor) if *valor >= 5)),
+                |_, _| Catraca::Livre,
+            )
+            .transicao("Livre", "Empurrar", "Travada", |_, _| Catraca::Travada { moedas: 0 });
+
+        assert_eq!(
+            maquina.disparar(Gesto::Moeda(1)),
+            Err(ErroTransicao::Recusada { estado: "Travada", evento: "Moeda", guardas: vec!["valor > 100", "valor >= 5"] })
+        );
+        assert_eq!(maquina.disparar(Gesto::Moeda(10)), Ok(&Catraca::Livre));
+        maquina.disparar(Gesto::Empurrar).unwrap();
+        assert_eq!(maquina.disparar(Gesto::Moeda(500)), Ok(&Catraca::Quebrada));
+    }
+
+    #[test]
+    fn test_acao_com_destino_errado() {
+        let eventos = Rc::new(RefCell::new(Vec::new()));
+        let saida = eventos.clone();
+        // A tabela diz Livre, mas a ação monta Quebrada
+        let mut maquina = Maquina::new(Catraca::Travada { moedas: 0 })
+            .transicao("Travada", "Moeda", "Livre", |_, _| Catraca::Quebrada)
+            .ao_sair("Travada", move |_| saida.borrow_mut().push("saiu"));
+
+        assert_eq!(
+            maquina.disparar(Gesto::Moeda(5)),
+            Err(ErroTransicao::DestinoErrado { esperado: "Livre", obtido: "Quebrada" })
+        );
+        assert_eq!(maquina.estado(), &Catraca::Travada { moedas: 0 });
+        assert!(eventos.borrow().is_empty());
+        assert_eq!(maquina.historico().len(), 1);
+        assert!(maquina.historico()[0].resultado.is_err());
+    }
+}
@@ -0,0 +1,352 @@
+// Message como protocolo de comandos de verdade, com três formatos:
+//
+// - binário, em quadros: [versão: u8][tamanho: u32 BE][tipo: u8][campos...],
+//   onde `tamanho` conta os bytes depois do cabeçalho (tipo + campos);
+// - texto, uma mensagem por linha: "QUIT", "MOVE 10 -20", "WRITE olá",
+//   "COLOR 255 0 0" (em WRITE, \n, \r e \ viram \\n, \\r e \\\\);
+// - JSON, pelo serde: "Quit", {"Move":{"x":10,"y":-20}}, {"Write":"olá"},
+//   {"ChangeColor":[255,0,0]}.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+    ChangeColor(i32, i32, i32),
+}
+
+pub const VERSAO: u8 = 1;
+const CABECALHO: usize = 5;
+// Um quadro maior que isso é recusado antes de esperar pelo resto dos bytes
+pub const TAMANHO_MAXIMO: u32 = 1 << 20;
+
+const TIPO_QUIT: u8 = 0;
+const TIPO_MOVE: u8 = 1;
+const TIPO_WRITE: u8 = 2;
+const TIPO_CHANGE_COLOR: u8 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErroProtocolo {
+    // Faltam bytes: chegou só parte do quadro, dá para tentar de novo com mais
+    Truncado { esperado: usize, recebido: usize },
+    VersaoDesconhecida(u8),
+    // Corpo (tipo + campos) maior que TAMANHO_MAXIMO, ao ler ou ao escrever
+    QuadroGrande(usize),
+    QuadroVazio,
+    TipoDesconhecido(u8),
+    // O tamanho do quadro não bate com os campos do tipo
+    CamposIncompletos { tipo: u8 },
+    BytesSobrando { tipo: u8, sobra: usize },
+    TextoNaoUtf8,
+    ComandoVazio,
+    ComandoDesconhecido(String),
+    Argumentos { comando: &'static str, esperado: usize, recebido: usize },
+    NumeroInvalido(String),
+    EscapeInvalido(String),
+}
+
+impl fmt::Display for ErroProtocolo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErroProtocolo::Truncado { esperado, recebido } => write!(f, "quadro truncado: esperados {} bytes, recebidos {}", esperado, recebido),
+            ErroProtocolo::VersaoDesconhecida(versao) => write!(f, "versão {} desconhecida (esperada {})", versao, VERSAO),
+            ErroProtocolo::QuadroGrande(tamanho) => write!(f, "quadro de {} bytes passa do máximo de {}", tamanho, TAMANHO_MAXIMO),
+            ErroProtocolo::QuadroVazio => write!(f, "quadro sem tipo de mensagem"),
+            ErroProtocolo::TipoDesconhecido(tipo) => write!(f, "tipo de mensagem {} desconhecido", tipo),
+            ErroProtocolo::CamposIncompletos { tipo } => write!(f, "faltam campos na mensagem do tipo {}", tipo),
+            ErroProtocolo::BytesSobrando { tipo, sobra } => write!(f, "{} bytes sobrando na mensagem do tipo {}", sobra, tipo),
+            ErroProtocolo::TextoNaoUtf8 => write!(f, "texto não é UTF-8 válido"),
+            ErroProtocolo::ComandoVazio => write!(f, "linha vazia"),
+            ErroProtocolo::ComandoDesconhecido(comando) => write!(f, "comando '{}' desconhecido", comando),
+            ErroProtocolo::Argumentos { comando, esperado, recebido } => {
+                write!(f, "{} espera {} argumentos, recebeu {}", comando, esperado, recebido)
+            }
+            ErroProtocolo::NumeroInvalido(texto) => write!(f, "número inválido: '{}'", texto),
+            ErroProtocolo::EscapeInvalido(escape) => write!(f, "escape inválido: '{}'", escape),
+        }
+    }
+}
+
+impl std::error::Error for ErroProtocolo {}
+
+// ---------- Binário ----------
+
+impl Message {
+    // Recusa o que o outro lado recusaria: um Write com mais de
+    // TAMANHO_MAXIMO bytes nem caberia no campo de tamanho se passasse de u32
+    pub fn codificar(&self) -> Result<Vec<u8>, ErroProtocolo> {
+        let mut corpo = Vec::new();
+        match self {
+            Message::Quit => corpo.push(TIPO_QUIT),
+            Message::Move { x, y } => {
+                corpo.push(TIPO_MOVE);
+                corpo.extend_from_slice(&x.to_be_bytes());
+                corpo.extend_from_slice(&y.to_be_bytes());
+            }
+            Message::Write(texto) => {
+                corpo.push(TIPO_WRITE);
+                corpo.extend_from_slice(texto.as_bytes());
+            }
+            Message::ChangeColor(r, g, b) => {
+                corpo.push(TIPO_CHANGE_COLOR);
+                for componente in [r, g, b] {
+                    corpo.extend_from_slice(&componente.to_be_bytes());
+                }
+            }
+        }
+        let tamanho = match u32::try_from(corpo.len()) {
+            Ok(tamanho) if tamanho <= TAMANHO_MAXIMO => tamanho,
+            _ => return Err(ErroProtocolo::QuadroGrande(corpo.len())),
+        };
+        let mut quadro = Vec::with_capacity(CABECALHO + corpo.len());
+        quadro.push(VERSAO);
+        quadro.extend_from_slice(&tamanho.to_be_bytes());
+        quadro.extend_from_slice(&corpo);
+        Ok(quadro)
+    }
+
+    // Lê o primeiro quadro de `bytes` e devolve a mensagem e quantos bytes
+    // ela ocupou; o que vem depois é o começo do próximo quadro
+    pub fn decodificar(bytes: &[u8]) -> Result<(Message, usize), ErroProtocolo> {
+        if bytes.len() < CABECALHO {
+            return Err(ErroProtocolo::Truncado { esperado: CABECALHO, recebido: bytes.len() });
+        }
+        if bytes[0] != VERSAO {
+            return Err(ErroProtocolo::VersaoDesconhecida(bytes[0]));
+        }
+        let tamanho = u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
+        if tamanho > TAMANHO_MAXIMO {
+            return Err(ErroProtocolo::QuadroGrande(tamanho as usize));
+        }
+        let total = CABECALHO + tamanho as usize;
+        if bytes.len() < total {
+            return Err(ErroProtocolo::Truncado { esperado: total, recebido: bytes.len() });
+        }
+
+        let (&tipo, campos) = bytes[CABECALHO..total].split_first().ok_or(ErroProtocolo::QuadroVazio)?;
+        let mut leitor = Leitor { tipo, campos };
+        let mensagem = match tipo {
+            TIPO_QUIT => Message::Quit,
+            TIPO_MOVE => Message::Move { x: leitor.i32()?, y: leitor.i32()? },
+            TIPO_WRITE => {
+                let texto = std::str::from_utf8(leitor.campos).map_err(|_| ErroProtocolo::TextoNaoUtf8)?;
+                leitor.campos = &[];
+                Message::Write(texto.to_string())
+            }
+            TIPO_CHANGE_COLOR => Message::ChangeColor(leitor.i32()?, leitor.i32()?, leitor.i32()?),
+            _ => return Err(ErroProtocolo::TipoDesconhecido(tipo)),
+        };
+        if !leitor.campos.is_empty() {
+            return Err(ErroProtocolo::BytesSobrando { tipo, sobra: leitor.campos.len() });
+        }
+        Ok((mensagem, total))
+    }
+}
+
+struct Leitor<'a> {
+    tipo: u8,
+    campos: &'a [u8],
+}
+
+impl Leitor<'_> {
+    fn i32(&mut self) -> Result<i32, ErroProtocolo> {
+        if self.campos.len() < 4 {
+            return Err(ErroProtocolo::CamposIncompletos { tipo: self.tipo });
+        }
+        let (numero, resto) = self.campos.split_at(4);
+        self.campos = resto;
+        Ok(i32::from_be_bytes([numero[0], numero[1], numero[2], numero[3]]))
+    }
+}
+
+// ---------- Texto ----------
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Message::Quit => write!(f, "QUIT"),
+            Message::Move { x, y } => write!(f, "MOVE {} {}", x, y),
+            Message::Write(texto) => {
+                write!(f, "WRITE ")?;
+                for c in texto.chars() {
+                    match c {
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\r' => write!(f, "\\r")?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                Ok(())
+            }
+            Message::ChangeColor(r, g, b) => write!(f, "COLOR {} {} {}", r, g, b),
+        }
+    }
+}
+
+impl FromStr for Message {
+    type Err = ErroProtocolo;
+
+    // Uma linha, com ou sem o \n (ou \r\n) do final; o comando pode vir em minúsculas
+    fn from_str(linha: &str) -> Result<Message, ErroProtocolo> {
+        let linha = linha.strip_suffix('\n').unwrap_or(linha);
+        let linha = linha.strip_suffix('\r').unwrap_or(linha);
+        let (comando, resto) = linha.split_once(' ').unwrap_or((linha, ""));
+        match comando.to_uppercase().as_str() {
+            "" => Err(ErroProtocolo::ComandoVazio),
+            "QUIT" => {
+                numeros::<0>("QUIT", resto)?;
+                Ok(Message::Quit)
+            }
+            "MOVE" => {
+                let [x, y] = numeros("MOVE", resto)?;
+                Ok(Message::Move { x, y })
+            }
+            // O texto vai até o fim da linha, espaços inclusive
+            "WRITE" => Ok(Message::Write(tirar_escapes(resto)?)),
+            "COLOR" => {
+                let [r, g, b] = numeros("COLOR", resto)?;
+                Ok(Message::ChangeColor(r, g, b))
+            }
+            _ => Err(ErroProtocolo::ComandoDesconhecido(comando.to_string())),
+        }
+    }
+}
+
+fn numeros<const N: usize>(comando: &'static str, argumentos: &str) -> Result<[i32; N], ErroProtocolo> {
+    let partes: Vec<&str> = argumentos.split_whitespace().collect();
+    if partes.len() != N {
+        return Err(ErroProtocolo::Argumentos { comando, esperado: N, recebido: partes.len() });
+    }
+    let mut numeros = [0; N];
+    for (numero, parte) in numeros.iter_mut().zip(partes) {
+        *numero = parte.parse().map_err(|_| ErroProtocolo::NumeroInvalido(parte.to_string()))?;
+    }
+    Ok(numeros)
+}
+
+fn tirar_escapes(texto: &str) -> Result<String, ErroProtocolo> {
+    let mut resultado = String::with_capacity(texto.len());
+    let mut caracteres = texto.chars();
+    while let Some(c) = caracteres.next() {
+        if c != '\\' {
+            resultado.push(c);
+            continue;
+        }
+        match caracteres.next() {
+            Some('\\') => resultado.push('\\'),
+            Some('n') => resultado.push('\n'),
+            Some('r') => resultado.push('\r'),
+            Some(outro) => return Err(ErroProtocolo::EscapeInvalido(format!("\\{}", outro))),
+            None => return Err(ErroProtocolo::EscapeInvalido("\\".to_string())),
+        }
+    }
+    Ok(resultado)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn mensagem() -> impl Strategy<Value = Message> {
+        prop_oneof![
+            Just(Message::Quit),
+            (any::<i32>(), any::<i32>()).prop_map(|(x, y)| Message::Move { x, y }),
+            any::<String>().prop_map(Message::Write),
+            (any::<i32>(), any::<i32>(), any::<i32>()).prop_map(|(r, g, b)| Message::ChangeColor(r, g, b)),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_binario_ida_e_volta(mensagem in mensagem()) {
+            let quadro = mensagem.codificar().unwrap();
+            prop_assert_eq!(Message::decodificar(&quadro), Ok((mensagem, quadro.len())));
+        }
+
+        #[test]
+        fn test_binario_truncado(mensagem in mensagem(), corte in any::<prop::sample::Index>()) {
+            let quadro = mensagem.codificar().unwrap();
+            let recebido = corte.index(quadro.len());
+            let truncado = matches!(
+                Message::decodificar(&quadro[..recebido]),
+                Err(ErroProtocolo::Truncado { recebido: r, .. }) if r == recebido
+            );
+            prop_assert!(truncado);
+        }
+
+        #[test]
+        fn test_binario_lixo(bytes in prop::collection::vec(any::<u8>(), 0..64)) {
+            // Qualquer sequência de bytes vira mensagem ou erro, nunca pânico
+            let _ = Message::decodificar(&bytes);
+        }
+
+        #[test]
+        fn test_texto_ida_e_volta(mensagem in mensagem()) {
+            let linha = mensagem.to_string();
+            prop_assert!(!linha.contains('\n'));
+            prop_assert_eq!(format!("{}\n", linha).parse::<Message>(), Ok(mensagem));
+        }
+
+        #[test]
+        fn test_json_ida_e_volta(mensagem in mensagem()) {
+            let json = serde_json::to_string(&mensagem).unwrap();
+            prop_assert_eq!(serde_json::from_str::<Message>(&json).unwrap(), mensagem);
+        }
+    }
+
+    #[test]
+    fn test_quadros_em_sequencia() {
+        let mut fluxo = Message::Move { x: 10, y: -20 }.codificar().unwrap();
+        assert_eq!(fluxo, [1, 0, 0, 0, 9, 1, 0, 0, 0, 10, 255, 255, 255, 236]);
+        fluxo.extend(Message::Write("olá".into()).codificar().unwrap());
+        fluxo.extend(Message::Quit.codificar().unwrap());
+
+        let mut lidas = Vec::new();
+        let mut resto = &fluxo[..];
+        while !resto.is_empty() {
+            let (mensagem, usados) = Message::decodificar(resto).unwrap();
+            lidas.push(mensagem);
+            resto = &resto[usados..];
+        }
+        assert_eq!(lidas, [Message::Move { x: 10, y: -20 }, Message::Write("olá".into()), Message::Quit]);
+    }
+
+    #[test]
+    fn test_tamanho_maximo() {
+        // O corpo é o byte do tipo mais o texto
+        let no_limite = Message::Write("a".repeat(TAMANHO_MAXIMO as usize - 1));
+        let quadro = no_limite.codificar().unwrap();
+        assert_eq!(quadro.len(), CABECALHO + TAMANHO_MAXIMO as usize);
+        assert_eq!(Message::decodificar(&quadro), Ok((no_limite, quadro.len())));
+
+        let acima = Message::Write("a".repeat(TAMANHO_MAXIMO as usize));
+        assert_eq!(acima.codificar(), Err(ErroProtocolo::QuadroGrande(TAMANHO_MAXIMO as usize + 1)));
+    }
+
+    #[test]
+    fn test_erros() {
+        assert_eq!(Message::decodificar(&[2, 0, 0, 0, 1, 0]), Err(ErroProtocolo::VersaoDesconhecida(2)));
+        assert_eq!(Message::decodificar(&[1, 0, 0, 0, 1, 9]), Err(ErroProtocolo::TipoDesconhecido(9)));
+        assert_eq!(Message::decodificar(&[1, 0, 0, 0, 0]), Err(ErroProtocolo::QuadroVazio));
+        assert_eq!(Message::decodificar(&[1, 0, 0, 0, 3, 1, 0, 0]), Err(ErroProtocolo::CamposIncompletos { tipo: 1 }));
+        assert_eq!(Message::decodificar(&[1, 0, 0, 0, 2, 0, 7]), Err(ErroProtocolo::BytesSobrando { tipo: 0, sobra: 1 }));
+        assert_eq!(Message::decodificar(&[1, 0, 0, 0, 2, 2, 0xff]), Err(ErroProtocolo::TextoNaoUtf8));
+        assert_eq!(Message::decodificar(&[1, 255, 0, 0, 0]), Err(ErroProtocolo::QuadroGrande(0xff00_0000)));
+
+        assert_eq!("move 1 2".parse(), Ok(Message::Move { x: 1, y: 2 }));
+        assert_eq!("WRITE  dois\\nespaços".parse(), Ok(Message::Write(" dois\nespaços".into())));
+        assert_eq!("".parse::<Message>(), Err(ErroProtocolo::ComandoVazio));
+        assert_eq!("JUMP 1".parse::<Message>(), Err(ErroProtocolo::ComandoDesconhecido("JUMP".into())));
+        assert_eq!(
+            "MOVE 1".parse::<Message>(),
+            Err(ErroProtocolo::Argumentos { comando: "MOVE", esperado: 2, recebido: 1 })
+        );
+        assert_eq!("COLOR 1 2 x".parse::<Message>(), Err(ErroProtocolo::NumeroInvalido("x".into())));
+        assert_eq!("WRITE a\\tb".parse::<Message>(), Err(ErroProtocolo::EscapeInvalido("\\t".into())));
+    }
+}
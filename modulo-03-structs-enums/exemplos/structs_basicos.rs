@@ -1,3 +1,8 @@
+mod geometria;
+
+use geometria::{Afim, Circle, Point, Polygon, Rectangle, RotatedRectangle, Segment, Shape, Triangle};
+use std::f64::consts::PI;
+
 #[derive(Debug)]
 struct User {
     username: String,
@@ -6,43 +11,6 @@ struct User {
     active: bool,
 }
 
-#[derive(Debug)]
-struct Point {
-    x: f64,
-    y: f64,
-}
-
-#[derive(Debug)]
-struct Rectangle {
-    width: f64,
-    height: f64,
-}
-
-impl Rectangle {
-    fn area(&self) -> f64 {
-        self.width * self.height
-    }
-    
-    fn can_hold(&self, other: &Rectangle) -> bool {
-        self.width > other.width && self.height > other.height
-    }
-    
-    fn square(size: f64) -> Rectangle {
-        Rectangle {
-            width: size,
-            height: size,
-        }
-    }
-    
-    fn perimeter(&self) -> f64 {
-        2.0 * (self.width + self.height)
-    }
-    
-    fn is_square(&self) -> bool {
-        self.width == self.height
-    }
-}
-
 // Tuple struct
 #[derive(Debug)]
 struct Color(i32, i32, i32);
@@ -146,6 +114,72 @@ fn main() {
     println!("Antes: {:?}", rect5);
     // Aqui poderíamos ter um método que modifica o retângulo
     println!("Depois: {:?}", rect5);
+    
+    demonstrar_geometria();
+}
+
+// Outras formas pelo trait Shape: interseção, contenção, fecho convexo e transformações
+fn demonstrar_geometria() {
+    println!("\n=== Geometria 2D ===");
+    let mut ponto = criar_ponto_origem();
+    mover_ponto(&mut ponto, 3.0, 4.0);
+    println!("Distância da origem a {:?}: {}", ponto, calcular_distancia(&criar_ponto_origem(), &ponto));
+    
+    let formas: Vec<Box<dyn Shape>> = vec![
+        Box::new(Rectangle { width: 30.0, height: 50.0 }),
+        Box::new(Circle { centro: Point::new(15.0, 25.0), raio: 10.0 }),
+        Box::new(Triangle { a: Point::new(25.0, 40.0), b: Point::new(45.0, 40.0), c: Point::new(35.0, 60.0) }),
+        Box::new(RotatedRectangle { center: Point::new(60.0, 10.0), width: 10.0, height: 4.0, angle: PI / 6.0 }),
+        Box::new(Segment { a: Point::new(-5.0, -5.0), b: Point::new(5.0, 5.0) }),
+    ];
+    for forma in &formas {
+        let limites = forma.limites();
+        println!("{:?}", forma);
+        println!("  área {:.2}, perímetro {:.2}, caixa {:.1}×{:.1} a partir de ({:.1}, {:.1})",
+            forma.area(),
+            forma.perimeter(),
+            limites.largura(),
+            limites.altura(),
+            limites.min.x,
+            limites.min.y);
+    }
+    
+    println!("\nInterseções e contenções:");
+    for (i, a) in formas.iter().enumerate() {
+        for (j, b) in formas.iter().enumerate().skip(i + 1) {
+            let caixa_comum = a.limites().intersecao(&b.limites()).map_or(0.0, |caixa| caixa.largura() * caixa.altura());
+            println!("  {} e {}: intersecta? {}, área comum das caixas {:.1}, {} contém {}? {}",
+                i, j, a.intersecta(b.as_ref()), caixa_comum, i, j, a.can_hold(b.as_ref()));
+        }
+    }
+    
+    // Um "U": o ponto na abertura está fora, mesmo entre as duas hastes
+    let u = Polygon::new(
+        [(0.0, 0.0), (6.0, 0.0), (6.0, 6.0), (4.0, 6.0), (4.0, 2.0), (2.0, 2.0), (2.0, 6.0), (0.0, 6.0)]
+            .iter()
+            .map(|&(x, y)| Point::new(x, y))
+            .collect(),
+    )
+    .expect("polígono com 8 vértices");
+    println!("\nPolígono em U (convexo? {})", u.eh_convexo());
+    for p in [Point::new(1.0, 5.0), Point::new(3.0, 5.0), Point::new(3.0, 1.0)] {
+        println!("  contém ({}, {})? {}", p.x, p.y, u.contem_ponto(p));
+    }
+    if let Some(fecho) = geometria::envoltoria_convexa(u.vertices()) {
+        println!("  fecho convexo: {:?} (área {} contra {})", fecho.vertices(), fecho.area(), u.area());
+    }
+    
+    let giro = Afim::rotacao_em(Point::new(15.0, 25.0), PI / 2.0);
+    println!("\nRetângulo 30×50 girado 90° em torno do centro: {:?}", Rectangle { width: 30.0, height: 50.0 }.transformar(&giro).limites());
+    let circulo = Circle { centro: Point::new(0.0, 0.0), raio: 1.0 };
+    println!("Círculo escalado por 3: {:?}", circulo.transformar(&Afim::escala(3.0, 3.0)));
+    println!("Círculo esticado (2, 1): área {:.3} (elipse: {:.3})", circulo.transformar(&Afim::escala(2.0, 1.0)).area(), 2.0 * PI);
+    let cisalhado = RotatedRectangle { center: Point::new(0.0, 0.0), width: 4.0, height: 2.0, angle: 0.0 }
+        .transformar(&Afim::cisalhamento(1.0, 0.0).depois(&Afim::translacao(10.0, 0.0)));
+    println!("Retângulo cisalhado: {:?}", cisalhado);
+    if let Some(volta) = giro.inversa() {
+        println!("Desfazendo o giro: {:?}", volta.aplicar(giro.aplicar(Point::new(0.0, 0.0))));
+    }
 }
 
 fn build_user(email: String, username: String) -> User {
@@ -159,9 +193,7 @@ fn build_user(email: String, username: String) -> User {
 
 // Função que trabalha com structs
 fn calcular_distancia(p1: &Point, p2: &Point) -> f64 {
-    let dx = p1.x - p2.x;
-    let dy = p1.y - p2.y;
-    (dx * dx + dy * dy).sqrt()
+    p1.distancia(*p2)
 }
 
 // Função que retorna struct